@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -15,6 +16,12 @@ pub struct Config {
     pub thresholds: Thresholds,
     #[serde(default)]
     pub export: ExportConfig,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    #[serde(default)]
+    pub latency: LatencyConfig,
+    #[serde(default)]
+    pub process: ProcessConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -85,6 +92,259 @@ pub struct ExportConfig {
     pub log_interval: u64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkConfig {
+    /// Interface globs to monitor; empty means every interface
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Interface globs to ignore, applied after `include`
+    #[serde(default = "default_network_exclude")]
+    pub exclude: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProbeMode {
+    /// ICMP echo through the system `ping` binary
+    Icmp,
+    /// TCP handshake time to `host:port`
+    Tcp,
+    Disabled,
+}
+
+impl ProbeMode {
+    pub fn name(&self) -> &str {
+        match self {
+            ProbeMode::Icmp => "icmp",
+            ProbeMode::Tcp => "tcp",
+            ProbeMode::Disabled => "disabled",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyConfig {
+    #[serde(default = "default_probe_mode")]
+    pub mode: ProbeMode,
+    /// Tried in order each round; the first one that answers is recorded
+    #[serde(default = "default_probe_targets")]
+    pub targets: Vec<String>,
+    /// Raised to `MIN_PROBE_INTERVAL` on load so the probe thread cannot spin
+    #[serde(
+        default = "default_probe_interval",
+        deserialize_with = "deserialize_probe_interval"
+    )]
+    pub interval: u64,
+    #[serde(default = "default_probe_timeout")]
+    pub timeout: u64,
+}
+
+/// A column of the process table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessColumn {
+    Pid,
+    Ppid,
+    User,
+    State,
+    Nice,
+    Threads,
+    Cpu,
+    MemPercent,
+    Rss,
+    Virt,
+    Shared,
+    ReadRate,
+    WriteRate,
+    MinorFaults,
+    MajorFaults,
+    StartTime,
+    CpuTime,
+    Name,
+    Command,
+    Cgroup,
+    Unit,
+    Container,
+    CpuGraph,
+    MemGraph,
+    IoGraph,
+}
+
+impl ProcessColumn {
+    pub const ALL: [ProcessColumn; 25] = [
+        ProcessColumn::Pid,
+        ProcessColumn::Ppid,
+        ProcessColumn::User,
+        ProcessColumn::State,
+        ProcessColumn::Nice,
+        ProcessColumn::Threads,
+        ProcessColumn::Cpu,
+        ProcessColumn::MemPercent,
+        ProcessColumn::Rss,
+        ProcessColumn::Virt,
+        ProcessColumn::Shared,
+        ProcessColumn::ReadRate,
+        ProcessColumn::WriteRate,
+        ProcessColumn::MinorFaults,
+        ProcessColumn::MajorFaults,
+        ProcessColumn::StartTime,
+        ProcessColumn::CpuTime,
+        ProcessColumn::Name,
+        ProcessColumn::Command,
+        ProcessColumn::Cgroup,
+        ProcessColumn::Unit,
+        ProcessColumn::Container,
+        ProcessColumn::CpuGraph,
+        ProcessColumn::MemGraph,
+        ProcessColumn::IoGraph,
+    ];
+
+    /// Table header
+    pub fn title(&self) -> &str {
+        match self {
+            ProcessColumn::Pid => "PID",
+            ProcessColumn::Ppid => "PPID",
+            ProcessColumn::User => "User",
+            ProcessColumn::State => "S",
+            ProcessColumn::Nice => "NI",
+            ProcessColumn::Threads => "Thr",
+            ProcessColumn::Cpu => "CPU",
+            ProcessColumn::MemPercent => "MEM%",
+            ProcessColumn::Rss => "Memory",
+            ProcessColumn::Virt => "Virt",
+            ProcessColumn::Shared => "Shared",
+            ProcessColumn::ReadRate => "Read/s",
+            ProcessColumn::WriteRate => "Write/s",
+            ProcessColumn::MinorFaults => "MinFlt/s",
+            ProcessColumn::MajorFaults => "MajFlt/s",
+            ProcessColumn::StartTime => "Start",
+            ProcessColumn::CpuTime => "CPU Time",
+            ProcessColumn::Name => "Process",
+            ProcessColumn::Command => "Command",
+            ProcessColumn::Cgroup => "Cgroup",
+            ProcessColumn::Unit => "Unit",
+            ProcessColumn::Container => "Container",
+            ProcessColumn::CpuGraph => "CPU Hist",
+            ProcessColumn::MemGraph => "Mem Hist",
+            ProcessColumn::IoGraph => "I/O Hist",
+        }
+    }
+
+    /// Longer name used in the column editor
+    pub fn description(&self) -> &str {
+        match self {
+            ProcessColumn::Pid => "Process ID",
+            ProcessColumn::Ppid => "Parent process ID",
+            ProcessColumn::User => "Owning user",
+            ProcessColumn::State => "State (R, S, D, Z, ...)",
+            ProcessColumn::Nice => "Nice value",
+            ProcessColumn::Threads => "Thread count",
+            ProcessColumn::Cpu => "CPU usage",
+            ProcessColumn::MemPercent => "Share of total memory",
+            ProcessColumn::Rss => "Resident memory",
+            ProcessColumn::Virt => "Virtual memory",
+            ProcessColumn::Shared => "Shared memory",
+            ProcessColumn::ReadRate => "Disk read rate",
+            ProcessColumn::WriteRate => "Disk write rate",
+            ProcessColumn::MinorFaults => "Minor page faults per second",
+            ProcessColumn::MajorFaults => "Major page faults per second (read from disk)",
+            ProcessColumn::StartTime => "Start time",
+            ProcessColumn::CpuTime => "Total CPU time",
+            ProcessColumn::Name => "Process name",
+            ProcessColumn::Command => "Full command line",
+            ProcessColumn::Cgroup => "Control group",
+            ProcessColumn::Unit => "systemd unit (service or scope)",
+            ProcessColumn::Container => "Container name, or ID when the runtime is unreachable",
+            ProcessColumn::CpuGraph => "CPU usage history",
+            ProcessColumn::MemGraph => "Resident memory history",
+            ProcessColumn::IoGraph => "Disk I/O rate history",
+        }
+    }
+
+    pub fn default_width(&self) -> u16 {
+        match self {
+            ProcessColumn::State => 1,
+            ProcessColumn::Nice | ProcessColumn::Threads => 4,
+            ProcessColumn::MemPercent => 5,
+            ProcessColumn::Pid | ProcessColumn::Ppid | ProcessColumn::Cpu => 7,
+            ProcessColumn::StartTime => 6,
+            ProcessColumn::CpuTime => 9,
+            ProcessColumn::MinorFaults | ProcessColumn::MajorFaults => 9,
+            ProcessColumn::Rss | ProcessColumn::Virt | ProcessColumn::Shared => 10,
+            ProcessColumn::CpuGraph | ProcessColumn::MemGraph | ProcessColumn::IoGraph => 10,
+            ProcessColumn::ReadRate | ProcessColumn::WriteRate => 11,
+            ProcessColumn::User | ProcessColumn::Container => 12,
+            ProcessColumn::Unit => 24,
+            ProcessColumn::Name => 20,
+            ProcessColumn::Cgroup => 30,
+            ProcessColumn::Command => 40,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessConfig {
+    #[serde(default)]
+    pub columns: ProcessColumns,
+    /// Seconds an exited process stays in the recently exited panel
+    #[serde(default = "default_exited_retention")]
+    pub exited_retention: u64,
+    /// Catch every exit through netlink proc events when running as root
+    #[serde(default = "default_true")]
+    pub proc_events: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessColumns {
+    /// Columns shown, left to right
+    #[serde(default = "default_process_columns")]
+    pub visible: Vec<ProcessColumn>,
+    /// Width overrides in characters; other columns use their default width
+    #[serde(default)]
+    pub widths: BTreeMap<ProcessColumn, u16>,
+}
+
+impl ProcessColumns {
+    pub fn width(&self, column: ProcessColumn) -> u16 {
+        self.widths
+            .get(&column)
+            .copied()
+            .unwrap_or_else(|| column.default_width())
+    }
+
+    /// Grow or shrink a column, dropping the override once it is back to the default
+    pub fn resize(&mut self, column: ProcessColumn, delta: i16) {
+        let width = (self.width(column) as i16 + delta).clamp(1, 200) as u16;
+        if width == column.default_width() {
+            self.widths.remove(&column);
+        } else {
+            self.widths.insert(column, width);
+        }
+    }
+
+    pub fn toggle(&mut self, column: ProcessColumn) {
+        if let Some(pos) = self.visible.iter().position(|&c| c == column) {
+            // Keep at least one column so the table never collapses to nothing
+            if self.visible.len() > 1 {
+                self.visible.remove(pos);
+            }
+        } else {
+            self.visible.push(column);
+        }
+    }
+
+    /// Move a visible column left (negative) or right (positive)
+    pub fn shift(&mut self, column: ProcessColumn, delta: isize) {
+        let Some(pos) = self.visible.iter().position(|&c| c == column) else {
+            return;
+        };
+        let target = pos as isize + delta;
+        if target >= 0 && (target as usize) < self.visible.len() {
+            self.visible.swap(pos, target as usize);
+        }
+    }
+}
+
 // Default value functions
 fn default_cpu_refresh() -> u64 {
     1000
@@ -140,6 +400,46 @@ fn default_disk_warning() -> f32 {
 fn default_log_interval() -> u64 {
     5000
 }
+fn default_probe_mode() -> ProbeMode {
+    ProbeMode::Icmp
+}
+fn default_probe_targets() -> Vec<String> {
+    vec!["8.8.8.8".to_string(), "1.1.1.1".to_string()]
+}
+fn default_probe_interval() -> u64 {
+    3000
+}
+/// Shortest time between latency probe rounds, in milliseconds
+const MIN_PROBE_INTERVAL: u64 = 1000;
+fn deserialize_probe_interval<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<u64, D::Error> {
+    Ok(u64::deserialize(deserializer)?.max(MIN_PROBE_INTERVAL))
+}
+fn default_probe_timeout() -> u64 {
+    1000
+}
+fn default_process_columns() -> Vec<ProcessColumn> {
+    vec![
+        ProcessColumn::Pid,
+        ProcessColumn::User,
+        ProcessColumn::State,
+        ProcessColumn::Threads,
+        ProcessColumn::Name,
+        ProcessColumn::Cpu,
+        ProcessColumn::CpuGraph,
+        ProcessColumn::Rss,
+    ]
+}
+fn default_exited_retention() -> u64 {
+    30
+}
+fn default_network_exclude() -> Vec<String> {
+    ["lo", "veth*", "docker*", "br-*", "virbr*"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
 
 impl Default for RefreshRates {
     fn default() -> Self {
@@ -200,6 +500,45 @@ impl Default for ExportConfig {
     }
 }
 
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            include: Vec::new(),
+            exclude: default_network_exclude(),
+        }
+    }
+}
+
+impl Default for ProcessConfig {
+    fn default() -> Self {
+        Self {
+            columns: ProcessColumns::default(),
+            exited_retention: default_exited_retention(),
+            proc_events: default_true(),
+        }
+    }
+}
+
+impl Default for ProcessColumns {
+    fn default() -> Self {
+        Self {
+            visible: default_process_columns(),
+            widths: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for LatencyConfig {
+    fn default() -> Self {
+        Self {
+            mode: default_probe_mode(),
+            targets: default_probe_targets(),
+            interval: default_probe_interval(),
+            timeout: default_probe_timeout(),
+        }
+    }
+}
+
 impl Config {
     pub fn load() -> anyhow::Result<Self> {
         let config_path = Self::config_path()?;
@@ -225,6 +564,58 @@ impl Config {
         Ok(())
     }
 
+    /// Write `columns` into the `[process.columns]` table of the config file at `path`,
+    /// leaving the rest of the file, comments included, as it was
+    pub fn save_process_columns(path: &Path, columns: &ProcessColumns) -> anyhow::Result<()> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let mut document: toml_edit::DocumentMut = contents.parse()?;
+        // Serialized through serde so column names match what `load` reads
+        let serialized: toml_edit::DocumentMut = toml::to_string(columns)?.parse()?;
+
+        let process = document
+            .entry("process")
+            .or_insert_with(|| {
+                let mut table = toml_edit::Table::new();
+                table.set_implicit(true);
+                toml_edit::Item::Table(table)
+            })
+            .as_table_mut()
+            .ok_or_else(|| anyhow::anyhow!("process is not a table"))?;
+        let table = process
+            .entry("columns")
+            .or_insert_with(toml_edit::table)
+            .as_table_mut()
+            .ok_or_else(|| anyhow::anyhow!("process.columns is not a table"))?;
+
+        if let Some(visible) = serialized.get("visible") {
+            table["visible"] = visible.clone();
+        }
+        let widths = serialized.get("widths").and_then(|w| w.as_table());
+        match table.get_mut("widths").and_then(|w| w.as_table_mut()) {
+            Some(existing) => {
+                existing.clear();
+                for (column, width) in widths.into_iter().flat_map(|w| w.iter()) {
+                    existing.insert(column, width.clone());
+                }
+            }
+            None => {
+                if let Some(widths) = widths.filter(|w| !w.is_empty()) {
+                    table.insert("widths", toml_edit::Item::Table(widths.clone()));
+                }
+            }
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, document.to_string())?;
+        Ok(())
+    }
+
     pub fn create_default_config() -> anyhow::Result<()> {
         let config = Config::default();
         config.save()
@@ -319,6 +710,51 @@ mod tests {
         assert_eq!(display.max_processes, 20);
     }
 
+    #[test]
+    fn test_network_config() {
+        let network = NetworkConfig::default();
+        assert!(network.include.is_empty());
+        assert!(network.exclude.contains(&"lo".to_string()));
+        assert!(network.exclude.contains(&"veth*".to_string()));
+
+        let toml_str = r#"
+            [network]
+            include = ["eth*", "wlan*"]
+            exclude = []
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.network.include, vec!["eth*", "wlan*"]);
+        assert!(config.network.exclude.is_empty());
+    }
+
+    #[test]
+    fn test_latency_config() {
+        let latency = LatencyConfig::default();
+        assert_eq!(latency.mode, ProbeMode::Icmp);
+        assert_eq!(latency.interval, 3000);
+
+        let toml_str = r#"
+            [latency]
+            mode = "tcp"
+            targets = ["10.0.0.1:22"]
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.latency.mode, ProbeMode::Tcp);
+        assert_eq!(config.latency.targets, vec!["10.0.0.1:22"]);
+        assert_eq!(config.latency.timeout, 1000);
+
+        let config: Config = toml::from_str("[latency]\nmode = \"disabled\"").unwrap();
+        assert_eq!(config.latency.mode, ProbeMode::Disabled);
+    }
+
+    #[test]
+    fn test_latency_interval_minimum() {
+        let config: Config = toml::from_str("[latency]\ninterval = 0").unwrap();
+        assert_eq!(config.latency.interval, MIN_PROBE_INTERVAL);
+        let config: Config = toml::from_str("[latency]\ninterval = 5000").unwrap();
+        assert_eq!(config.latency.interval, 5000);
+    }
+
     #[test]
     fn test_config_serialization() {
         let config = Config::default();
@@ -364,4 +800,92 @@ mod tests {
         assert_eq!(config.refresh_rates.cpu, 1000);
         assert_eq!(config.display.max_processes, 20);
     }
+
+    #[test]
+    fn test_process_columns_round_trip() {
+        let toml_str = r#"
+            [process.columns]
+            visible = ["pid", "command", "cpu_time", "mem_percent"]
+
+            [process.columns.widths]
+            command = 60
+        "#;
+        let mut config: Config = toml::from_str(toml_str).unwrap();
+        let columns = &config.process.columns;
+        assert_eq!(columns.visible[1], ProcessColumn::Command);
+        assert_eq!(columns.width(ProcessColumn::Command), 60);
+        assert_eq!(columns.width(ProcessColumn::Pid), 7);
+
+        config.process.columns.resize(ProcessColumn::Command, -20);
+        assert!(config.process.columns.widths.is_empty());
+        config.process.columns.resize(ProcessColumn::Virt, 2);
+
+        let saved = toml::to_string_pretty(&config).unwrap();
+        let reloaded: Config = toml::from_str(&saved).unwrap();
+        assert_eq!(
+            reloaded.process.columns.visible,
+            config.process.columns.visible
+        );
+        assert_eq!(reloaded.process.columns.width(ProcessColumn::Virt), 12);
+    }
+
+    #[test]
+    fn test_save_process_columns_keeps_rest_of_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("custom.toml");
+        let original = "# My settings\n\
+                        [refresh_rates]\n\
+                        cpu = 500 # fast\n\
+                        \n\
+                        [process.columns]\n\
+                        # Picked by hand\n\
+                        visible = [\"pid\", \"name\"]\n\
+                        \n\
+                        [process.columns.widths]\n\
+                        name = 30\n";
+        fs::write(&path, original).unwrap();
+
+        let mut columns = ProcessColumns {
+            visible: vec![ProcessColumn::Pid, ProcessColumn::Command],
+            widths: BTreeMap::new(),
+        };
+        columns.widths.insert(ProcessColumn::Command, 50);
+        Config::save_process_columns(&path, &columns).unwrap();
+
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.starts_with("# My settings\n[refresh_rates]\ncpu = 500 # fast\n"));
+        assert!(saved.contains("# Picked by hand\nvisible = [\"pid\", \"command\"]"));
+        assert!(!saved.contains("name = 30"));
+        let reloaded: Config = toml::from_str(&saved).unwrap();
+        assert_eq!(reloaded.refresh_rates.cpu, 500);
+        assert_eq!(reloaded.process.columns.visible, columns.visible);
+        assert_eq!(reloaded.process.columns.width(ProcessColumn::Command), 50);
+
+        // A missing file gets just the columns table
+        let fresh = dir.path().join("new/config.toml");
+        Config::save_process_columns(&fresh, &columns).unwrap();
+        let reloaded: Config = toml::from_str(&fs::read_to_string(&fresh).unwrap()).unwrap();
+        assert_eq!(reloaded.process.columns.visible, columns.visible);
+    }
+
+    #[test]
+    fn test_process_columns_editing() {
+        let mut columns = ProcessColumns::default();
+        columns.toggle(ProcessColumn::Nice);
+        assert_eq!(columns.visible.last(), Some(&ProcessColumn::Nice));
+        columns.shift(ProcessColumn::Nice, -1);
+        assert_eq!(
+            columns.visible[columns.visible.len() - 2],
+            ProcessColumn::Nice
+        );
+        columns.toggle(ProcessColumn::Nice);
+        assert!(!columns.visible.contains(&ProcessColumn::Nice));
+
+        let mut single = ProcessColumns {
+            visible: vec![ProcessColumn::Pid],
+            widths: BTreeMap::new(),
+        };
+        single.toggle(ProcessColumn::Pid);
+        assert_eq!(single.visible, vec![ProcessColumn::Pid]);
+    }
 }
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
@@ -10,21 +11,71 @@ pub struct Metrics {
     pub memory: MemoryMetrics,
     pub network: NetworkMetrics,
     pub disk: Vec<DiskMetrics>,
+    #[serde(default)]
+    pub disk_io: Vec<DiskIOMetrics>,
     pub processes: Vec<ProcessMetrics>,
     pub temperature: Option<TempMetrics>,
     pub system: SystemMetrics,
+    /// System-wide pressure stall information, when the kernel provides it
+    #[serde(default)]
+    pub pressure: Option<PressureMetrics>,
+    /// Pressure of the container rtop runs in; absent on a host
+    #[serde(default)]
+    pub cgroup_pressure: Option<PressureMetrics>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CpuMetrics {
     pub cores: Vec<CoreMetric>,
+    /// Against the cgroup CPU limit when there is one
     pub average: f32,
+    /// CPUs the cgroup rtop runs in may use, when fewer than the host has
+    #[serde(default)]
+    pub limit: Option<f64>,
+    /// Aggregate /proc/stat breakdown over the last refresh
+    #[serde(default)]
+    pub times: Option<CpuTimeMetrics>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct CoreMetric {
     pub id: usize,
     pub usage: f32,
+    /// Current clock and cpufreq policy limits in MHz
+    #[serde(default)]
+    pub frequency: Option<u64>,
+    #[serde(default)]
+    pub min_frequency: Option<u64>,
+    #[serde(default)]
+    pub max_frequency: Option<u64>,
+    #[serde(default)]
+    pub governor: Option<String>,
+    /// Physical package and core; SMT siblings share a core
+    #[serde(default)]
+    pub package: Option<u32>,
+    #[serde(default)]
+    pub core: Option<u32>,
+    #[serde(default)]
+    pub siblings: Vec<usize>,
+    /// "P" or "E" on hybrid CPUs
+    #[serde(default)]
+    pub core_type: Option<String>,
+    #[serde(default)]
+    pub times: Option<CpuTimeMetrics>,
+}
+
+/// Percent of CPU time per kind; guest time is not counted again in user
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CpuTimeMetrics {
+    pub user: f64,
+    pub nice: f64,
+    pub system: f64,
+    pub idle: f64,
+    pub iowait: f64,
+    pub irq: f64,
+    pub softirq: f64,
+    pub steal: f64,
+    pub guest: f64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -36,32 +87,142 @@ pub struct MemoryMetrics {
     pub swap_total: u64,
     pub swap_used: u64,
     pub swap_percent: f32,
+    /// Host-wide /proc/meminfo breakdown
+    #[serde(default)]
+    pub breakdown: Option<MemoryBreakdown>,
+    #[serde(default)]
+    pub swap: Option<SwapMetrics>,
+    #[serde(default)]
+    pub events: Option<MemoryEventMetrics>,
+}
+
+/// OOM kills, page faults and reclaim from /proc/vmstat; totals are since boot
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MemoryEventMetrics {
+    pub oom_kills: u64,
+    pub oom_kills_since_start: u64,
+    pub minor_faults: u64,
+    pub major_faults: u64,
+    pub pages_scanned: u64,
+    pub pages_stolen: u64,
+    pub minor_fault_rate: f64, // per sec
+    pub major_fault_rate: f64, // per sec
+    pub kswapd_scan_rate: f64, // pages/sec
+    pub direct_scan_rate: f64, // pages/sec
+    pub steal_rate: f64,       // pages/sec
+}
+
+/// All values in bytes
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MemoryBreakdown {
+    pub available: u64,
+    pub cached: u64,
+    pub buffers: u64,
+    pub shared: u64,
+    pub slab_reclaimable: u64,
+    pub slab_unreclaimable: u64,
+    pub dirty: u64,
+    pub writeback: u64,
+    pub committed: u64,
+    pub commit_limit: u64,
+    pub hugepages_total: u64,
+    pub hugepages_free: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SwapMetrics {
+    pub devices: Vec<SwapDeviceMetrics>,
+    pub zram: Vec<ZramMetrics>,
+    pub zswap: Option<ZswapMetrics>,
+    pub pages_in_rate: f64,  // pages/sec
+    pub pages_out_rate: f64, // pages/sec
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SwapDeviceMetrics {
+    pub name: String,
+    pub kind: String,
+    pub size: u64,
+    pub used: u64,
+    pub priority: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ZramMetrics {
+    pub name: String,
+    pub disksize: u64,
+    pub orig_data_size: u64,
+    pub compr_data_size: u64,
+    pub mem_used_total: u64,
+    pub algorithm: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ZswapMetrics {
+    pub enabled: bool,
+    pub compressor: Option<String>,
+    pub pool_size: Option<u64>,
+    pub stored: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct NetworkMetrics {
-    pub received: u64,
-    pub transmitted: u64,
-    pub rx_rate: f64,
-    pub tx_rate: f64,
+    pub received: u64,    // cumulative bytes
+    pub transmitted: u64, // cumulative bytes
+    pub rx_rate: f64,     // bytes/sec over the measured sample interval
+    pub tx_rate: f64,     // bytes/sec over the measured sample interval
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DiskMetrics {
     pub name: String,
     pub mount_point: String,
+    #[serde(default)]
+    pub fs_type: String,
     pub total: u64,
+    #[serde(default)]
+    pub used: u64,
     pub available: u64,
     pub percent: f32,
+    #[serde(default)]
+    pub inodes_total: u64,
+    #[serde(default)]
+    pub inodes_used: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DiskIOMetrics {
+    pub device: String,
+    pub read_rate: f64,  // bytes/sec
+    pub write_rate: f64, // bytes/sec
+    pub read_total: u64, // cumulative bytes
+    pub write_total: u64,
+    pub util_percent: f64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ProcessMetrics {
     pub pid: u32,
     pub name: String,
+    #[serde(default)]
+    pub user: String,
+    #[serde(default)]
+    pub threads: usize,
+    #[serde(default)]
+    pub cgroup: String,
+    /// systemd unit
+    #[serde(default)]
+    pub unit: Option<String>,
+    /// Container name, or its short ID when the runtime could not be asked
+    #[serde(default)]
+    pub container: Option<String>,
     pub cpu: f32,
     pub memory: u64,
     pub memory_percent: f32,
+    #[serde(default)]
+    pub minor_fault_rate: f64, // per sec
+    #[serde(default)]
+    pub major_fault_rate: f64, // per sec
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -77,6 +238,28 @@ pub struct SensorMetric {
     pub temperature: f32,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PressureMetrics {
+    pub cpu: Option<PsiMetric>,
+    pub memory: Option<PsiMetric>,
+    pub io: Option<PsiMetric>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PsiMetric {
+    pub some: PsiWindow,
+    pub full: Option<PsiWindow>,
+}
+
+/// Percent of time stalled over 10 s, 60 s and 300 s; total stall time in microseconds
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PsiWindow {
+    pub avg10: f64,
+    pub avg60: f64,
+    pub avg300: f64,
+    pub total: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SystemMetrics {
     pub hostname: String,
@@ -98,11 +281,9 @@ impl Metrics {
         let mut file = File::create(path)?;
 
         // Write header
-        writeln!(file, "timestamp,cpu_avg,memory_percent,swap_percent,network_rx_rate,network_tx_rate,uptime,load_1m,load_5m,load_15m")?;
+        writeln!(file, "timestamp,cpu_avg,memory_percent,swap_percent,network_rx_rate,network_tx_rate,uptime,load_1m,load_5m,load_15m,disk_device,disk_mount_point,disk_fs_type,disk_total,disk_used,disk_available,disk_percent")?;
 
-        // Write data
-        writeln!(
-            file,
+        let summary = format!(
             "{},{:.2},{:.2},{:.2},{:.2},{:.2},{},{:.2},{:.2},{:.2}",
             self.timestamp,
             self.cpu.average,
@@ -114,12 +295,40 @@ impl Metrics {
             self.system.load_average.0,
             self.system.load_average.1,
             self.system.load_average.2,
-        )?;
+        );
+
+        // One row per mount; the system-wide columns repeat on each row
+        if self.disk.is_empty() {
+            writeln!(file, "{},,,,,,,", summary)?;
+        }
+        for disk in &self.disk {
+            writeln!(
+                file,
+                "{},{},{},{},{},{},{},{:.2}",
+                summary,
+                csv_field(&disk.name),
+                csv_field(&disk.mount_point),
+                csv_field(&disk.fs_type),
+                disk.total,
+                disk.used,
+                disk.available,
+                disk.percent,
+            )?;
+        }
 
         Ok(())
     }
 }
 
+/// Quote a CSV field per RFC 4180 when it holds a separator, quote or line break
+fn csv_field(value: &str) -> Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -131,9 +340,21 @@ mod tests {
             timestamp: "2026-02-04T20:00:00Z".to_string(),
             cpu: CpuMetrics {
                 cores: vec![
-                    CoreMetric { id: 0, usage: 25.5 },
-                    CoreMetric { id: 1, usage: 30.2 },
+                    CoreMetric {
+                        id: 0,
+                        usage: 25.5,
+                        frequency: Some(3400),
+                        package: Some(0),
+                        ..CoreMetric::default()
+                    },
+                    CoreMetric {
+                        id: 1,
+                        usage: 30.2,
+                        ..CoreMetric::default()
+                    },
                 ],
+                limit: None,
+                times: None,
                 average: 27.85,
             },
             memory: MemoryMetrics {
@@ -144,6 +365,9 @@ mod tests {
                 swap_total: 8_000_000_000,
                 swap_used: 1_000_000_000,
                 swap_percent: 12.5,
+                breakdown: None,
+                swap: None,
+                events: None,
             },
             network: NetworkMetrics {
                 received: 1_000_000,
@@ -154,16 +378,35 @@ mod tests {
             disk: vec![DiskMetrics {
                 name: "nvme0n1".to_string(),
                 mount_point: "/".to_string(),
+                fs_type: "ext4".to_string(),
                 total: 500_000_000_000,
+                used: 250_000_000_000,
                 available: 250_000_000_000,
                 percent: 50.0,
+                inodes_total: 1_000_000,
+                inodes_used: 100_000,
+            }],
+            disk_io: vec![DiskIOMetrics {
+                device: "nvme0n1".to_string(),
+                read_rate: 4096.0,
+                write_rate: 8192.0,
+                read_total: 1_000_000,
+                write_total: 2_000_000,
+                util_percent: 12.5,
             }],
             processes: vec![ProcessMetrics {
                 pid: 1234,
                 name: "test_process".to_string(),
+                user: "root".to_string(),
+                threads: 4,
+                cgroup: "/system.slice/test.service".to_string(),
+                unit: Some("test.service".to_string()),
+                container: None,
                 cpu: 10.5,
                 memory: 1_000_000,
                 memory_percent: 0.01,
+                minor_fault_rate: 0.0,
+                major_fault_rate: 0.0,
             }],
             temperature: Some(TempMetrics {
                 sensors: vec![SensorMetric {
@@ -180,6 +423,20 @@ mod tests {
                 uptime: 86400,
                 load_average: (1.5, 1.2, 0.9),
             },
+            pressure: Some(PressureMetrics {
+                cpu: Some(PsiMetric {
+                    some: PsiWindow {
+                        avg10: 2.5,
+                        avg60: 1.0,
+                        avg300: 0.5,
+                        total: 1_000_000,
+                    },
+                    full: None,
+                }),
+                memory: None,
+                io: None,
+            }),
+            cgroup_pressure: None,
         }
     }
 
@@ -219,6 +476,48 @@ mod tests {
         assert!(lines[1].contains("2026-02-04T20:00:00Z"));
         assert!(lines[1].contains("27.85"));
         assert!(lines[1].contains("50.00"));
+        assert!(lines[1].contains("nvme0n1,/,ext4"));
+    }
+
+    #[test]
+    fn test_export_csv_one_row_per_mount() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("metrics.csv");
+
+        let mut metrics = create_test_metrics();
+        metrics.disk.push(DiskMetrics {
+            name: "nvme0n1p2".to_string(),
+            mount_point: "/home".to_string(),
+            fs_type: "btrfs".to_string(),
+            total: 1000,
+            used: 100,
+            available: 900,
+            percent: 10.0,
+            inodes_total: 0,
+            inodes_used: 0,
+        });
+        metrics.export_csv(&file_path).unwrap();
+
+        let content = fs::read_to_string(&file_path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[2].contains("/home,btrfs,1000,100,900,10.00"));
+    }
+
+    #[test]
+    fn test_export_csv_quotes_fields() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("metrics.csv");
+
+        let mut metrics = create_test_metrics();
+        metrics.disk[0].mount_point = "/mnt/a,b \"c\"".to_string();
+        metrics.export_csv(&file_path).unwrap();
+
+        let content = fs::read_to_string(&file_path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].contains(",nvme0n1,\"/mnt/a,b \"\"c\"\"\",ext4,"));
+        assert_eq!(csv_field("/"), "/");
     }
 
     #[test]
@@ -230,6 +529,7 @@ mod tests {
         assert!(json.contains("cpu"));
         assert!(json.contains("memory"));
         assert!(json.contains("network"));
+        assert!(json.contains("\"pressure\":{\"cpu\":{\"some\":{\"avg10\":2.5"));
     }
 
     #[test]
@@ -256,5 +556,6 @@ mod tests {
         assert_eq!(metrics.timestamp, "2026-02-04T20:00:00Z");
         assert_eq!(metrics.cpu.average, 25.0);
         assert_eq!(metrics.system.hostname, "test");
+        assert!(metrics.pressure.is_none());
     }
 }
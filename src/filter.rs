@@ -0,0 +1,443 @@
+//! Process filter queries such as `user:postgres cpu>10 cmd:/--config/ !name:kworker`
+//!
+//! A query is a list of terms that must all match. A term is one of:
+//! - `word` or `/regex/`: matched against the process name and full command line
+//! - `field:text` or `field:/regex/`: matched against one field (name, cmd, user, state,
+//!   cgroup, unit, container, pid, ppid); plain text is a case-insensitive substring
+//! - `field>n`, `field>=n`, `field<n`, `field<=n`, `field=n`: numeric comparison on
+//!   cpu (percent), mem (bytes, K/M/G suffixes allowed), threads, nice, pid or ppid
+//!
+//! Any term can be negated with a leading `!`. Quoted text (`"a b"`, `cmd:"x:y"`) is
+//! always searched for literally.
+use regex::Regex;
+
+use crate::error::{Result, RtopError};
+use crate::monitor::ProcessInfo;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TextField {
+    /// Name or command line
+    Any,
+    Name,
+    Command,
+    User,
+    State,
+    Cgroup,
+    /// systemd unit
+    Unit,
+    /// Container name or ID
+    Container,
+    Pid,
+    Ppid,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum NumField {
+    Cpu,
+    Memory,
+    Threads,
+    Nice,
+    Pid,
+    Ppid,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CompareOp {
+    Less,
+    LessEqual,
+    Equal,
+    GreaterEqual,
+    Greater,
+}
+
+#[derive(Debug, Clone)]
+enum Matcher {
+    /// Lowercased needle
+    Text(String),
+    Regex(Regex),
+}
+
+#[derive(Debug, Clone)]
+enum Term {
+    Match(TextField, Matcher),
+    Compare(NumField, CompareOp, f64),
+}
+
+#[derive(Debug, Clone)]
+struct Condition {
+    negated: bool,
+    term: Term,
+}
+
+/// A parsed filter query; an empty query matches everything
+#[derive(Debug, Clone, Default)]
+pub struct ProcessFilter {
+    conditions: Vec<Condition>,
+}
+
+impl ProcessFilter {
+    pub fn parse(query: &str) -> Result<Self> {
+        let conditions = tokenize(query)?
+            .iter()
+            .map(|token| parse_condition(token))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { conditions })
+    }
+
+    pub fn matches(&self, process: &ProcessInfo) -> bool {
+        self.conditions
+            .iter()
+            .all(|c| c.term.matches(process) != c.negated)
+    }
+}
+
+impl Term {
+    fn matches(&self, p: &ProcessInfo) -> bool {
+        match self {
+            Term::Match(TextField::Any, matcher) => {
+                matcher.is_match(&p.name) || matcher.is_match(&p.command)
+            }
+            Term::Match(TextField::Pid, Matcher::Text(text)) => p.pid.to_string() == *text,
+            Term::Match(TextField::Ppid, Matcher::Text(text)) => {
+                p.ppid.is_some_and(|ppid| ppid.to_string() == *text)
+            }
+            Term::Match(field, matcher) => {
+                let value = match field {
+                    TextField::Name => p.name.clone(),
+                    TextField::Command => p.command.clone(),
+                    TextField::User => match &p.effective_user {
+                        Some(effective) => format!("{} {}", p.user, effective),
+                        None => p.user.clone(),
+                    },
+                    TextField::State => p.state.clone(),
+                    TextField::Cgroup => p.cgroup.clone(),
+                    TextField::Unit => p.unit.clone().unwrap_or_default(),
+                    TextField::Container => match (&p.container, &p.container_name) {
+                        (Some(id), Some(name)) => format!("{} {}", name, id),
+                        (Some(id), None) => id.clone(),
+                        _ => String::new(),
+                    },
+                    TextField::Pid => p.pid.to_string(),
+                    TextField::Ppid => p.ppid.map(|ppid| ppid.to_string()).unwrap_or_default(),
+                    TextField::Any => unreachable!(),
+                };
+                matcher.is_match(&value)
+            }
+            Term::Compare(field, op, value) => {
+                let actual = match field {
+                    NumField::Cpu => p.cpu_usage as f64,
+                    NumField::Memory => p.memory as f64,
+                    NumField::Threads => p.threads as f64,
+                    NumField::Nice => p.nice as f64,
+                    NumField::Pid => p.pid as f64,
+                    NumField::Ppid => match p.ppid {
+                        Some(ppid) => ppid as f64,
+                        None => return false,
+                    },
+                };
+                match op {
+                    CompareOp::Less => actual < *value,
+                    CompareOp::LessEqual => actual <= *value,
+                    CompareOp::Equal => actual == *value,
+                    CompareOp::GreaterEqual => actual >= *value,
+                    CompareOp::Greater => actual > *value,
+                }
+            }
+        }
+    }
+}
+
+impl Matcher {
+    fn is_match(&self, text: &str) -> bool {
+        match self {
+            Matcher::Text(needle) => text.to_lowercase().contains(needle),
+            Matcher::Regex(regex) => regex.is_match(text),
+        }
+    }
+}
+
+/// Split on whitespace, keeping `/regex with spaces/` and `"quoted text"` (quotes included)
+/// together
+fn tokenize(query: &str) -> Result<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut chars = query.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            // A regex only starts where a value starts: token start, after ':' or '!'
+            '/' if current.is_empty() || current.ends_with(':') || current == "!" => {
+                current.push('/');
+                let mut closed = false;
+                while let Some(c) = chars.next() {
+                    current.push(c);
+                    if c == '\\' {
+                        if let Some(escaped) = chars.next() {
+                            current.push(escaped);
+                        }
+                    } else if c == '/' {
+                        closed = true;
+                        break;
+                    }
+                }
+                if !closed {
+                    return Err(RtopError::InvalidInput(format!(
+                        "unterminated regex in '{}'",
+                        current
+                    )));
+                }
+            }
+            '"' => {
+                current.push('"');
+                let mut closed = false;
+                for c in chars.by_ref() {
+                    current.push(c);
+                    if c == '"' {
+                        closed = true;
+                        break;
+                    }
+                }
+                if !closed {
+                    return Err(RtopError::InvalidInput("unterminated quote".to_string()));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+fn parse_condition(token: &str) -> Result<Condition> {
+    let (negated, body) = match token.strip_prefix('!') {
+        Some(rest) => (true, rest),
+        None => (false, token),
+    };
+    if body.is_empty() {
+        return Err(RtopError::InvalidInput("'!' needs a term".to_string()));
+    }
+
+    // A leading regex or quote may itself contain ':' or '>', so it is never a field term
+    if !body.starts_with('/') && !body.starts_with('"') {
+        if let Some(term) = parse_comparison(body)? {
+            return Ok(Condition { negated, term });
+        }
+        if let Some((field, value)) = body.split_once(':') {
+            let field = text_field(field)?;
+            return Ok(Condition {
+                negated,
+                term: Term::Match(field, parse_matcher(value)?),
+            });
+        }
+    }
+
+    Ok(Condition {
+        negated,
+        term: Term::Match(TextField::Any, parse_matcher(body)?),
+    })
+}
+
+fn parse_comparison(body: &str) -> Result<Option<Term>> {
+    let Some(pos) = body.find(['<', '>', '=']) else {
+        return Ok(None);
+    };
+    let (field, rest) = body.split_at(pos);
+    // "name:a=b" is a text match whose value happens to contain '='
+    if field.contains(':') {
+        return Ok(None);
+    }
+
+    let (op, value) = if let Some(value) = rest.strip_prefix(">=") {
+        (CompareOp::GreaterEqual, value)
+    } else if let Some(value) = rest.strip_prefix("<=") {
+        (CompareOp::LessEqual, value)
+    } else if let Some(value) = rest.strip_prefix('>') {
+        (CompareOp::Greater, value)
+    } else if let Some(value) = rest.strip_prefix('<') {
+        (CompareOp::Less, value)
+    } else {
+        (CompareOp::Equal, rest.trim_start_matches('='))
+    };
+
+    let field = match field.to_lowercase().as_str() {
+        "cpu" => NumField::Cpu,
+        "mem" | "memory" | "rss" => NumField::Memory,
+        "threads" | "thr" => NumField::Threads,
+        "nice" | "ni" => NumField::Nice,
+        "pid" => NumField::Pid,
+        "ppid" => NumField::Ppid,
+        other if text_field(other).is_ok() => {
+            return Err(RtopError::InvalidInput(format!(
+                "'{}' cannot be compared (use cpu, mem, threads, nice, pid or ppid)",
+                other
+            )))
+        }
+        // Not a field at all, e.g. "--port=80"; search for it as text
+        _ => return Ok(None),
+    };
+    let value = parse_number(value, field == NumField::Memory)
+        .ok_or_else(|| RtopError::InvalidInput(format!("expected a number in '{}'", body)))?;
+    Ok(Some(Term::Compare(field, op, value)))
+}
+
+/// Plain number, or for memory a size with an optional K/M/G/T suffix (binary units)
+fn parse_number(value: &str, size: bool) -> Option<f64> {
+    let value = value.trim_end_matches('%');
+    if !size {
+        return value.parse().ok();
+    }
+    let upper = value.to_uppercase();
+    let trimmed = upper.trim_end_matches("IB").trim_end_matches('B');
+    let (number, multiplier) = match trimmed.chars().last()? {
+        'K' => (&trimmed[..trimmed.len() - 1], 1024.0),
+        'M' => (&trimmed[..trimmed.len() - 1], 1024.0 * 1024.0),
+        'G' => (&trimmed[..trimmed.len() - 1], 1024.0 * 1024.0 * 1024.0),
+        'T' => (
+            &trimmed[..trimmed.len() - 1],
+            1024.0 * 1024.0 * 1024.0 * 1024.0,
+        ),
+        _ => (trimmed, 1.0),
+    };
+    number.parse::<f64>().ok().map(|n| n * multiplier)
+}
+
+fn text_field(name: &str) -> Result<TextField> {
+    Ok(match name.to_lowercase().as_str() {
+        "name" => TextField::Name,
+        "cmd" | "command" => TextField::Command,
+        "user" | "u" => TextField::User,
+        "state" | "s" => TextField::State,
+        "cgroup" => TextField::Cgroup,
+        "unit" | "service" => TextField::Unit,
+        "container" | "ctr" => TextField::Container,
+        "pid" => TextField::Pid,
+        "ppid" => TextField::Ppid,
+        other => {
+            return Err(RtopError::InvalidInput(format!(
+                "unknown field '{}' (quote text to search for it literally)",
+                other
+            )))
+        }
+    })
+}
+
+fn parse_matcher(value: &str) -> Result<Matcher> {
+    if let Some(text) = value
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+    {
+        if text.is_empty() {
+            return Err(RtopError::InvalidInput("empty filter value".to_string()));
+        }
+        return Ok(Matcher::Text(text.to_lowercase()));
+    }
+    if let Some(pattern) = value
+        .strip_prefix('/')
+        .and_then(|rest| rest.strip_suffix('/'))
+    {
+        return Regex::new(pattern)
+            .map(Matcher::Regex)
+            .map_err(|e| RtopError::InvalidInput(regex_error_message(&e)));
+    }
+    if value.is_empty() {
+        return Err(RtopError::InvalidInput("empty filter value".to_string()));
+    }
+    Ok(Matcher::Text(value.to_lowercase()))
+}
+
+/// The one-line reason from a regex error, without the pattern and caret lines
+fn regex_error_message(error: &regex::Error) -> String {
+    let text = error.to_string();
+    let reason = text
+        .lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .unwrap_or("invalid regex");
+    format!("bad regex: {}", reason.trim_start_matches("error: "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(name: &str, command: &str, user: &str, cpu: f32, memory: u64) -> ProcessInfo {
+        ProcessInfo {
+            pid: 4242,
+            ppid: Some(1),
+            name: name.to_string(),
+            command: command.to_string(),
+            user: user.to_string(),
+            cpu_usage: cpu,
+            memory,
+            threads: 8,
+            state: "S".to_string(),
+            ..ProcessInfo::default()
+        }
+    }
+
+    fn matches(query: &str, p: &ProcessInfo) -> bool {
+        ProcessFilter::parse(query).unwrap().matches(p)
+    }
+
+    #[test]
+    fn test_fields_and_negation() {
+        let postgres = process(
+            "postgres",
+            "/usr/lib/postgresql/16/bin/postgres --config-file=/etc/pg.conf",
+            "postgres",
+            12.5,
+            512 * 1024 * 1024,
+        );
+        let kworker = process("kworker/0:1", "[kworker/0:1]", "root", 0.0, 0);
+
+        let query = "user:postgres cpu>10 cmd:/--config/ !name:kworker";
+        assert!(matches(query, &postgres));
+        assert!(!matches(query, &kworker));
+
+        assert!(matches("", &kworker));
+        assert!(matches("PG.CONF", &postgres));
+        assert!(matches("mem>=512M", &postgres));
+        assert!(!matches("mem>1G", &postgres));
+        assert!(matches("threads=8 pid:4242 ppid<2", &postgres));
+        assert!(matches("!/^k/", &postgres));
+        assert!(matches("name:kworker/0", &kworker));
+    }
+
+    #[test]
+    fn test_regex_with_spaces_and_quotes() {
+        let p = process("java", "java -Xmx2g -jar app.jar", "svc", 1.0, 0);
+        assert!(matches("cmd:/-jar app/", &p));
+        assert!(matches("\"-jar app\"", &p));
+        assert!(!matches("cmd:/^app/", &p));
+        assert!(matches("-Xmx2g", &p));
+
+        let mut web = process("nginx", "nginx -g daemon=off http://x", "www", 0.0, 0);
+        web.unit = Some("nginx.service".to_string());
+        web.container = Some("4f1c2e9a7b3d".to_string());
+        web.container_name = Some("frontend".to_string());
+        assert!(matches("unit:nginx.service ctr:frontend ctr:4f1c", &web));
+        assert!(matches("daemon=off", &web));
+        assert!(matches("cmd:\"http://x\"", &web));
+        assert!(matches("!\"-jar\"", &web));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = ProcessFilter::parse("cmd:/(/").unwrap_err().to_string();
+        assert!(err.starts_with("Invalid input: bad regex"), "{}", err);
+        assert!(!err.contains('\n'));
+
+        assert!(ProcessFilter::parse("cmd:/open").is_err());
+        assert!(ProcessFilter::parse("bogus:x").is_err());
+        assert!(ProcessFilter::parse("cpu>lots").is_err());
+        assert!(ProcessFilter::parse("name>3").is_err());
+        assert!(ProcessFilter::parse("!").is_err());
+    }
+}
@@ -2,6 +2,7 @@ mod cli;
 mod config;
 mod error;
 mod export;
+mod filter;
 mod graphics;
 mod monitor;
 mod theme;
@@ -49,8 +50,8 @@ fn main() -> anyhow::Result<()> {
     }
 
     // Load configuration
-    let config = if let Some(config_path) = cli.config {
-        let contents = std::fs::read_to_string(&config_path)?;
+    let config = if let Some(config_path) = &cli.config {
+        let contents = std::fs::read_to_string(config_path)?;
         toml::from_str(&contents)?
     } else {
         Config::load().unwrap_or_default()
@@ -93,6 +94,9 @@ fn main() -> anyhow::Result<()> {
     if cli.no_color {
         app.set_color_mode(false);
     }
+    if let Some(config_path) = cli.config {
+        app.set_config_path(config_path);
+    }
 
     // Main loop
     let start_time = Instant::now();
@@ -136,8 +140,8 @@ fn export_and_exit(path: &std::path::Path, format: &str, config: &Config) -> any
     let mut app = ui::App::new(config.clone());
     app.update();
 
-    // Give monitors time to collect data
-    std::thread::sleep(Duration::from_millis(500));
+    // Wait one full sampling interval so rate-based metrics have a real delta to work from
+    std::thread::sleep(config.cpu_refresh_duration());
     app.update();
 
     let metrics = app.collect_metrics();
@@ -0,0 +1,378 @@
+/// Control group membership of processes, as listed in /proc/<pid>/cgroup, and the
+/// resource usage of those groups from the cgroup v2 hierarchy
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+
+use super::pressure::{self, Pressure, PressureResource};
+
+/// Unit types systemd places processes in; slices only group other units
+const UNIT_SUFFIXES: [&str; 4] = [".service", ".scope", ".socket", ".mount"];
+/// Docker-compatible API sockets asked for container names
+const RUNTIME_SOCKETS: [&str; 2] = ["/var/run/docker.sock", "/run/podman/podman.sock"];
+/// Unknown container IDs trigger a runtime query at most this often
+const RUNTIME_QUERY_INTERVAL: Duration = Duration::from_secs(10);
+const RUNTIME_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Cgroup path of a process: the unified (v2) hierarchy when mounted, otherwise the
+/// systemd v1 hierarchy, otherwise the first one listed
+pub fn parse_proc_cgroup(contents: &str) -> String {
+    let entries: Vec<(&str, &str)> = contents
+        .lines()
+        .filter_map(|line| {
+            // hierarchy-ID:controller-list:cgroup-path
+            let mut parts = line.splitn(3, ':');
+            let _id = parts.next()?;
+            let controllers = parts.next()?;
+            let path = parts.next()?;
+            Some((controllers, path))
+        })
+        .collect();
+
+    entries
+        .iter()
+        .find(|(controllers, _)| controllers.is_empty())
+        .or_else(|| {
+            entries
+                .iter()
+                .find(|(controllers, _)| *controllers == "name=systemd")
+        })
+        .or_else(|| entries.first())
+        .map(|(_, path)| path.to_string())
+        .unwrap_or_default()
+}
+
+pub fn read_proc_cgroup(pid: u32) -> String {
+    fs::read_to_string(format!("/proc/{}/cgroup", pid))
+        .map(|contents| parse_proc_cgroup(&contents))
+        .unwrap_or_default()
+}
+
+/// Short (12 character) container ID when the cgroup path belongs to a Docker, Podman,
+/// containerd or CRI-O container
+pub fn container_id(cgroup_path: &str) -> Option<String> {
+    cgroup_path.rsplit('/').find_map(|component| {
+        let id = component.strip_suffix(".scope").unwrap_or(component);
+        let id = ["docker-", "libpod-", "cri-containerd-", "crio-"]
+            .iter()
+            .find_map(|prefix| id.strip_prefix(prefix))
+            .unwrap_or(id);
+        (id.len() == 64 && id.chars().all(|c| c.is_ascii_hexdigit())).then(|| id[..12].to_string())
+    })
+}
+
+/// Type of a unit name ("service" for "nginx.service"); `None` for slices and non-units
+pub fn unit_type(name: &str) -> Option<&'static str> {
+    UNIT_SUFFIXES
+        .iter()
+        .find(|suffix| name.ends_with(*suffix))
+        .map(|suffix| &suffix[1..])
+}
+
+/// Innermost systemd unit in a cgroup path, e.g. "nginx.service"
+pub fn systemd_unit(cgroup_path: &str) -> Option<String> {
+    cgroup_path
+        .rsplit('/')
+        .find(|component| unit_type(component).is_some())
+        .map(str::to_string)
+}
+
+/// Cgroup that usage is aggregated for: the path up to its innermost unit, so sub-groups
+/// a service creates for itself count towards the service
+pub fn group_path(cgroup_path: &str) -> String {
+    let components: Vec<&str> = cgroup_path.split('/').collect();
+    match components
+        .iter()
+        .rposition(|component| unit_type(component).is_some())
+    {
+        Some(unit) => components[..=unit].join("/"),
+        None => cgroup_path.to_string(),
+    }
+}
+
+/// Mount point of the unified (v2) hierarchy, also in hybrid setups
+pub fn unified_root() -> Option<PathBuf> {
+    ["/sys/fs/cgroup", "/sys/fs/cgroup/unified"]
+        .iter()
+        .map(PathBuf::from)
+        .find(|root| root.join("cgroup.controllers").exists())
+}
+
+/// Cumulative counters of one cgroup, including its descendants
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CgroupStats {
+    /// cpu.stat usage_usec
+    pub cpu_usec: u64,
+    /// memory.current; absent when the memory controller is not enabled for the group
+    pub memory: Option<u64>,
+    /// io.stat rbytes and wbytes summed over devices
+    pub read_bytes: u64,
+    pub write_bytes: u64,
+    /// cpu/memory/io.pressure in `PressureResource::ALL` order; absent without PSI
+    pub pressure: [Option<Pressure>; 3],
+}
+
+/// Read the v2 interface files of `cgroup_path` below `root`; `None` without cpu.stat
+pub fn read_cgroup_stats(root: &Path, cgroup_path: &str) -> Option<CgroupStats> {
+    let dir = root.join(cgroup_path.trim_start_matches('/'));
+    let cpu_usec = parse_cpu_stat(&fs::read_to_string(dir.join("cpu.stat")).ok()?)?;
+    let memory = fs::read_to_string(dir.join("memory.current"))
+        .ok()
+        .and_then(|contents| contents.trim().parse().ok());
+    let (read_bytes, write_bytes) = fs::read_to_string(dir.join("io.stat"))
+        .map(|contents| parse_io_stat(&contents))
+        .unwrap_or_default();
+    Some(CgroupStats {
+        cpu_usec,
+        memory,
+        read_bytes,
+        write_bytes,
+        pressure: PressureResource::ALL
+            .map(|resource| pressure::read_pressure(&dir, ".pressure", resource)),
+    })
+}
+
+/// usage_usec from cpu.stat
+pub fn parse_cpu_stat(contents: &str) -> Option<u64> {
+    contents.lines().find_map(|line| {
+        let (key, value) = line.split_once(' ')?;
+        (key == "usage_usec").then(|| value.trim().parse().ok())?
+    })
+}
+
+/// Total (read, written) bytes from io.stat lines like "8:0 rbytes=1 wbytes=2 rios=3 ..."
+pub fn parse_io_stat(contents: &str) -> (u64, u64) {
+    let mut totals = (0, 0);
+    for field in contents.split_whitespace() {
+        let Some((key, value)) = field.split_once('=') else {
+            continue;
+        };
+        let value: u64 = value.parse().unwrap_or(0);
+        match key {
+            "rbytes" => totals.0 += value,
+            "wbytes" => totals.1 += value,
+            _ => {}
+        }
+    }
+    totals
+}
+
+/// Container names from a local Docker or Podman API socket, looked up by short ID
+#[derive(Default)]
+pub struct ContainerNames {
+    names: HashMap<String, String>,
+    last_query: Option<Instant>,
+}
+
+impl ContainerNames {
+    pub fn name(&self, id: &str) -> Option<&str> {
+        self.names.get(id).map(String::as_str)
+    }
+
+    /// Ask the runtime again when containers with unknown IDs show up, rate limited so a
+    /// missing or unreachable socket costs nothing most refreshes
+    pub fn resolve<'a>(&mut self, mut ids: impl Iterator<Item = &'a str>) {
+        if !ids.any(|id| !self.names.contains_key(id)) {
+            return;
+        }
+        if self
+            .last_query
+            .is_some_and(|at| at.elapsed() < RUNTIME_QUERY_INTERVAL)
+        {
+            return;
+        }
+        self.last_query = Some(Instant::now());
+        if let Some(names) = RUNTIME_SOCKETS
+            .iter()
+            .find_map(|socket| query_containers(Path::new(socket)))
+        {
+            self.names = names;
+        }
+    }
+}
+
+#[cfg(unix)]
+fn query_containers(socket: &Path) -> Option<HashMap<String, String>> {
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixStream;
+
+    let mut stream = UnixStream::connect(socket).ok()?;
+    stream.set_read_timeout(Some(RUNTIME_TIMEOUT)).ok()?;
+    stream.set_write_timeout(Some(RUNTIME_TIMEOUT)).ok()?;
+    // HTTP/1.0 so the reply is neither chunked nor kept alive
+    stream
+        .write_all(b"GET /containers/json HTTP/1.0\r\nHost: localhost\r\n\r\n")
+        .ok()?;
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).ok()?;
+    let response = String::from_utf8_lossy(&response);
+    let (_, body) = response.split_once("\r\n\r\n")?;
+    parse_container_list(body)
+}
+
+#[cfg(not(unix))]
+fn query_containers(_socket: &Path) -> Option<HashMap<String, String>> {
+    None
+}
+
+/// Map short container IDs to names from a /containers/json reply
+pub fn parse_container_list(body: &str) -> Option<HashMap<String, String>> {
+    #[derive(Deserialize)]
+    struct Container {
+        #[serde(rename = "Id")]
+        id: String,
+        #[serde(rename = "Names", default)]
+        names: Vec<String>,
+    }
+
+    let containers: Vec<Container> = serde_json::from_str(body).ok()?;
+    Some(
+        containers
+            .into_iter()
+            .filter(|c| c.id.len() >= 12)
+            .filter_map(|c| {
+                let name = c.names.first()?.trim_start_matches('/').to_string();
+                Some((c.id[..12].to_string(), name))
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::pressure::PsiAverages;
+
+    const ID: &str = "4f1c2e9a7b3d5f6e8a9b0c1d2e3f4a5b6c7d8e9f0a1b2c3d4e5f6a7b8c9d0e1f";
+
+    #[test]
+    fn test_parse_proc_cgroup_prefers_unified() {
+        assert_eq!(
+            parse_proc_cgroup("0::/user.slice/user-1000.slice/session-2.scope\n"),
+            "/user.slice/user-1000.slice/session-2.scope"
+        );
+
+        let hybrid = "12:cpu,cpuacct:/system.slice/nginx.service\n\
+                      1:name=systemd:/system.slice/nginx.service\n\
+                      0::/system.slice/nginx.service\n";
+        assert_eq!(parse_proc_cgroup(hybrid), "/system.slice/nginx.service");
+
+        let v1 = "4:memory:/docker/abc\n1:name=systemd:/system.slice/docker.service\n";
+        assert_eq!(parse_proc_cgroup(v1), "/system.slice/docker.service");
+        assert_eq!(parse_proc_cgroup(""), "");
+    }
+
+    #[test]
+    fn test_container_id() {
+        let short = &ID[..12];
+        assert_eq!(
+            container_id(&format!("/system.slice/docker-{}.scope", ID)).as_deref(),
+            Some(short)
+        );
+        assert_eq!(
+            container_id(&format!("/docker/{}", ID)).as_deref(),
+            Some(short)
+        );
+        assert_eq!(
+            container_id(&format!(
+                "/kubepods.slice/kubepods-pod1.slice/cri-containerd-{}.scope",
+                ID
+            ))
+            .as_deref(),
+            Some(short)
+        );
+        assert_eq!(
+            container_id(&format!("/machine.slice/libpod-{}.scope/container", ID)).as_deref(),
+            Some(short)
+        );
+        assert_eq!(container_id("/user.slice/user-1000.slice"), None);
+    }
+
+    #[test]
+    fn test_systemd_unit_and_group() {
+        let path = "/user.slice/user-1000.slice/user@1000.service/app.slice/foo.service/sub";
+        assert_eq!(systemd_unit(path).as_deref(), Some("foo.service"));
+        assert_eq!(
+            group_path(path),
+            "/user.slice/user-1000.slice/user@1000.service/app.slice/foo.service"
+        );
+        assert_eq!(
+            systemd_unit("/system.slice/nginx.service").as_deref(),
+            Some("nginx.service")
+        );
+        assert_eq!(systemd_unit("/user.slice"), None);
+        assert_eq!(unit_type("session-2.scope"), Some("scope"));
+        assert_eq!(group_path("/user.slice"), "/user.slice");
+    }
+
+    #[test]
+    fn test_cgroup_stats() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let group = dir.path().join("system.slice/nginx.service");
+        fs::create_dir_all(&group).unwrap();
+        fs::write(
+            group.join("cpu.stat"),
+            "usage_usec 1500000\nuser_usec 1000000\nsystem_usec 500000\n",
+        )
+        .unwrap();
+        fs::write(group.join("memory.current"), "4096\n").unwrap();
+        fs::write(
+            group.join("io.stat"),
+            "8:0 rbytes=100 wbytes=20 rios=1 wios=1 dbytes=0 dios=0\n\
+             8:16 rbytes=5 wbytes=1 rios=1 wios=1 dbytes=0 dios=0\n",
+        )
+        .unwrap();
+        fs::write(
+            group.join("memory.pressure"),
+            "some avg10=2.50 avg60=1.00 avg300=0.50 total=900\n\
+             full avg10=1.25 avg60=0.50 avg300=0.25 total=400\n",
+        )
+        .unwrap();
+
+        let some = PsiAverages {
+            avg10: 2.5,
+            avg60: 1.0,
+            avg300: 0.5,
+            total: 900,
+        };
+        let full = PsiAverages {
+            avg10: 1.25,
+            avg60: 0.5,
+            avg300: 0.25,
+            total: 400,
+        };
+        assert_eq!(
+            read_cgroup_stats(dir.path(), "/system.slice/nginx.service"),
+            Some(CgroupStats {
+                cpu_usec: 1_500_000,
+                memory: Some(4096),
+                read_bytes: 105,
+                write_bytes: 21,
+                pressure: [
+                    None,
+                    Some(Pressure {
+                        some,
+                        full: Some(full)
+                    }),
+                    None
+                ],
+            })
+        );
+        assert_eq!(read_cgroup_stats(dir.path(), "/missing.service"), None);
+    }
+
+    #[test]
+    fn test_parse_container_list() {
+        let body = format!(
+            r#"[{{"Id":"{}","Names":["/web"],"Image":"nginx"}},{{"Id":"short","Names":["/x"]}}]"#,
+            ID
+        );
+        let names = parse_container_list(&body).unwrap();
+        assert_eq!(names.len(), 1);
+        assert_eq!(names.get(&ID[..12]).map(String::as_str), Some("web"));
+        assert!(parse_container_list("not json").is_none());
+    }
+}
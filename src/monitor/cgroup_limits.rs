@@ -0,0 +1,382 @@
+/// CPU and memory limits of the cgroup rtop itself runs in (cgroup v1 or v2), so usage
+/// inside a container is measured against the container's share rather than host totals
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// v1 reports "no limit" as a huge page-aligned number rather than a keyword
+const V1_UNLIMITED: u64 = 1 << 60;
+
+/// Limits and usage of the cgroup; each field is `None` when unlimited or unreadable
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CgroupLimits {
+    /// Tightest memory limit of the cgroup and its ancestors, in bytes
+    pub memory_limit: Option<u64>,
+    /// Memory charged to the cgroup minus reclaimable inactive file cache
+    pub memory_usage: Option<u64>,
+    /// CPUs worth of bandwidth granted by the CFS quota
+    pub cpu_quota: Option<f64>,
+    /// CPUs the cpuset allows
+    pub cpuset_cpus: Option<usize>,
+    /// Cumulative CPU time used by the cgroup, in microseconds
+    pub cpu_usage_usec: Option<u64>,
+}
+
+impl CgroupLimits {
+    /// CPUs the cgroup can use when that is fewer than the host has
+    pub fn cpu_limit(&self, host_cpus: usize) -> Option<f64> {
+        let cpuset = self.cpuset_cpus.map(|cpus| cpus as f64);
+        let limit = match (self.cpu_quota, cpuset) {
+            (Some(quota), Some(cpuset)) => quota.min(cpuset),
+            (quota, cpuset) => quota.or(cpuset)?,
+        };
+        (limit < host_cpus as f64).then_some(limit)
+    }
+}
+
+#[derive(Clone)]
+enum Hierarchy {
+    /// Directory of the cgroup in the unified hierarchy, and the mount point
+    V2 { dir: PathBuf, root: PathBuf },
+    /// Directory of the cgroup per controller
+    V1 {
+        memory: Option<PathBuf>,
+        cpu: Option<PathBuf>,
+        cpuacct: Option<PathBuf>,
+        cpuset: Option<PathBuf>,
+    },
+}
+
+/// Detected once at startup and cloned into each monitor that reads the limits
+#[derive(Clone)]
+pub struct CgroupLimitReader {
+    hierarchy: Option<Hierarchy>,
+    /// Whether rtop runs in a container rather than just a limited cgroup on the host
+    in_container: bool,
+}
+
+impl CgroupLimitReader {
+    pub fn detect() -> Self {
+        let proc_cgroup = fs::read_to_string("/proc/self/cgroup").unwrap_or_default();
+        let mut reader = Self::new(Path::new("/sys/fs/cgroup"), &proc_cgroup);
+        reader.in_container = Path::new("/.dockerenv").exists()
+            || Path::new("/run/.containerenv").exists()
+            || std::env::var_os("container").is_some();
+        reader
+    }
+
+    /// `root` is where cgroups are mounted and `proc_cgroup` the contents of
+    /// /proc/self/cgroup; a fixture directory works as well as the real mount
+    pub fn new(root: &Path, proc_cgroup: &str) -> Self {
+        let entries: Vec<(&str, &str)> = proc_cgroup
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(3, ':');
+                let _id = parts.next()?;
+                Some((parts.next()?, parts.next()?))
+            })
+            .collect();
+
+        // Inside a cgroup namespace or with the group bind-mounted at the root, the path
+        // from /proc/self/cgroup does not exist below the mount and the root is the group
+        let locate = |base: PathBuf, path: &str| {
+            let dir = base.join(path.trim_start_matches('/'));
+            if dir.is_dir() {
+                dir
+            } else {
+                base
+            }
+        };
+
+        let hierarchy = if root.join("cgroup.controllers").exists() {
+            entries
+                .iter()
+                .find(|(controllers, _)| controllers.is_empty())
+                .map(|(_, path)| Hierarchy::V2 {
+                    dir: locate(root.to_path_buf(), path),
+                    root: root.to_path_buf(),
+                })
+        } else {
+            let controller = |name: &str| {
+                entries
+                    .iter()
+                    .find(|(controllers, _)| controllers.split(',').any(|c| c == name))
+                    .and_then(|(controllers, path)| {
+                        // Co-mounted controllers share a directory such as "cpu,cpuacct"
+                        [name, controllers]
+                            .iter()
+                            .map(|dir| root.join(dir))
+                            .find(|base| base.is_dir())
+                            .map(|base| locate(base, path))
+                    })
+            };
+            Some(Hierarchy::V1 {
+                memory: controller("memory"),
+                cpu: controller("cpu"),
+                cpuacct: controller("cpuacct"),
+                cpuset: controller("cpuset"),
+            })
+        };
+
+        Self {
+            hierarchy,
+            in_container: false,
+        }
+    }
+
+    pub fn read(&self) -> CgroupLimits {
+        match &self.hierarchy {
+            Some(Hierarchy::V2 { dir, root }) => read_v2(dir, root),
+            Some(Hierarchy::V1 {
+                memory,
+                cpu,
+                cpuacct,
+                cpuset,
+            }) => read_v1(
+                memory.as_deref(),
+                cpu.as_deref(),
+                cpuacct.as_deref(),
+                cpuset.as_deref(),
+            ),
+            None => CgroupLimits::default(),
+        }
+    }
+
+    /// Directory of rtop's own cgroup in the unified hierarchy, unless that is the host's
+    /// root cgroup, whose files describe the whole system
+    pub fn unified_dir(&self) -> Option<&Path> {
+        match &self.hierarchy {
+            Some(Hierarchy::V2 { dir, root }) if dir != root || self.in_container => Some(dir),
+            _ => None,
+        }
+    }
+
+    pub fn in_container(&self) -> bool {
+        self.in_container
+    }
+}
+
+fn read_v2(dir: &Path, root: &Path) -> CgroupLimits {
+    let mut limits = CgroupLimits::default();
+
+    // Limits of ancestors apply too; the tightest one wins
+    for dir in dir.ancestors().take_while(|dir| dir.starts_with(root)) {
+        if let Some(memory) = read_trimmed(&dir.join("memory.max")).and_then(|v| v.parse().ok()) {
+            limits.memory_limit = Some(limits.memory_limit.map_or(memory, |m: u64| m.min(memory)));
+        }
+        if let Some(quota) = read_trimmed(&dir.join("cpu.max")).and_then(|v| parse_cpu_max(&v)) {
+            limits.cpu_quota = Some(limits.cpu_quota.map_or(quota, |q: f64| q.min(quota)));
+        }
+    }
+
+    limits.memory_usage = read_trimmed(&dir.join("memory.current"))
+        .and_then(|v| v.parse::<u64>().ok())
+        .map(|current| current.saturating_sub(inactive_file(dir, "inactive_file")));
+    limits.cpuset_cpus = read_trimmed(&dir.join("cpuset.cpus.effective"))
+        .or_else(|| read_trimmed(&dir.join("cpuset.cpus")))
+        .and_then(|list| count_cpu_list(&list));
+    limits.cpu_usage_usec = fs::read_to_string(dir.join("cpu.stat"))
+        .ok()
+        .and_then(|contents| super::cgroup::parse_cpu_stat(&contents));
+    limits
+}
+
+fn read_v1(
+    memory: Option<&Path>,
+    cpu: Option<&Path>,
+    cpuacct: Option<&Path>,
+    cpuset: Option<&Path>,
+) -> CgroupLimits {
+    let number = |dir: Option<&Path>, file: &str| -> Option<i64> {
+        read_trimmed(&dir?.join(file))?.parse().ok()
+    };
+
+    let mut limits = CgroupLimits::default();
+    if let Some(memory) = memory {
+        // hierarchical_memory_limit already folds in the ancestors' limits
+        limits.memory_limit = fs::read_to_string(memory.join("memory.stat"))
+            .ok()
+            .and_then(|stat| stat_value(&stat, "hierarchical_memory_limit"))
+            .or_else(|| number(Some(memory), "memory.limit_in_bytes").map(|v| v as u64))
+            .filter(|&limit| limit < V1_UNLIMITED);
+        limits.memory_usage = number(Some(memory), "memory.usage_in_bytes").map(|usage| {
+            (usage as u64).saturating_sub(inactive_file(memory, "total_inactive_file"))
+        });
+    }
+    if let (Some(quota), Some(period)) = (
+        number(cpu, "cpu.cfs_quota_us"),
+        number(cpu, "cpu.cfs_period_us"),
+    ) {
+        limits.cpu_quota = (quota > 0 && period > 0).then(|| quota as f64 / period as f64);
+    }
+    limits.cpuset_cpus = cpuset
+        .and_then(|dir| read_trimmed(&dir.join("cpuset.cpus")))
+        .and_then(|list| count_cpu_list(&list));
+    // cpuacct.usage is in nanoseconds
+    limits.cpu_usage_usec = number(cpuacct, "cpuacct.usage").map(|ns| ns as u64 / 1000);
+    limits
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|contents| contents.trim().to_string())
+}
+
+/// Page cache the kernel can drop at will; `docker stats` leaves it out of usage as well
+fn inactive_file(dir: &Path, key: &str) -> u64 {
+    fs::read_to_string(dir.join("memory.stat"))
+        .ok()
+        .and_then(|stat| stat_value(&stat, key))
+        .unwrap_or(0)
+}
+
+fn stat_value(stat: &str, key: &str) -> Option<u64> {
+    stat.lines().find_map(|line| {
+        let (name, value) = line.split_once(' ')?;
+        (name == key).then(|| value.trim().parse().ok())?
+    })
+}
+
+/// CPUs from cpu.max ("quota period", or "max period" when unlimited)
+pub fn parse_cpu_max(contents: &str) -> Option<f64> {
+    let mut fields = contents.split_whitespace();
+    let quota: f64 = fields.next()?.parse().ok()?;
+    let period: f64 = fields.next().unwrap_or("100000").parse().ok()?;
+    (quota > 0.0 && period > 0.0).then(|| quota / period)
+}
+
+/// Number of CPUs in a list such as "0-3,8,10-11"
+pub fn count_cpu_list(list: &str) -> Option<usize> {
+    let mut count = 0;
+    for range in list.split(',').filter(|range| !range.is_empty()) {
+        count += match range.split_once('-') {
+            Some((start, end)) => {
+                let start = start.trim().parse::<usize>().ok()?;
+                end.trim().parse::<usize>().ok()?.checked_sub(start)? + 1
+            }
+            None => {
+                range.trim().parse::<usize>().ok()?;
+                1
+            }
+        };
+    }
+    (count > 0).then_some(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, file: &str, contents: &str) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join(file), contents).unwrap();
+    }
+
+    #[test]
+    fn test_parse_helpers() {
+        assert_eq!(parse_cpu_max("max 100000"), None);
+        assert_eq!(parse_cpu_max("150000 100000"), Some(1.5));
+        assert_eq!(count_cpu_list("0-3,8,10-11"), Some(7));
+        assert_eq!(count_cpu_list("5"), Some(1));
+        assert_eq!(count_cpu_list(""), None);
+        assert_eq!(count_cpu_list("x"), None);
+        assert_eq!(count_cpu_list("3-1"), None);
+    }
+
+    #[test]
+    fn test_v2_limits_from_fixture() {
+        let root = TempDir::new().unwrap();
+        write(
+            root.path(),
+            "cgroup.controllers",
+            "cpuset cpu io memory pids\n",
+        );
+        let parent = root.path().join("system.slice");
+        write(&parent, "memory.max", "536870912\n");
+        write(&parent, "cpu.max", "max 100000\n");
+        let group = parent.join("docker-abc.scope");
+        write(&group, "memory.max", "max\n");
+        write(&group, "memory.current", "300000000\n");
+        write(
+            &group,
+            "memory.stat",
+            "anon 200000000\ninactive_file 50000000\n",
+        );
+        write(&group, "cpu.max", "200000 100000\n");
+        write(&group, "cpuset.cpus.effective", "0-3\n");
+        write(&group, "cpu.stat", "usage_usec 4200\nuser_usec 4000\n");
+
+        let reader = CgroupLimitReader::new(root.path(), "0::/system.slice/docker-abc.scope\n");
+        let limits = reader.read();
+        assert_eq!(
+            limits,
+            CgroupLimits {
+                memory_limit: Some(512 * 1024 * 1024),
+                memory_usage: Some(250_000_000),
+                cpu_quota: Some(2.0),
+                cpuset_cpus: Some(4),
+                cpu_usage_usec: Some(4200),
+            }
+        );
+        assert_eq!(limits.cpu_limit(64), Some(2.0));
+        assert_eq!(limits.cpu_limit(2), None);
+
+        // In a cgroup namespace the listed path is "/" and the mount is the group itself
+        let reader = CgroupLimitReader::new(&group, "0::/\n");
+        assert_eq!(reader.read().cpu_quota, None);
+        write(&group, "cgroup.controllers", "cpu memory\n");
+        let reader = CgroupLimitReader::new(&group, "0::/\n");
+        assert_eq!(reader.read().cpu_quota, Some(2.0));
+    }
+
+    #[test]
+    fn test_v1_limits_from_fixture() {
+        let root = TempDir::new().unwrap();
+        let memory = root.path().join("memory/docker/abc");
+        write(&memory, "memory.limit_in_bytes", "1073741824\n");
+        write(&memory, "memory.usage_in_bytes", "600000000\n");
+        write(
+            &memory,
+            "memory.stat",
+            "cache 1000\nhierarchical_memory_limit 1073741824\ntotal_inactive_file 100000000\n",
+        );
+        let cpu = root.path().join("cpu,cpuacct/docker/abc");
+        write(&cpu, "cpu.cfs_quota_us", "50000\n");
+        write(&cpu, "cpu.cfs_period_us", "100000\n");
+        write(&cpu, "cpuacct.usage", "5000000\n");
+        write(
+            &root.path().join("cpuset/docker/abc"),
+            "cpuset.cpus",
+            "0-7\n",
+        );
+
+        let proc_cgroup = "7:cpuset:/docker/abc\n\
+                           4:cpu,cpuacct:/docker/abc\n\
+                           3:memory:/docker/abc\n\
+                           1:name=systemd:/docker/abc\n";
+        let limits = CgroupLimitReader::new(root.path(), proc_cgroup).read();
+        assert_eq!(
+            limits,
+            CgroupLimits {
+                memory_limit: Some(1024 * 1024 * 1024),
+                memory_usage: Some(500_000_000),
+                cpu_quota: Some(0.5),
+                cpuset_cpus: Some(8),
+                cpu_usage_usec: Some(5000),
+            }
+        );
+
+        // Unlimited groups report a huge limit and a quota of -1
+        write(
+            &memory,
+            "memory.stat",
+            "hierarchical_memory_limit 9223372036854771712\n",
+        );
+        write(&memory, "memory.limit_in_bytes", "9223372036854771712\n");
+        write(&cpu, "cpu.cfs_quota_us", "-1\n");
+        let limits = CgroupLimitReader::new(root.path(), proc_cgroup).read();
+        assert_eq!(limits.memory_limit, None);
+        assert_eq!(limits.cpu_quota, None);
+    }
+}
@@ -1,18 +1,53 @@
-use std::collections::VecDeque;
+use super::cgroup_limits::CgroupLimitReader;
+use super::cpu_times::{CpuTimeShares, ProcStatCpus};
+use super::cpu_topology::{CpuFrequency, CpuTopology, TopologyReader};
+use std::collections::{HashMap, VecDeque};
+use std::time::Instant;
 use sysinfo::{CpuRefreshKind, RefreshKind, System};
 
 const HISTORY_SIZE: usize = 61;
 
+/// One logical CPU: its usage and history, clock, and place in the topology
+#[derive(Debug, Clone)]
+pub struct CpuCoreInfo {
+    /// Kernel CPU number (cpu0, cpu1, ...)
+    pub id: usize,
+    pub usage: f32,
+    pub history: Vec<f32>,
+    pub frequency: CpuFrequency,
+    pub topology: CpuTopology,
+    /// /proc/stat breakdown over the last refresh (Linux)
+    pub times: Option<CpuTimeShares>,
+}
+
 pub struct CpuMonitor {
     system: System,
     history: Vec<VecDeque<f32>>,
+    topology_reader: TopologyReader,
+    /// Kernel CPU number of each sysinfo CPU, which may skip offline CPUs
+    ids: Vec<usize>,
+    topology: Vec<CpuTopology>,
+    frequencies: Vec<CpuFrequency>,
+    /// Previous /proc/stat reading; time shares are the difference to the next one
+    last_stat: Option<ProcStatCpus>,
+    time_shares: Option<CpuTimeShares>,
+    core_time_shares: HashMap<usize, CpuTimeShares>,
+    time_history: VecDeque<CpuTimeShares>,
+    cgroup: CgroupLimitReader,
+    /// CPUs the cgroup may use, when fewer than the host has
+    cpu_limit: Option<f64>,
+    /// Previous cgroup CPU time reading (µs)
+    last_cgroup_usage: Option<(Instant, u64)>,
+    /// Cgroup CPU usage as a share of its limit
+    limited_usage: Option<f32>,
 }
 
 impl CpuMonitor {
-    pub fn new() -> Self {
+    pub fn new(cgroup: CgroupLimitReader) -> Self {
         let mut system =
             System::new_with_specifics(RefreshKind::new().with_cpu(CpuRefreshKind::everything()));
         system.refresh_cpu_all();
+        let last_stat = ProcStatCpus::read();
 
         // Wait a bit to get accurate CPU usage
         std::thread::sleep(std::time::Duration::from_millis(200));
@@ -20,12 +55,47 @@ impl CpuMonitor {
 
         let cpu_count = system.cpus().len();
         let history = vec![VecDeque::from(vec![0.0; HISTORY_SIZE]); cpu_count];
+        let ids: Vec<usize> = system
+            .cpus()
+            .iter()
+            .enumerate()
+            .map(|(i, cpu)| {
+                cpu.name()
+                    .trim_start_matches(|c: char| !c.is_ascii_digit())
+                    .parse()
+                    .unwrap_or(i)
+            })
+            .collect();
+        let topology_reader = TopologyReader::new();
+        let topology = topology_reader.topology(&ids);
 
-        Self { system, history }
+        let mut monitor = Self {
+            system,
+            history,
+            topology_reader,
+            ids,
+            topology,
+            frequencies: Vec::new(),
+            last_stat,
+            time_shares: None,
+            core_time_shares: HashMap::new(),
+            time_history: VecDeque::from(vec![CpuTimeShares::default(); HISTORY_SIZE]),
+            cgroup,
+            cpu_limit: None,
+            last_cgroup_usage: None,
+            limited_usage: None,
+        };
+        monitor.sample_cgroup();
+        monitor.sample_frequencies();
+        monitor.sample_times();
+        monitor
     }
 
     pub fn update(&mut self) {
         self.system.refresh_cpu_all();
+        self.sample_cgroup();
+        self.sample_frequencies();
+        self.sample_times();
 
         for (i, cpu) in self.system.cpus().iter().enumerate() {
             if i < self.history.len() {
@@ -35,9 +105,86 @@ impl CpuMonitor {
         }
     }
 
-    #[allow(dead_code)]
-    pub fn get_cpu_count(&self) -> usize {
-        self.system.cpus().len()
+    /// cpufreq where the kernel exposes it, otherwise the clock sysinfo reads from
+    /// /proc/cpuinfo
+    fn sample_frequencies(&mut self) {
+        self.frequencies = self
+            .ids
+            .iter()
+            .zip(self.system.cpus())
+            .map(|(&id, cpu)| {
+                let mut frequency = self.topology_reader.frequency(id);
+                if frequency.current.is_none() && cpu.frequency() > 0 {
+                    frequency.current = Some(cpu.frequency());
+                }
+                frequency
+            })
+            .collect();
+    }
+
+    fn sample_times(&mut self) {
+        let Some(stat) = ProcStatCpus::read() else {
+            return;
+        };
+        if let Some(last) = &self.last_stat {
+            let shares = stat.total.shares_since(&last.total);
+            self.core_time_shares = stat
+                .cores
+                .iter()
+                .filter_map(|(id, times)| Some((*id, times.shares_since(last.cores.get(id)?))))
+                .collect();
+            self.time_shares = Some(shares);
+            self.time_history.pop_front();
+            self.time_history.push_back(shares);
+        }
+        self.last_stat = Some(stat);
+    }
+
+    /// Where all CPUs spent their time over the last refresh
+    pub fn times(&self) -> Option<CpuTimeShares> {
+        self.time_shares
+    }
+
+    pub fn time_history(&self) -> &VecDeque<CpuTimeShares> {
+        &self.time_history
+    }
+
+    /// Per-core figures stay host-wide (they come from /proc/stat), but with a CPU limit
+    /// the cgroup's own CPU time is measured against the CPUs it is allowed
+    fn sample_cgroup(&mut self) {
+        let limits = self.cgroup.read();
+        self.cpu_limit = limits.cpu_limit(self.system.cpus().len());
+        let now = Instant::now();
+        let (Some(limit), Some(usage)) = (self.cpu_limit, limits.cpu_usage_usec) else {
+            self.limited_usage = None;
+            self.last_cgroup_usage = None;
+            return;
+        };
+        if let Some((at, previous)) = self.last_cgroup_usage {
+            let elapsed = now.duration_since(at).as_secs_f64();
+            if elapsed > 0.0 {
+                let used_cpus = usage.saturating_sub(previous) as f64 / 1e6 / elapsed;
+                self.limited_usage = Some((used_cpus / limit * 100.0).min(100.0) as f32);
+            }
+        }
+        self.last_cgroup_usage = Some((now, usage));
+    }
+
+    pub fn cpu_limit(&self) -> Option<f64> {
+        self.cpu_limit
+    }
+
+    pub fn in_container(&self) -> bool {
+        self.cgroup.in_container()
+    }
+
+    /// Overall usage: against the cgroup's CPU limit when there is one, otherwise the
+    /// mean over all cores
+    pub fn average(&self) -> f32 {
+        self.limited_usage.unwrap_or_else(|| {
+            let cpus = self.system.cpus();
+            cpus.iter().map(|cpu| cpu.cpu_usage()).sum::<f32>() / cpus.len().max(1) as f32
+        })
     }
 
     #[allow(dead_code)]
@@ -52,17 +199,20 @@ impl CpuMonitor {
         }
     }
 
-    pub fn get_all_cpu_data(&self) -> Vec<(String, f32, Vec<f32>)> {
-        let cpus = self.system.cpus();
-        let mut result = Vec::with_capacity(cpus.len());
-
-        for (i, cpu) in cpus.iter().enumerate() {
-            let name = format!("CPU{} {:>5.1}%", i + 1, cpu.cpu_usage());
-            let usage = cpu.cpu_usage();
-            let history: Vec<f32> = self.history[i].iter().copied().collect();
-            result.push((name, usage, history));
-        }
-
-        result
+    /// Every logical CPU in kernel order
+    pub fn cores(&self) -> Vec<CpuCoreInfo> {
+        self.system
+            .cpus()
+            .iter()
+            .enumerate()
+            .map(|(i, cpu)| CpuCoreInfo {
+                id: self.ids[i],
+                usage: cpu.cpu_usage(),
+                history: self.history[i].iter().copied().collect(),
+                frequency: self.frequencies.get(i).cloned().unwrap_or_default(),
+                topology: self.topology.get(i).cloned().unwrap_or_default(),
+                times: self.core_time_shares.get(&self.ids[i]).copied(),
+            })
+            .collect()
     }
 }
@@ -0,0 +1,222 @@
+/// Where CPU time goes, from the cpu lines of /proc/stat
+use std::collections::HashMap;
+use std::fs;
+
+/// Cumulative clock ticks of one `cpu` line
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CpuTimes {
+    pub user: u64,
+    pub nice: u64,
+    pub system: u64,
+    pub idle: u64,
+    pub iowait: u64,
+    pub irq: u64,
+    pub softirq: u64,
+    pub steal: u64,
+    /// Already counted in `user` and `nice` by the kernel
+    pub guest: u64,
+    pub guest_nice: u64,
+}
+
+impl CpuTimes {
+    /// Share of each kind of time since `earlier`, in percent
+    pub fn shares_since(&self, earlier: &CpuTimes) -> CpuTimeShares {
+        let delta = |now: u64, before: u64| now.saturating_sub(before) as f64;
+        let guest = delta(self.guest, earlier.guest);
+        let guest_nice = delta(self.guest_nice, earlier.guest_nice);
+        let user = (delta(self.user, earlier.user) - guest).max(0.0);
+        let nice = (delta(self.nice, earlier.nice) - guest_nice).max(0.0);
+        let system = delta(self.system, earlier.system);
+        let idle = delta(self.idle, earlier.idle);
+        let iowait = delta(self.iowait, earlier.iowait);
+        let irq = delta(self.irq, earlier.irq);
+        let softirq = delta(self.softirq, earlier.softirq);
+        let steal = delta(self.steal, earlier.steal);
+
+        let total =
+            user + nice + system + idle + iowait + irq + softirq + steal + guest + guest_nice;
+        if total <= 0.0 {
+            return CpuTimeShares::default();
+        }
+        let percent = |ticks: f64| ticks * 100.0 / total;
+        CpuTimeShares {
+            user: percent(user),
+            nice: percent(nice),
+            system: percent(system),
+            idle: percent(idle),
+            iowait: percent(iowait),
+            irq: percent(irq),
+            softirq: percent(softirq),
+            steal: percent(steal),
+            guest: percent(guest + guest_nice),
+        }
+    }
+}
+
+/// Percent of elapsed CPU time per kind; they add up to 100 once a second sample exists
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CpuTimeShares {
+    pub user: f64,
+    pub nice: f64,
+    pub system: f64,
+    pub idle: f64,
+    /// Idle while the CPU had I/O outstanding
+    pub iowait: f64,
+    pub irq: f64,
+    pub softirq: f64,
+    /// Taken by the hypervisor for other guests
+    pub steal: f64,
+    /// Running guest VMs, guest_nice included
+    pub guest: f64,
+}
+
+impl CpuTimeShares {
+    pub fn get(&self, kind: CpuTimeKind) -> f64 {
+        match kind {
+            CpuTimeKind::User => self.user,
+            CpuTimeKind::Nice => self.nice,
+            CpuTimeKind::System => self.system,
+            CpuTimeKind::Iowait => self.iowait,
+            CpuTimeKind::Irq => self.irq,
+            CpuTimeKind::Softirq => self.softirq,
+            CpuTimeKind::Steal => self.steal,
+            CpuTimeKind::Guest => self.guest,
+        }
+    }
+}
+
+/// Non-idle kinds of time, in stacking order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CpuTimeKind {
+    User,
+    Nice,
+    System,
+    Iowait,
+    Irq,
+    Softirq,
+    Steal,
+    Guest,
+}
+
+impl CpuTimeKind {
+    pub const ALL: [CpuTimeKind; 8] = [
+        CpuTimeKind::User,
+        CpuTimeKind::Nice,
+        CpuTimeKind::System,
+        CpuTimeKind::Iowait,
+        CpuTimeKind::Irq,
+        CpuTimeKind::Softirq,
+        CpuTimeKind::Steal,
+        CpuTimeKind::Guest,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CpuTimeKind::User => "user",
+            CpuTimeKind::Nice => "nice",
+            CpuTimeKind::System => "sys",
+            CpuTimeKind::Iowait => "iowait",
+            CpuTimeKind::Irq => "irq",
+            CpuTimeKind::Softirq => "softirq",
+            CpuTimeKind::Steal => "steal",
+            CpuTimeKind::Guest => "guest",
+        }
+    }
+}
+
+/// The aggregate `cpu` line and the per-CPU `cpuN` lines of /proc/stat
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProcStatCpus {
+    pub total: CpuTimes,
+    pub cores: HashMap<usize, CpuTimes>,
+}
+
+impl ProcStatCpus {
+    pub fn read() -> Option<Self> {
+        fs::read_to_string("/proc/stat")
+            .ok()
+            .and_then(|contents| parse_proc_stat(&contents))
+    }
+}
+
+pub fn parse_proc_stat(contents: &str) -> Option<ProcStatCpus> {
+    let mut total = None;
+    let mut cores = HashMap::new();
+    for line in contents.lines() {
+        let mut fields = line.split_whitespace();
+        let Some(name) = fields.next().and_then(|name| name.strip_prefix("cpu")) else {
+            continue;
+        };
+        // Older kernels report fewer columns; missing ones read as zero
+        let mut next = || fields.next().and_then(|f| f.parse().ok()).unwrap_or(0);
+        let times = CpuTimes {
+            user: next(),
+            nice: next(),
+            system: next(),
+            idle: next(),
+            iowait: next(),
+            irq: next(),
+            softirq: next(),
+            steal: next(),
+            guest: next(),
+            guest_nice: next(),
+        };
+        if name.is_empty() {
+            total = Some(times);
+        } else if let Ok(id) = name.parse() {
+            cores.insert(id, times);
+        }
+    }
+    Some(ProcStatCpus {
+        total: total?,
+        cores,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_stat() {
+        let contents = "cpu  400 20 100 1000 50 10 20 30 40 0\n\
+                        cpu0 200 10 50 500 25 5 10 15 20 0\n\
+                        cpu3 200 10 50 500 25 5 10 15 20 0\n\
+                        intr 12345 0 0\n\
+                        ctxt 987654\n";
+        let stat = parse_proc_stat(contents).unwrap();
+        assert_eq!(stat.total.user, 400);
+        assert_eq!(stat.total.guest, 40);
+        assert_eq!(stat.cores.len(), 2);
+        assert_eq!(stat.cores[&3].steal, 15);
+        assert_eq!(parse_proc_stat("intr 1\n"), None);
+    }
+
+    #[test]
+    fn test_shares_since() {
+        let earlier = CpuTimes::default();
+        let later = CpuTimes {
+            user: 50,
+            nice: 0,
+            system: 20,
+            idle: 110,
+            iowait: 10,
+            irq: 0,
+            softirq: 0,
+            steal: 10,
+            guest: 10,
+            guest_nice: 0,
+        };
+        // 200 ticks in total; guest time is taken out of user
+        let shares = later.shares_since(&earlier);
+        assert_eq!(shares.user, 20.0);
+        assert_eq!(shares.guest, 5.0);
+        assert_eq!(shares.system, 10.0);
+        assert_eq!(shares.idle, 55.0);
+        assert_eq!(shares.steal, 5.0);
+        let busy: f64 = CpuTimeKind::ALL.iter().map(|&k| shares.get(k)).sum();
+        assert!((busy + shares.idle - 100.0).abs() < 1e-9);
+
+        assert_eq!(earlier.shares_since(&later), CpuTimeShares::default());
+    }
+}
@@ -0,0 +1,270 @@
+/// Layout and clock of logical CPUs from /sys/devices/system/cpu (Linux)
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Core class on hybrid CPUs (Intel P/E cores, ARM big.LITTLE)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoreType {
+    Performance,
+    Efficiency,
+}
+
+impl CoreType {
+    pub fn letter(self) -> &'static str {
+        match self {
+            CoreType::Performance => "P",
+            CoreType::Efficiency => "E",
+        }
+    }
+}
+
+/// Where a logical CPU sits; fixed for the life of the process
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CpuTopology {
+    pub package: Option<u32>,
+    /// Physical core within the package
+    pub core: Option<u32>,
+    /// Logical CPUs sharing the physical core (SMT), this one included
+    pub siblings: Vec<usize>,
+    pub core_type: Option<CoreType>,
+}
+
+/// Clock and cpufreq policy of a logical CPU, in MHz
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CpuFrequency {
+    pub current: Option<u64>,
+    pub min: Option<u64>,
+    pub max: Option<u64>,
+    pub governor: Option<String>,
+}
+
+pub struct TopologyReader {
+    /// /sys/devices/system/cpu
+    cpu_dir: PathBuf,
+    /// /sys/devices, where Intel hybrid CPUs list their cpu_core and cpu_atom PMUs
+    devices_dir: PathBuf,
+}
+
+impl TopologyReader {
+    pub fn new() -> Self {
+        Self::with_root(Path::new("/sys/devices"))
+    }
+
+    fn with_root(devices_dir: &Path) -> Self {
+        Self {
+            cpu_dir: devices_dir.join("system/cpu"),
+            devices_dir: devices_dir.to_path_buf(),
+        }
+    }
+
+    /// Topology of each CPU in `ids`, in the same order
+    pub fn topology(&self, ids: &[usize]) -> Vec<CpuTopology> {
+        let core_types = self.core_types(ids);
+        ids.iter()
+            .zip(core_types)
+            .map(|(&id, core_type)| {
+                let dir = self.cpu_dir.join(format!("cpu{}/topology", id));
+                let number = |file: &str| read_trimmed(&dir.join(file))?.parse().ok();
+                CpuTopology {
+                    package: number("physical_package_id"),
+                    core: number("core_id"),
+                    siblings: read_trimmed(&dir.join("thread_siblings_list"))
+                        .map(|list| parse_cpu_list(&list))
+                        .unwrap_or_else(|| vec![id]),
+                    core_type,
+                }
+            })
+            .collect()
+    }
+
+    /// Intel lists hybrid core classes as PMUs; ARM reports a lower cpu_capacity for
+    /// little cores. Uniform CPUs get no type.
+    fn core_types(&self, ids: &[usize]) -> Vec<Option<CoreType>> {
+        let pmu = |name: &str| {
+            read_trimmed(&self.devices_dir.join(name).join("cpus"))
+                .map(|list| parse_cpu_list(&list))
+                .unwrap_or_default()
+        };
+        let (performance, efficiency) = (pmu("cpu_core"), pmu("cpu_atom"));
+        if !performance.is_empty() && !efficiency.is_empty() {
+            return ids
+                .iter()
+                .map(|id| {
+                    if performance.contains(id) {
+                        Some(CoreType::Performance)
+                    } else if efficiency.contains(id) {
+                        Some(CoreType::Efficiency)
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+        }
+
+        let capacities: Vec<Option<u64>> = ids
+            .iter()
+            .map(|id| {
+                read_trimmed(&self.cpu_dir.join(format!("cpu{}/cpu_capacity", id)))?
+                    .parse()
+                    .ok()
+            })
+            .collect();
+        let highest = capacities.iter().flatten().max().copied();
+        let lowest = capacities.iter().flatten().min().copied();
+        if highest == lowest {
+            return vec![None; ids.len()];
+        }
+        capacities
+            .into_iter()
+            .map(|capacity| {
+                capacity.map(|c| {
+                    if Some(c) == highest {
+                        CoreType::Performance
+                    } else {
+                        CoreType::Efficiency
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Frequencies from cpufreq; missing on most VMs, where /proc/cpuinfo is the fallback
+    pub fn frequency(&self, id: usize) -> CpuFrequency {
+        let dir = self.cpu_dir.join(format!("cpu{}/cpufreq", id));
+        let mhz = |file: &str| {
+            read_trimmed(&dir.join(file))?
+                .parse::<u64>()
+                .ok()
+                .map(|khz| khz / 1000)
+        };
+        CpuFrequency {
+            current: mhz("scaling_cur_freq"),
+            min: mhz("scaling_min_freq"),
+            max: mhz("scaling_max_freq"),
+            governor: read_trimmed(&dir.join("scaling_governor")),
+        }
+    }
+}
+
+impl Default for TopologyReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok().map(|s| s.trim().to_string())
+}
+
+/// Expand a list such as "0-3,8,10-11"
+pub fn parse_cpu_list(list: &str) -> Vec<usize> {
+    let mut cpus = Vec::new();
+    for range in list.trim().split(',').filter(|range| !range.is_empty()) {
+        let bounds: Option<(usize, usize)> = match range.split_once('-') {
+            Some((start, end)) => start.trim().parse().ok().zip(end.trim().parse().ok()),
+            None => range.trim().parse().ok().map(|cpu| (cpu, cpu)),
+        };
+        if let Some((start, end)) = bounds {
+            cpus.extend(start..=end);
+        }
+    }
+    cpus
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, path: &str, contents: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn test_parse_cpu_list() {
+        assert_eq!(parse_cpu_list("0-3,8,10-11\n"), vec![0, 1, 2, 3, 8, 10, 11]);
+        assert_eq!(parse_cpu_list("5"), vec![5]);
+        assert!(parse_cpu_list("").is_empty());
+    }
+
+    #[test]
+    fn test_intel_hybrid_topology() {
+        let root = TempDir::new().unwrap();
+        let root = root.path();
+        write(root, "cpu_core/cpus", "0-1\n");
+        write(root, "cpu_atom/cpus", "2\n");
+        for (cpu, core, siblings) in [(0, 0, "0-1"), (1, 0, "0-1"), (2, 8, "2")] {
+            let dir = format!("system/cpu/cpu{}", cpu);
+            write(
+                root,
+                &format!("{}/topology/physical_package_id", dir),
+                "0\n",
+            );
+            write(
+                root,
+                &format!("{}/topology/core_id", dir),
+                &format!("{}\n", core),
+            );
+            write(
+                root,
+                &format!("{}/topology/thread_siblings_list", dir),
+                siblings,
+            );
+        }
+        write(
+            root,
+            "system/cpu/cpu0/cpufreq/scaling_cur_freq",
+            "3400000\n",
+        );
+        write(
+            root,
+            "system/cpu/cpu0/cpufreq/scaling_max_freq",
+            "5100000\n",
+        );
+        write(
+            root,
+            "system/cpu/cpu0/cpufreq/scaling_governor",
+            "powersave\n",
+        );
+
+        let reader = TopologyReader::with_root(root);
+        let topology = reader.topology(&[0, 1, 2]);
+        assert_eq!(topology[0].package, Some(0));
+        assert_eq!(topology[1].siblings, vec![0, 1]);
+        assert_eq!(topology[2].core, Some(8));
+        assert_eq!(topology[0].core_type, Some(CoreType::Performance));
+        assert_eq!(topology[2].core_type, Some(CoreType::Efficiency));
+
+        let frequency = reader.frequency(0);
+        assert_eq!(frequency.current, Some(3400));
+        assert_eq!(frequency.max, Some(5100));
+        assert_eq!(frequency.min, None);
+        assert_eq!(frequency.governor.as_deref(), Some("powersave"));
+        assert_eq!(reader.frequency(2), CpuFrequency::default());
+    }
+
+    #[test]
+    fn test_core_types_from_capacity() {
+        let root = TempDir::new().unwrap();
+        let root = root.path();
+        write(root, "system/cpu/cpu0/cpu_capacity", "1024\n");
+        write(root, "system/cpu/cpu1/cpu_capacity", "446\n");
+        let reader = TopologyReader::with_root(root);
+        let types: Vec<_> = reader
+            .topology(&[0, 1])
+            .into_iter()
+            .map(|t| t.core_type)
+            .collect();
+        assert_eq!(
+            types,
+            vec![Some(CoreType::Performance), Some(CoreType::Efficiency)]
+        );
+        // No topology files: each CPU is its own sibling
+        assert_eq!(reader.topology(&[1])[0].siblings, vec![1]);
+
+        write(root, "system/cpu/cpu1/cpu_capacity", "1024\n");
+        assert_eq!(reader.topology(&[0, 1])[0].core_type, None);
+    }
+}
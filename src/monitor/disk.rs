@@ -1,35 +1,266 @@
+use std::path::Path;
 use sysinfo::Disks;
 
+/// Filesystem types that never represent real storage
+const PSEUDO_FILESYSTEMS: [&str; 14] = [
+    "overlay",
+    "tmpfs",
+    "devtmpfs",
+    "squashfs",
+    "proc",
+    "sysfs",
+    "cgroup",
+    "cgroup2",
+    "devpts",
+    "mqueue",
+    "hugetlbfs",
+    "debugfs",
+    "tracefs",
+    "autofs",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiskSortOrder {
+    Mount,
+    Size,
+    Percent,
+    Free,
+}
+
+impl DiskSortOrder {
+    pub fn next(self) -> Self {
+        match self {
+            DiskSortOrder::Mount => DiskSortOrder::Size,
+            DiskSortOrder::Size => DiskSortOrder::Percent,
+            DiskSortOrder::Percent => DiskSortOrder::Free,
+            DiskSortOrder::Free => DiskSortOrder::Mount,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            DiskSortOrder::Mount => "Mount",
+            DiskSortOrder::Size => "Size",
+            DiskSortOrder::Percent => "Use%",
+            DiskSortOrder::Free => "Free",
+        }
+    }
+}
+
+/// Usage of one mounted filesystem
+#[derive(Debug, Clone, Default)]
+pub struct FilesystemInfo {
+    pub device: String,
+    pub mount_point: String,
+    pub fs_type: String,
+    pub total: u64,
+    /// Blocks in use; blocks reserved for root are neither used nor available
+    pub used: u64,
+    /// Space unprivileged users can still allocate
+    pub available: u64,
+    pub inodes_total: u64,
+    pub inodes_used: u64,
+    /// Other mount points of the same device (bind mounts, btrfs subvolumes)
+    pub bind_mounts: Vec<String>,
+}
+
+impl FilesystemInfo {
+    /// Share of the space usable without root privileges, like df's Use%
+    pub fn percent(&self) -> f32 {
+        let usable = self.used + self.available;
+        if usable == 0 {
+            0.0
+        } else {
+            (self.used as f64 / usable as f64 * 100.0) as f32
+        }
+    }
+
+    pub fn inode_percent(&self) -> f32 {
+        if self.inodes_total == 0 {
+            0.0
+        } else {
+            (self.inodes_used as f64 / self.inodes_total as f64 * 100.0) as f32
+        }
+    }
+}
+
 pub struct DiskMonitor {
     disks: Disks,
+    filesystems: Vec<FilesystemInfo>,
+    sort_order: DiskSortOrder,
 }
 
 impl DiskMonitor {
     pub fn new() -> Self {
         let disks = Disks::new_with_refreshed_list();
-        Self { disks }
+        let mut monitor = Self {
+            disks,
+            filesystems: Vec::new(),
+            sort_order: DiskSortOrder::Mount,
+        };
+        monitor.collect_filesystems();
+        monitor
     }
 
     pub fn update(&mut self) {
-        self.disks.refresh();
+        // Pick up mounts and unmounts as well as usage changes
+        self.disks.refresh_list();
+        self.collect_filesystems();
     }
 
-    pub fn get_disk_data(&self) -> (f32, u64, u64) {
-        // Get the first disk or aggregate all disks
-        let (total_space, available_space) = self
-            .disks
-            .iter()
-            .fold((0u64, 0u64), |(total, avail), disk| {
-                (total + disk.total_space(), avail + disk.available_space())
-            });
+    pub fn cycle_sort_order(&mut self) {
+        self.sort_order = self.sort_order.next();
+        self.sort_filesystems();
+    }
+
+    pub fn sort_order(&self) -> DiskSortOrder {
+        self.sort_order
+    }
+
+    /// Real filesystems, one entry per device, in the current sort order
+    pub fn get_filesystems(&self) -> &[FilesystemInfo] {
+        &self.filesystems
+    }
+
+    fn collect_filesystems(&mut self) {
+        let mut filesystems: Vec<FilesystemInfo> = Vec::new();
+
+        for disk in self.disks.iter() {
+            let fs_type = disk.file_system().to_string_lossy().to_string();
+            if is_pseudo_filesystem(&fs_type) {
+                continue;
+            }
 
-        if total_space == 0 {
-            return (0.0, 0, 0);
+            let device = disk.name().to_string_lossy().to_string();
+            let mount_point = disk.mount_point().to_string_lossy().to_string();
+
+            // Bind mounts report the same device; keep the shortest mount point as primary
+            if let Some(existing) = filesystems.iter_mut().find(|fs| fs.device == device) {
+                if mount_point.len() < existing.mount_point.len() {
+                    let previous = std::mem::replace(&mut existing.mount_point, mount_point);
+                    existing.bind_mounts.push(previous);
+                } else {
+                    existing.bind_mounts.push(mount_point);
+                }
+                continue;
+            }
+
+            let total = disk.total_space();
+            let available = disk.available_space();
+            let mut info = FilesystemInfo {
+                device,
+                mount_point,
+                fs_type,
+                total,
+                used: total.saturating_sub(available),
+                available,
+                ..FilesystemInfo::default()
+            };
+
+            if let Some(stat) = statvfs(disk.mount_point()) {
+                info.used = stat.used;
+                info.available = stat.available;
+                info.inodes_total = stat.inodes_total;
+                info.inodes_used = stat.inodes_used;
+            }
+
+            filesystems.push(info);
         }
 
-        let used = total_space.saturating_sub(available_space);
-        let percent = (used as f64 / total_space as f64 * 100.0) as f32;
+        self.filesystems = filesystems;
+        self.sort_filesystems();
+    }
+
+    fn sort_filesystems(&mut self) {
+        match self.sort_order {
+            DiskSortOrder::Mount => self
+                .filesystems
+                .sort_by(|a, b| a.mount_point.cmp(&b.mount_point)),
+            DiskSortOrder::Size => self
+                .filesystems
+                .sort_by_key(|fs| std::cmp::Reverse(fs.total)),
+            DiskSortOrder::Percent => self.filesystems.sort_by(|a, b| {
+                b.percent()
+                    .partial_cmp(&a.percent())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            }),
+            DiskSortOrder::Free => self.filesystems.sort_by_key(|fs| fs.available),
+        }
+    }
+}
+
+pub fn is_pseudo_filesystem(fs_type: &str) -> bool {
+    PSEUDO_FILESYSTEMS.contains(&fs_type)
+}
+
+struct StatVfs {
+    used: u64,
+    available: u64,
+    inodes_total: u64,
+    inodes_used: u64,
+}
+
+#[cfg(unix)]
+fn statvfs(path: &Path) -> Option<StatVfs> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: c_path is a valid NUL-terminated string and stat is a properly sized out-param
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+
+    let frsize = stat.f_frsize as u64;
+    Some(StatVfs {
+        used: (stat.f_blocks as u64).saturating_sub(stat.f_bfree as u64) * frsize,
+        available: stat.f_bavail as u64 * frsize,
+        inodes_total: stat.f_files as u64,
+        inodes_used: (stat.f_files as u64).saturating_sub(stat.f_ffree as u64),
+    })
+}
+
+#[cfg(not(unix))]
+fn statvfs(_path: &Path) -> Option<StatVfs> {
+    None
+}
 
-        (percent, used, total_space)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pseudo_filesystems() {
+        assert!(is_pseudo_filesystem("overlay"));
+        assert!(is_pseudo_filesystem("tmpfs"));
+        assert!(is_pseudo_filesystem("squashfs"));
+        assert!(!is_pseudo_filesystem("ext4"));
+        assert!(!is_pseudo_filesystem("btrfs"));
+    }
+
+    #[test]
+    fn test_filesystem_percent() {
+        // 50 blocks reserved for root count as neither used nor available, as in df
+        let fs = FilesystemInfo {
+            total: 1000,
+            used: 250,
+            available: 700,
+            inodes_total: 100,
+            inodes_used: 10,
+            ..FilesystemInfo::default()
+        };
+        assert!((fs.percent() - 26.315_79).abs() < 0.001);
+        assert_eq!(fs.inode_percent(), 10.0);
+        assert_eq!(FilesystemInfo::default().percent(), 0.0);
+    }
+
+    #[test]
+    fn test_sort_order_cycles() {
+        let mut order = DiskSortOrder::Mount;
+        for _ in 0..4 {
+            order = order.next();
+        }
+        assert_eq!(order, DiskSortOrder::Mount);
     }
 }
@@ -1,62 +1,335 @@
-use std::collections::HashMap;
+/// Per-device disk I/O monitoring backed by /proc/diskstats (Linux)
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::Path;
 use std::time::Instant;
-use sysinfo::Disks;
+
+const HISTORY_SIZE: usize = 61;
+const SECTOR_SIZE: u64 = 512;
 
 pub struct DiskIOMonitor {
-    disks: Disks,
     last_stats: HashMap<String, DiskStats>,
-    current_stats: HashMap<String, DiskStats>,
+    devices: Vec<DiskIOInfo>,
+    read_history: HashMap<String, VecDeque<f64>>,
+    write_history: HashMap<String, VecDeque<f64>>,
+    util_history: HashMap<String, VecDeque<f64>>,
     last_update: Instant,
 }
 
-#[allow(dead_code)]
-#[derive(Debug, Clone, Copy)]
-struct DiskStats {
-    read_bytes: u64,
-    write_bytes: u64,
+/// Raw cumulative counters for one line of /proc/diskstats
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DiskStats {
+    pub reads_completed: u64,
+    pub sectors_read: u64,
+    pub ms_reading: u64,
+    pub writes_completed: u64,
+    pub sectors_written: u64,
+    pub ms_writing: u64,
+    pub ms_doing_io: u64,
+    pub weighted_ms_doing_io: u64,
 }
 
 impl DiskIOMonitor {
     pub fn new() -> Self {
-        let mut disks = Disks::new_with_refreshed_list();
-        disks.refresh();
-
-        Self {
-            disks,
+        let mut monitor = Self {
             last_stats: HashMap::new(),
-            current_stats: HashMap::new(),
+            devices: Vec::new(),
+            read_history: HashMap::new(),
+            write_history: HashMap::new(),
+            util_history: HashMap::new(),
             last_update: Instant::now(),
-        }
+        };
+
+        // Prime the counters so the first real update yields rates
+        monitor.last_stats = Self::read_diskstats();
+        monitor
     }
 
     pub fn update(&mut self) {
-        self.disks.refresh_list();
-        self.disks.refresh();
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_update).as_secs_f64();
+        let current_stats = Self::read_diskstats();
 
-        // Update stats
-        self.last_stats = self.current_stats.clone();
-        self.current_stats.clear();
+        if elapsed > 0.0 {
+            let mut infos: Vec<DiskIOInfo> = current_stats
+                .iter()
+                .filter_map(|(name, stats)| {
+                    self.last_stats
+                        .get(name)
+                        .map(|prev| DiskIOInfo::from_delta(name, prev, stats, elapsed))
+                })
+                .collect();
+            infos.sort_by(|a, b| a.name.cmp(&b.name));
 
-        // Note: sysinfo doesn't provide I/O stats directly
-        // This is a placeholder - real implementation would need to read from /proc/diskstats on Linux
+            self.devices = Self::group_partitions(infos);
 
-        self.last_update = Instant::now();
+            for device in &self.devices {
+                Self::push_history(&mut self.read_history, &device.name, device.read_rate);
+                Self::push_history(&mut self.write_history, &device.name, device.write_rate);
+                Self::push_history(&mut self.util_history, &device.name, device.util_percent);
+            }
+
+            // Drop history for devices that disappeared (e.g. unplugged USB disks)
+            let names: Vec<&str> = self.devices.iter().map(|d| d.name.as_str()).collect();
+            self.read_history.retain(|k, _| names.contains(&k.as_str()));
+            self.write_history
+                .retain(|k, _| names.contains(&k.as_str()));
+            self.util_history.retain(|k, _| names.contains(&k.as_str()));
+        }
+
+        self.last_stats = current_stats;
+        self.last_update = now;
     }
 
-    #[allow(dead_code)]
+    /// Whole-disk devices, each carrying its partitions
     pub fn get_disk_io(&self) -> Vec<DiskIOInfo> {
-        // Placeholder for disk I/O info
-        // Real implementation would calculate rates from /proc/diskstats
-        vec![]
+        self.devices.clone()
+    }
+
+    /// Read and write rate history (bytes/sec) for a whole-disk device
+    pub fn get_rate_history(&self, name: &str) -> Option<(Vec<f64>, Vec<f64>)> {
+        let read = self.read_history.get(name)?;
+        let write = self.write_history.get(name)?;
+        Some((
+            read.iter().copied().collect(),
+            write.iter().copied().collect(),
+        ))
+    }
+
+    /// Busy time history (0-100%) for a whole-disk device
+    pub fn get_util_history(&self, name: &str) -> Option<Vec<f64>> {
+        self.util_history
+            .get(name)
+            .map(|h| h.iter().copied().collect())
+    }
+
+    fn push_history(map: &mut HashMap<String, VecDeque<f64>>, name: &str, value: f64) {
+        let history = map
+            .entry(name.to_string())
+            .or_insert_with(|| VecDeque::from(vec![0.0; HISTORY_SIZE]));
+        history.pop_front();
+        history.push_back(value);
+    }
+
+    fn read_diskstats() -> HashMap<String, DiskStats> {
+        fs::read_to_string("/proc/diskstats")
+            .map(|contents| parse_diskstats(&contents))
+            .unwrap_or_default()
+    }
+
+    /// Nest partitions under their parent disk; orphans are kept as top-level entries
+    fn group_partitions(infos: Vec<DiskIOInfo>) -> Vec<DiskIOInfo> {
+        let names: Vec<String> = infos.iter().map(|d| d.name.clone()).collect();
+        let (partitions, mut disks): (Vec<DiskIOInfo>, Vec<DiskIOInfo>) =
+            infos.into_iter().partition(|d| {
+                parent_device(&d.name)
+                    .map(|parent| names.contains(&parent))
+                    .unwrap_or(false)
+            });
+
+        for partition in partitions {
+            if let Some(parent) = parent_device(&partition.name) {
+                if let Some(disk) = disks.iter_mut().find(|d| d.name == parent) {
+                    disk.partitions.push(partition);
+                }
+            }
+        }
+
+        disks
     }
 }
 
-#[derive(Debug, Clone)]
-#[allow(dead_code)]
+impl Default for DiskIOMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct DiskIOInfo {
     pub name: String,
     pub read_rate: f64,  // bytes/sec
     pub write_rate: f64, // bytes/sec
     pub read_total: u64,
     pub write_total: u64,
+    pub read_iops: f64,
+    pub write_iops: f64,
+    pub await_ms: f64,     // Average time per completed request
+    pub queue_depth: f64,  // Average number of in-flight requests
+    pub util_percent: f64, // Share of wall time the device was busy
+    pub partitions: Vec<DiskIOInfo>,
+}
+
+impl DiskIOInfo {
+    fn from_delta(name: &str, prev: &DiskStats, cur: &DiskStats, elapsed_secs: f64) -> Self {
+        let elapsed_ms = elapsed_secs * 1000.0;
+        let reads = cur.reads_completed.saturating_sub(prev.reads_completed);
+        let writes = cur.writes_completed.saturating_sub(prev.writes_completed);
+        let io_ms = cur.ms_reading.saturating_sub(prev.ms_reading)
+            + cur.ms_writing.saturating_sub(prev.ms_writing);
+        let busy_ms = cur.ms_doing_io.saturating_sub(prev.ms_doing_io);
+        let weighted_ms = cur
+            .weighted_ms_doing_io
+            .saturating_sub(prev.weighted_ms_doing_io);
+
+        let await_ms = if reads + writes > 0 {
+            io_ms as f64 / (reads + writes) as f64
+        } else {
+            0.0
+        };
+
+        Self {
+            name: name.to_string(),
+            read_rate: (cur.sectors_read.saturating_sub(prev.sectors_read) * SECTOR_SIZE) as f64
+                / elapsed_secs,
+            write_rate: (cur.sectors_written.saturating_sub(prev.sectors_written) * SECTOR_SIZE)
+                as f64
+                / elapsed_secs,
+            read_total: cur.sectors_read * SECTOR_SIZE,
+            write_total: cur.sectors_written * SECTOR_SIZE,
+            read_iops: reads as f64 / elapsed_secs,
+            write_iops: writes as f64 / elapsed_secs,
+            await_ms,
+            queue_depth: weighted_ms as f64 / elapsed_ms,
+            util_percent: (busy_ms as f64 / elapsed_ms * 100.0).min(100.0),
+            partitions: Vec::new(),
+        }
+    }
+}
+
+/// Parse the contents of /proc/diskstats, skipping idle virtual devices
+pub fn parse_diskstats(contents: &str) -> HashMap<String, DiskStats> {
+    let mut stats = HashMap::new();
+
+    for line in contents.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 14 {
+            continue;
+        }
+
+        let name = fields[2];
+        let num = |i: usize| fields[i].parse::<u64>().unwrap_or(0);
+
+        let entry = DiskStats {
+            reads_completed: num(3),
+            sectors_read: num(5),
+            ms_reading: num(6),
+            writes_completed: num(7),
+            sectors_written: num(9),
+            ms_writing: num(10),
+            ms_doing_io: num(12),
+            weighted_ms_doing_io: num(13),
+        };
+
+        // loop and ram devices that were never used only add noise
+        let idle = entry.reads_completed == 0 && entry.writes_completed == 0;
+        if idle && (name.starts_with("loop") || name.starts_with("ram")) {
+            continue;
+        }
+
+        stats.insert(name.to_string(), entry);
+    }
+
+    stats
+}
+
+/// Resolve the whole-disk device a partition belongs to
+pub fn parent_device(name: &str) -> Option<String> {
+    // sysfs knows for sure: /sys/class/block/<part> links into /sys/devices/.../<disk>/<part>
+    let sys_path = Path::new("/sys/class/block").join(name);
+    if sys_path.exists() {
+        if !sys_path.join("partition").exists() {
+            return None;
+        }
+        return fs::canonicalize(&sys_path).ok().and_then(|p| {
+            p.parent()
+                .and_then(|parent| parent.file_name())
+                .map(|n| n.to_string_lossy().to_string())
+        });
+    }
+
+    parent_device_from_name(name)
+}
+
+/// Name-based fallback when sysfs is unavailable (sda1, nvme0n1p2, mmcblk0p1)
+fn parent_device_from_name(name: &str) -> Option<String> {
+    let base = name.trim_end_matches(|c: char| c.is_ascii_digit());
+    if base.len() == name.len() || base.is_empty() {
+        return None;
+    }
+
+    if let Some(stripped) = base.strip_suffix('p') {
+        if stripped.ends_with(|c: char| c.is_ascii_digit()) {
+            return Some(stripped.to_string());
+        }
+    }
+
+    let whole_disk_prefixes = ["sd", "hd", "vd", "xvd"];
+    if whole_disk_prefixes.iter().any(|p| base.starts_with(p)) {
+        return Some(base.to_string());
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+   7       0 loop0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0
+ 259       0 nvme0n1 1000 0 8000 500 2000 0 16000 1500 2 3000 4000 0 0 0 0
+ 259       1 nvme0n1p1 900 0 7200 450 1800 0 14400 1400 0 2800 3700 0 0 0 0
+";
+
+    #[test]
+    fn test_parse_diskstats() {
+        let stats = parse_diskstats(SAMPLE);
+        assert!(!stats.contains_key("loop0"));
+        let disk = stats.get("nvme0n1").unwrap();
+        assert_eq!(disk.reads_completed, 1000);
+        assert_eq!(disk.sectors_read, 8000);
+        assert_eq!(disk.sectors_written, 16000);
+        assert_eq!(disk.ms_doing_io, 3000);
+        assert_eq!(disk.weighted_ms_doing_io, 4000);
+    }
+
+    #[test]
+    fn test_rates_from_delta() {
+        let prev = DiskStats::default();
+        let cur = DiskStats {
+            reads_completed: 100,
+            sectors_read: 2048,
+            ms_reading: 150,
+            writes_completed: 50,
+            sectors_written: 1024,
+            ms_writing: 150,
+            ms_doing_io: 500,
+            weighted_ms_doing_io: 1000,
+        };
+        let info = DiskIOInfo::from_delta("sda", &prev, &cur, 2.0);
+        assert_eq!(info.read_rate, 2048.0 * 512.0 / 2.0);
+        assert_eq!(info.write_rate, 1024.0 * 512.0 / 2.0);
+        assert_eq!(info.read_iops, 50.0);
+        assert_eq!(info.write_iops, 25.0);
+        assert_eq!(info.await_ms, 2.0);
+        assert_eq!(info.queue_depth, 0.5);
+        assert_eq!(info.util_percent, 25.0);
+    }
+
+    #[test]
+    fn test_parent_device_from_name() {
+        assert_eq!(parent_device_from_name("sda1"), Some("sda".to_string()));
+        assert_eq!(
+            parent_device_from_name("nvme0n1p2"),
+            Some("nvme0n1".to_string())
+        );
+        assert_eq!(
+            parent_device_from_name("mmcblk0p1"),
+            Some("mmcblk0".to_string())
+        );
+        assert_eq!(parent_device_from_name("sda"), None);
+        assert_eq!(parent_device_from_name("nvme0n1"), None);
+        assert_eq!(parent_device_from_name("dm-0"), None);
+    }
 }
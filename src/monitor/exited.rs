@@ -0,0 +1,587 @@
+/// Recently exited processes, including ones too short-lived to show up in a refresh
+/// Without privileges exits are noticed by diffing refreshes; as root the kernel's process
+/// connector (netlink proc events) reports every fork, exec and exit as it happens
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::mpsc::Receiver;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Upper bound on retained entries so a fork storm cannot grow the list without limit
+const MAX_EXITED: usize = 1000;
+
+#[derive(Debug, Clone)]
+pub struct ExitedProcess {
+    pub pid: u32,
+    /// Kept so the panel honours the kernel-thread option like the process list
+    pub kernel_thread: bool,
+    pub name: String,
+    pub command: String,
+    /// User plus system CPU time in seconds; when polling, as of the last refresh that saw it
+    pub cpu_time: Option<f64>,
+    /// Seconds between start and exit
+    pub lifetime: Option<f64>,
+    /// When the exit was observed: the event itself, or the refresh that found it gone
+    pub exited_at: SystemTime,
+    /// Only reported by proc events
+    pub status: Option<ExitStatus>,
+    /// Started and exited between two refreshes of the process list
+    pub short_lived: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExitStatus {
+    Code(i32),
+    Signal(i32),
+}
+
+impl ExitStatus {
+    /// Decode a wait(2) status word as the kernel reports it
+    pub fn from_wait_status(status: u32) -> Self {
+        let signal = status & 0x7f;
+        if signal != 0 {
+            ExitStatus::Signal(signal as i32)
+        } else {
+            ExitStatus::Code(((status >> 8) & 0xff) as i32)
+        }
+    }
+}
+
+impl fmt::Display for ExitStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExitStatus::Code(code) => write!(f, "{}", code),
+            ExitStatus::Signal(signal) => write!(f, "sig {}", signal),
+        }
+    }
+}
+
+pub struct ExitTracker {
+    retention: Duration,
+    exited: VecDeque<(Instant, ExitedProcess)>,
+    /// Exits reported by the proc events thread, when it could be started
+    events: Option<Receiver<ExitedProcess>>,
+}
+
+impl ExitTracker {
+    pub fn new(retention: Duration, use_proc_events: bool) -> Self {
+        let events = if use_proc_events {
+            proc_events::spawn()
+        } else {
+            None
+        };
+        Self {
+            retention,
+            exited: VecDeque::new(),
+            events,
+        }
+    }
+
+    pub fn uses_proc_events(&self) -> bool {
+        self.events.is_some()
+    }
+
+    pub fn retention(&self) -> Duration {
+        self.retention
+    }
+
+    /// Record an exit noticed by diffing refreshes; proc events already cover those
+    pub fn record_polled(&mut self, process: ExitedProcess) {
+        if self.events.is_none() {
+            self.push(process);
+        }
+    }
+
+    /// Drain reported exits and forget the ones past the retention period; `seen` tells
+    /// whether a refresh ever listed a PID, `listed` whether an exit belongs in the list
+    pub fn update(&mut self, seen: impl Fn(u32) -> bool, listed: impl Fn(&ExitedProcess) -> bool) {
+        let reported: Vec<ExitedProcess> = self
+            .events
+            .as_ref()
+            .map(|events| events.try_iter().filter(|p| listed(p)).collect())
+            .unwrap_or_default();
+        for mut process in reported {
+            process.short_lived = !seen(process.pid);
+            self.push(process);
+        }
+
+        while self
+            .exited
+            .front()
+            .is_some_and(|(at, _)| at.elapsed() > self.retention)
+        {
+            self.exited.pop_front();
+        }
+    }
+
+    fn push(&mut self, process: ExitedProcess) {
+        if self.exited.len() == MAX_EXITED {
+            self.exited.pop_front();
+        }
+        self.exited.push_back((Instant::now(), process));
+    }
+
+    /// Newest first
+    pub fn recent(&self) -> impl Iterator<Item = &ExitedProcess> {
+        self.exited.iter().rev().map(|(_, process)| process)
+    }
+}
+
+/// One event of the process connector that matters for exit tracking
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProcEvent {
+    /// A new process (not thread) was forked
+    Fork {
+        pid: u32,
+        parent: u32,
+        timestamp_ns: u64,
+    },
+    Exec {
+        pid: u32,
+    },
+    /// A task exited; `pid` is the thread group, `tid` the task itself
+    Exit {
+        pid: u32,
+        tid: u32,
+        status: u32,
+        timestamp_ns: u64,
+    },
+}
+
+const NLMSG_HDRLEN: usize = 16;
+const NLMSG_DONE: u16 = 3;
+const CN_MSG_LEN: usize = 20;
+const CN_IDX_PROC: u32 = 1;
+const CN_VAL_PROC: u32 = 1;
+const PROC_EVENT_FORK: u32 = 0x0000_0001;
+const PROC_EVENT_EXEC: u32 = 0x0000_0002;
+const PROC_EVENT_EXIT: u32 = 0x8000_0000;
+
+/// Parse a datagram from the process connector: netlink messages, each carrying a
+/// `cn_msg` header followed by a `proc_event`
+pub fn parse_proc_events(buf: &[u8]) -> Vec<ProcEvent> {
+    let u16_at = |b: &[u8], i: usize| -> Option<u16> {
+        Some(u16::from_ne_bytes(b.get(i..i + 2)?.try_into().ok()?))
+    };
+    let u32_at = |b: &[u8], i: usize| -> Option<u32> {
+        Some(u32::from_ne_bytes(b.get(i..i + 4)?.try_into().ok()?))
+    };
+    let u64_at = |b: &[u8], i: usize| -> Option<u64> {
+        Some(u64::from_ne_bytes(b.get(i..i + 8)?.try_into().ok()?))
+    };
+
+    let mut events = Vec::new();
+    let mut offset = 0;
+    while let Some(len) = u32_at(buf, offset).map(|len| len as usize) {
+        if len < NLMSG_HDRLEN || offset + len > buf.len() {
+            break;
+        }
+        let message = &buf[offset..offset + len];
+        offset += (len + 3) & !3;
+
+        if u16_at(message, 4) != Some(NLMSG_DONE) {
+            continue;
+        }
+        let connector = &message[NLMSG_HDRLEN..];
+        if u32_at(connector, 0) != Some(CN_IDX_PROC) || u32_at(connector, 4) != Some(CN_VAL_PROC) {
+            continue;
+        }
+        // struct proc_event: what, cpu, timestamp_ns, then the per-event data
+        let event = connector.get(CN_MSG_LEN..).unwrap_or_default();
+        let (Some(what), Some(timestamp_ns)) = (u32_at(event, 0), u64_at(event, 8)) else {
+            continue;
+        };
+        let field = |i: usize| u32_at(event, 16 + 4 * i);
+        let parsed = match what {
+            // parent_pid, parent_tgid, child_pid, child_tgid
+            PROC_EVENT_FORK => match (field(1), field(2), field(3)) {
+                (Some(parent), Some(tid), Some(pid)) if tid == pid => Some(ProcEvent::Fork {
+                    pid,
+                    parent,
+                    timestamp_ns,
+                }),
+                _ => None,
+            },
+            PROC_EVENT_EXEC => field(1).map(|pid| ProcEvent::Exec { pid }),
+            // process_pid, process_tgid, exit_code
+            PROC_EVENT_EXIT => match (field(0), field(1), field(2)) {
+                (Some(tid), Some(pid), Some(status)) => Some(ProcEvent::Exit {
+                    pid,
+                    tid,
+                    status,
+                    timestamp_ns,
+                }),
+                _ => None,
+            },
+            _ => None,
+        };
+        events.extend(parsed);
+    }
+    events
+}
+
+#[cfg(target_os = "linux")]
+mod proc_events {
+    use std::collections::HashMap;
+    use std::fs;
+    use std::io;
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+    use std::path::Path;
+    use std::sync::mpsc::{self, Receiver, Sender};
+    use std::thread;
+    use std::time::SystemTime;
+
+    use super::{
+        parse_proc_events, ExitStatus, ExitedProcess, ProcEvent, CN_IDX_PROC, CN_MSG_LEN,
+        CN_VAL_PROC, NLMSG_DONE, NLMSG_HDRLEN,
+    };
+    use crate::monitor::{process, process_detail};
+
+    const PROC_CN_MCAST_LISTEN: u32 = 1;
+    /// Forked processes whose exit has not been seen; pruned against /proc past this
+    const MAX_TRACKED: usize = 8192;
+
+    /// What is known about a live process before it exits; after exit its cmdline is gone
+    struct Tracked {
+        forked_ns: Option<u64>,
+        kernel_thread: bool,
+        name: String,
+        command: String,
+    }
+
+    impl Tracked {
+        /// Classified while the process is alive and its command line still readable
+        fn new(pid: u32, ppid: Option<u32>, forked_ns: Option<u64>) -> Self {
+            let (name, command) = read_names(pid);
+            Tracked {
+                forked_ns,
+                kernel_thread: process::is_kernel_thread(
+                    None,
+                    pid,
+                    ppid,
+                    command.is_empty(),
+                    false,
+                ),
+                name,
+                command,
+            }
+        }
+    }
+
+    /// Start listening for proc events; needs CAP_NET_ADMIN, so in practice root
+    pub fn spawn() -> Option<Receiver<ExitedProcess>> {
+        // Unprivileged subscriptions are accepted but never receive anything
+        // SAFETY: geteuid has no preconditions
+        if unsafe { libc::geteuid() } != 0 {
+            return None;
+        }
+        let socket = subscribe().ok()?;
+        let (sender, receiver) = mpsc::channel();
+        thread::Builder::new()
+            .name("rtop-proc-events".to_string())
+            .spawn(move || event_loop(socket, sender))
+            .ok()
+            .map(|_| receiver)
+    }
+
+    fn subscribe() -> io::Result<OwnedFd> {
+        // SAFETY: plain socket(2) call; the descriptor is owned right away
+        let fd = unsafe {
+            libc::socket(
+                libc::AF_NETLINK,
+                libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+                libc::NETLINK_CONNECTOR,
+            )
+        };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: fd is a fresh, valid descriptor not owned elsewhere
+        let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        // SAFETY: sockaddr_nl is plain data, all-zero is a valid value
+        let mut address: libc::sockaddr_nl = unsafe { std::mem::zeroed() };
+        address.nl_family = libc::AF_NETLINK as u16;
+        address.nl_groups = CN_IDX_PROC;
+        // SAFETY: address points to a properly sized sockaddr_nl
+        let bound = unsafe {
+            libc::bind(
+                socket.as_raw_fd(),
+                (&address as *const libc::sockaddr_nl).cast(),
+                std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+            )
+        };
+        if bound < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let len = NLMSG_HDRLEN + CN_MSG_LEN + 4;
+        let mut message = Vec::with_capacity(len);
+        message.extend((len as u32).to_ne_bytes());
+        message.extend(NLMSG_DONE.to_ne_bytes());
+        message.extend(0u16.to_ne_bytes()); // flags
+        message.extend(0u32.to_ne_bytes()); // sequence
+        message.extend(std::process::id().to_ne_bytes());
+        message.extend(CN_IDX_PROC.to_ne_bytes());
+        message.extend(CN_VAL_PROC.to_ne_bytes());
+        message.extend(0u32.to_ne_bytes()); // sequence
+        message.extend(0u32.to_ne_bytes()); // ack
+        message.extend(4u16.to_ne_bytes()); // payload length
+        message.extend(0u16.to_ne_bytes()); // flags
+        message.extend(PROC_CN_MCAST_LISTEN.to_ne_bytes());
+        // SAFETY: message is a valid buffer of the given length
+        let sent = unsafe { libc::send(socket.as_raw_fd(), message.as_ptr().cast(), len, 0) };
+        if sent != len as isize {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(socket)
+    }
+
+    fn event_loop(socket: OwnedFd, sender: Sender<ExitedProcess>) {
+        let clock_ticks = process_detail::clock_ticks_per_second();
+        // Processes already running have no fork event; note what they run up front
+        let mut tracked: HashMap<u32, Tracked> = fs::read_dir("/proc")
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|entry| entry.file_name().to_str()?.parse::<u32>().ok())
+                    .map(|pid| {
+                        let stat =
+                            process_detail::read_stat(Path::new(&format!("/proc/{}/stat", pid)));
+                        (pid, Tracked::new(pid, stat.map(|s| s.ppid), None))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let mut buf = vec![0u8; 16 * 1024];
+
+        loop {
+            // SAFETY: buf is a valid writable buffer of buf.len() bytes
+            let received =
+                unsafe { libc::recv(socket.as_raw_fd(), buf.as_mut_ptr().cast(), buf.len(), 0) };
+            if received < 0 {
+                match io::Error::last_os_error().raw_os_error() {
+                    // The kernel dropped events under load; keep listening
+                    Some(libc::ENOBUFS) | Some(libc::EINTR) => continue,
+                    _ => return,
+                }
+            }
+
+            for event in parse_proc_events(&buf[..received as usize]) {
+                match event {
+                    ProcEvent::Fork {
+                        pid,
+                        parent,
+                        timestamp_ns,
+                    } => {
+                        tracked.insert(pid, Tracked::new(pid, Some(parent), Some(timestamp_ns)));
+                    }
+                    ProcEvent::Exec { pid } => {
+                        let (name, command) = read_names(pid);
+                        match tracked.get_mut(&pid) {
+                            Some(entry) => {
+                                entry.name = name;
+                                entry.command = command;
+                            }
+                            None => {
+                                let ppid = process_detail::read_stat(Path::new(&format!(
+                                    "/proc/{}/stat",
+                                    pid
+                                )))
+                                .map(|s| s.ppid);
+                                tracked.insert(pid, Tracked::new(pid, ppid, None));
+                            }
+                        }
+                    }
+                    ProcEvent::Exit {
+                        pid,
+                        tid,
+                        status,
+                        timestamp_ns,
+                    } if pid == tid => {
+                        let exited = exited_process(
+                            pid,
+                            tracked.remove(&pid),
+                            status,
+                            timestamp_ns,
+                            clock_ticks,
+                        );
+                        if sender.send(exited).is_err() {
+                            return;
+                        }
+                    }
+                    ProcEvent::Exit { .. } => {}
+                }
+            }
+
+            if tracked.len() > MAX_TRACKED {
+                tracked.retain(|pid, _| Path::new(&format!("/proc/{}", pid)).exists());
+            }
+        }
+    }
+
+    fn read_names(pid: u32) -> (String, String) {
+        let name = fs::read_to_string(format!("/proc/{}/comm", pid))
+            .map(|comm| comm.trim_end().to_string())
+            .unwrap_or_default();
+        let command = fs::read(format!("/proc/{}/cmdline", pid))
+            .map(|bytes| {
+                bytes
+                    .split(|&b| b == 0)
+                    .filter(|arg| !arg.is_empty())
+                    .map(String::from_utf8_lossy)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .unwrap_or_default();
+        (name, command)
+    }
+
+    /// Build the record while the exiting task is still readable (usually as a zombie);
+    /// if its parent already reaped it, only what was tracked at fork/exec remains
+    fn exited_process(
+        pid: u32,
+        tracked: Option<Tracked>,
+        status: u32,
+        exit_ns: u64,
+        clock_ticks: f64,
+    ) -> ExitedProcess {
+        let stat = process_detail::read_stat(Path::new(&format!("/proc/{}/stat", pid)));
+        let lifetime = match tracked.as_ref().and_then(|t| t.forked_ns) {
+            Some(forked_ns) => Some(exit_ns.saturating_sub(forked_ns) as f64 / 1e9),
+            None => stat.as_ref().and_then(|stat| {
+                let uptime: f64 = fs::read_to_string("/proc/uptime")
+                    .ok()?
+                    .split_whitespace()
+                    .next()?
+                    .parse()
+                    .ok()?;
+                Some((uptime - stat.starttime as f64 / clock_ticks).max(0.0))
+            }),
+        };
+        // Untracked exits are zombies already, whose command line is gone
+        let kernel_thread = tracked.as_ref().map_or_else(
+            || {
+                let ppid = stat.as_ref().map(|stat| stat.ppid);
+                process::is_kernel_thread(None, pid, ppid, true, true)
+            },
+            |t| t.kernel_thread,
+        );
+        let (mut name, command) = tracked.map(|t| (t.name, t.command)).unwrap_or_default();
+        if let Some(stat) = &stat {
+            name = stat.comm.clone();
+        }
+        let command = if command.is_empty() && !name.is_empty() {
+            format!("[{}]", name)
+        } else {
+            command
+        };
+
+        ExitedProcess {
+            pid,
+            kernel_thread,
+            name,
+            command,
+            cpu_time: stat.map(|stat| (stat.utime + stat.stime) as f64 / clock_ticks),
+            lifetime,
+            exited_at: SystemTime::now(),
+            status: Some(ExitStatus::from_wait_status(status)),
+            short_lived: false,
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod proc_events {
+    use super::ExitedProcess;
+    use std::sync::mpsc::Receiver;
+
+    pub fn spawn() -> Option<Receiver<ExitedProcess>> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(what: u32, timestamp_ns: u64, data: &[u32]) -> Vec<u8> {
+        let mut event = Vec::new();
+        event.extend(what.to_ne_bytes());
+        event.extend(0u32.to_ne_bytes()); // cpu
+        event.extend(timestamp_ns.to_ne_bytes());
+        for value in data {
+            event.extend(value.to_ne_bytes());
+        }
+
+        let len = NLMSG_HDRLEN + CN_MSG_LEN + event.len();
+        let mut buf = Vec::new();
+        buf.extend((len as u32).to_ne_bytes());
+        buf.extend(NLMSG_DONE.to_ne_bytes());
+        buf.extend([0u8; 10]); // flags, sequence, port
+        buf.extend(CN_IDX_PROC.to_ne_bytes());
+        buf.extend(CN_VAL_PROC.to_ne_bytes());
+        buf.extend([0u8; 8]); // sequence, ack
+        buf.extend((event.len() as u16).to_ne_bytes());
+        buf.extend([0u8; 2]);
+        buf.extend(event);
+        buf
+    }
+
+    #[test]
+    fn test_parse_proc_events() {
+        let mut buf = message(PROC_EVENT_FORK, 5, &[100, 100, 200, 200]);
+        // A new thread is not a new process
+        buf.extend(message(PROC_EVENT_FORK, 6, &[200, 200, 201, 200]));
+        buf.extend(message(PROC_EVENT_EXEC, 7, &[200, 200]));
+        buf.extend(message(PROC_EVENT_EXIT, 9, &[200, 200, 9, 9, 100, 100]));
+        // Unrelated event types are skipped
+        buf.extend(message(0x40, 10, &[200, 200]));
+
+        assert_eq!(
+            parse_proc_events(&buf),
+            vec![
+                ProcEvent::Fork {
+                    pid: 200,
+                    parent: 100,
+                    timestamp_ns: 5
+                },
+                ProcEvent::Exec { pid: 200 },
+                ProcEvent::Exit {
+                    pid: 200,
+                    tid: 200,
+                    status: 9,
+                    timestamp_ns: 9
+                },
+            ]
+        );
+        assert!(parse_proc_events(&buf[..10]).is_empty());
+    }
+
+    #[test]
+    fn test_exit_status() {
+        assert_eq!(ExitStatus::from_wait_status(0), ExitStatus::Code(0));
+        assert_eq!(ExitStatus::from_wait_status(2 << 8), ExitStatus::Code(2));
+        assert_eq!(ExitStatus::from_wait_status(9), ExitStatus::Signal(9));
+        assert_eq!(ExitStatus::Signal(15).to_string(), "sig 15");
+    }
+
+    #[test]
+    fn test_tracker_expires_entries() {
+        let mut tracker = ExitTracker::new(Duration::ZERO, false);
+        tracker.record_polled(ExitedProcess {
+            pid: 1,
+            kernel_thread: false,
+            name: "make".to_string(),
+            command: "make -j8".to_string(),
+            cpu_time: Some(0.5),
+            lifetime: Some(1.0),
+            exited_at: SystemTime::now(),
+            status: None,
+            short_lived: false,
+        });
+        assert_eq!(tracker.recent().count(), 1);
+        std::thread::sleep(Duration::from_millis(2));
+        tracker.update(|_| true, |_| true);
+        assert_eq!(tracker.recent().count(), 0);
+    }
+}
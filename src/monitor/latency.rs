@@ -0,0 +1,255 @@
+/// Background latency probing (ICMP via `ping` or TCP connect)
+/// Probes run on a dedicated thread so an unreachable target never stalls the UI
+use std::collections::VecDeque;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::config::{LatencyConfig, ProbeMode};
+
+const HISTORY_SIZE: usize = 61;
+const DEFAULT_TCP_PORT: u16 = 443;
+
+/// Outcome of one probe round: the first target that answered, or a loss
+#[derive(Debug, Clone)]
+pub struct ProbeSample {
+    pub target: String,
+    pub latency_ms: Option<f32>,
+}
+
+pub struct LatencyMonitor {
+    mode: ProbeMode,
+    receiver: Option<Receiver<ProbeSample>>,
+    samples: VecDeque<Option<f32>>,
+    last_target: Option<String>,
+}
+
+impl LatencyMonitor {
+    pub fn new(config: &LatencyConfig) -> Self {
+        let receiver = if config.mode == ProbeMode::Disabled || config.targets.is_empty() {
+            None
+        } else {
+            let (sender, receiver) = mpsc::channel();
+            let config = config.clone();
+            thread::Builder::new()
+                .name("rtop-latency".to_string())
+                .spawn(move || probe_loop(config, sender))
+                .ok()
+                .map(|_| receiver)
+        };
+
+        Self {
+            mode: config.mode,
+            receiver,
+            samples: VecDeque::with_capacity(HISTORY_SIZE),
+            last_target: None,
+        }
+    }
+
+    /// Drain results produced by the probe thread; never blocks
+    pub fn update(&mut self) {
+        let Some(receiver) = &self.receiver else {
+            return;
+        };
+
+        while let Ok(sample) = receiver.try_recv() {
+            if self.samples.len() == HISTORY_SIZE {
+                self.samples.pop_front();
+            }
+            self.samples.push_back(sample.latency_ms);
+            self.last_target = Some(sample.target);
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    pub fn mode(&self) -> ProbeMode {
+        self.mode
+    }
+
+    /// Latency of the most recent probe round, `None` if it was lost or none ran yet
+    pub fn current(&self) -> Option<f32> {
+        self.samples.back().copied().flatten()
+    }
+
+    pub fn last_target(&self) -> Option<&str> {
+        self.last_target.as_deref()
+    }
+
+    /// Latency history in ms; lost probes are `None`
+    pub fn history(&self) -> Vec<Option<f32>> {
+        self.samples.iter().copied().collect()
+    }
+
+    /// Share of lost probes across the retained history (0-100)
+    pub fn loss_percent(&self) -> f32 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        let lost = self.samples.iter().filter(|s| s.is_none()).count();
+        lost as f32 / self.samples.len() as f32 * 100.0
+    }
+
+    pub fn average(&self) -> Option<f32> {
+        let answered: Vec<f32> = self.samples.iter().filter_map(|s| *s).collect();
+        if answered.is_empty() {
+            None
+        } else {
+            Some(answered.iter().sum::<f32>() / answered.len() as f32)
+        }
+    }
+}
+
+fn probe_loop(config: LatencyConfig, sender: Sender<ProbeSample>) {
+    let interval = Duration::from_millis(config.interval);
+    let timeout = Duration::from_millis(config.timeout.max(1));
+
+    loop {
+        let started = Instant::now();
+
+        let sample = config
+            .targets
+            .iter()
+            .find_map(|target| {
+                probe(config.mode, target, timeout).map(|latency| ProbeSample {
+                    target: target.clone(),
+                    latency_ms: Some(latency),
+                })
+            })
+            .unwrap_or_else(|| ProbeSample {
+                target: config.targets[0].clone(),
+                latency_ms: None,
+            });
+
+        // The monitor was dropped; stop probing
+        if sender.send(sample).is_err() {
+            return;
+        }
+
+        thread::sleep(interval.saturating_sub(started.elapsed()));
+    }
+}
+
+fn probe(mode: ProbeMode, target: &str, timeout: Duration) -> Option<f32> {
+    match mode {
+        ProbeMode::Icmp => icmp_probe(target, timeout),
+        ProbeMode::Tcp => tcp_probe(target, timeout),
+        ProbeMode::Disabled => None,
+    }
+}
+
+/// Measure TCP handshake time to `host:port` (port defaults to 443)
+pub fn tcp_probe(target: &str, timeout: Duration) -> Option<f32> {
+    let addr = resolve(target)?;
+    let start = Instant::now();
+    TcpStream::connect_timeout(&addr, timeout).ok()?;
+    Some(start.elapsed().as_secs_f32() * 1000.0)
+}
+
+fn resolve(target: &str) -> Option<SocketAddr> {
+    target
+        .to_socket_addrs()
+        .or_else(|_| (target, DEFAULT_TCP_PORT).to_socket_addrs())
+        .ok()?
+        .next()
+}
+
+fn icmp_probe(host: &str, timeout: Duration) -> Option<f32> {
+    // ping only takes whole seconds for its deadline
+    let timeout_secs = timeout.as_secs().max(1).to_string();
+    let output = Command::new("ping")
+        .args(["-c", "1", "-W", &timeout_secs, host])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    parse_ping_output(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Extract the round-trip time from ping output (Linux/macOS), e.g. "time=14.2 ms"
+fn parse_ping_output(stdout: &str) -> Option<f32> {
+    stdout.lines().find_map(|line| {
+        let time_str = &line[line.find("time=")? + 5..];
+        let end = time_str.find([' ', 'm'])?;
+        time_str[..end].parse::<f32>().ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    fn tcp_config(target: String) -> LatencyConfig {
+        LatencyConfig {
+            mode: ProbeMode::Tcp,
+            targets: vec![target],
+            interval: 50,
+            timeout: 500,
+        }
+    }
+
+    #[test]
+    fn test_parse_ping_output() {
+        let output = "64 bytes from 1.1.1.1: icmp_seq=1 ttl=57 time=14.2 ms";
+        assert_eq!(parse_ping_output(output), Some(14.2));
+        assert_eq!(parse_ping_output("Request timeout"), None);
+    }
+
+    #[test]
+    fn test_tcp_probe_local_listener() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        assert!(tcp_probe(&addr, Duration::from_millis(500)).is_some());
+    }
+
+    #[test]
+    fn test_tcp_probe_closed_port() {
+        // Bind then drop to get a port nothing listens on
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .to_string();
+        assert!(tcp_probe(&addr, Duration::from_millis(500)).is_none());
+    }
+
+    #[test]
+    fn test_background_probe_records_history() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let mut monitor = LatencyMonitor::new(&tcp_config(addr.clone()));
+        assert!(monitor.is_enabled());
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while monitor.history().len() < 2 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(20));
+            monitor.update();
+        }
+
+        assert!(monitor.history().len() >= 2);
+        assert!(monitor.current().is_some());
+        assert_eq!(monitor.loss_percent(), 0.0);
+        assert_eq!(monitor.last_target(), Some(addr.as_str()));
+    }
+
+    #[test]
+    fn test_disabled_probe() {
+        let config = LatencyConfig {
+            mode: ProbeMode::Disabled,
+            ..tcp_config("127.0.0.1:1".to_string())
+        };
+        let mut monitor = LatencyMonitor::new(&config);
+        monitor.update();
+        assert!(!monitor.is_enabled());
+        assert!(monitor.current().is_none());
+        assert!(monitor.history().is_empty());
+    }
+}
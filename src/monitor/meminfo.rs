@@ -0,0 +1,176 @@
+/// Breakdown of system memory from /proc/meminfo
+use std::fs;
+
+/// /proc/meminfo fields rtop uses, in bytes (the file reports kB)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemInfo {
+    pub total: u64,
+    pub free: u64,
+    pub available: u64,
+    pub buffers: u64,
+    /// Page cache, including shmem
+    pub cached: u64,
+    pub shmem: u64,
+    pub slab_reclaimable: u64,
+    pub slab_unreclaimable: u64,
+    pub dirty: u64,
+    pub writeback: u64,
+    pub committed: u64,
+    pub commit_limit: u64,
+    pub hugepages_total: u64,
+    pub hugepages_free: u64,
+    pub hugepage_size: u64,
+    /// Zswap pool size and the uncompressed size it holds (Linux 6.5+)
+    pub zswap: Option<u64>,
+    pub zswapped: Option<u64>,
+}
+
+impl MemInfo {
+    pub fn read() -> Option<Self> {
+        fs::read_to_string("/proc/meminfo")
+            .ok()
+            .and_then(|contents| parse_meminfo(&contents))
+    }
+
+    /// Memory held by processes and the kernel that cannot simply be dropped
+    pub fn used(&self) -> u64 {
+        self.total
+            .saturating_sub(self.free)
+            .saturating_sub(self.buffers)
+            .saturating_sub(self.cached)
+            .saturating_sub(self.slab_reclaimable)
+            .saturating_sub(self.slab_unreclaimable)
+    }
+
+    /// Size of one stacked segment; together they add up to `total`
+    pub fn component(&self, component: MemComponent) -> u64 {
+        match component {
+            MemComponent::Used => self.used(),
+            MemComponent::Shared => self.shmem,
+            MemComponent::Buffers => self.buffers,
+            MemComponent::Cached => self.cached.saturating_sub(self.shmem),
+            MemComponent::Slab => self.slab_reclaimable + self.slab_unreclaimable,
+            MemComponent::Free => self.free,
+        }
+    }
+}
+
+/// Segments of the memory bar, in drawing order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemComponent {
+    Used,
+    Shared,
+    Buffers,
+    Cached,
+    Slab,
+    Free,
+}
+
+impl MemComponent {
+    pub const ALL: [MemComponent; 6] = [
+        MemComponent::Used,
+        MemComponent::Shared,
+        MemComponent::Buffers,
+        MemComponent::Cached,
+        MemComponent::Slab,
+        MemComponent::Free,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MemComponent::Used => "Used",
+            MemComponent::Shared => "Shared",
+            MemComponent::Buffers => "Buffers",
+            MemComponent::Cached => "Cached",
+            MemComponent::Slab => "Slab",
+            MemComponent::Free => "Free",
+        }
+    }
+}
+
+pub fn parse_meminfo(contents: &str) -> Option<MemInfo> {
+    let mut info = MemInfo::default();
+    let mut hugepages_total = 0;
+    let mut hugepages_free = 0;
+    for line in contents.lines() {
+        let Some((key, rest)) = line.split_once(':') else {
+            continue;
+        };
+        let mut fields = rest.split_whitespace();
+        let Some(value) = fields.next().and_then(|v| v.parse::<u64>().ok()) else {
+            continue;
+        };
+        // HugePages_* are page counts, everything else is in kB
+        let bytes = match fields.next() {
+            Some("kB") => value * 1024,
+            _ => value,
+        };
+        match key {
+            "MemTotal" => info.total = bytes,
+            "MemFree" => info.free = bytes,
+            "MemAvailable" => info.available = bytes,
+            "Buffers" => info.buffers = bytes,
+            "Cached" => info.cached = bytes,
+            "Shmem" => info.shmem = bytes,
+            "SReclaimable" => info.slab_reclaimable = bytes,
+            "SUnreclaim" => info.slab_unreclaimable = bytes,
+            "Dirty" => info.dirty = bytes,
+            "Writeback" => info.writeback = bytes,
+            "Committed_AS" => info.committed = bytes,
+            "CommitLimit" => info.commit_limit = bytes,
+            "HugePages_Total" => hugepages_total = value,
+            "HugePages_Free" => hugepages_free = value,
+            "Hugepagesize" => info.hugepage_size = bytes,
+            "Zswap" => info.zswap = Some(bytes),
+            "Zswapped" => info.zswapped = Some(bytes),
+            _ => {}
+        }
+    }
+    if info.total == 0 {
+        return None;
+    }
+    info.hugepages_total = hugepages_total * info.hugepage_size;
+    info.hugepages_free = hugepages_free * info.hugepage_size;
+    Some(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_meminfo() {
+        let contents = "MemTotal:       16000000 kB\n\
+                        MemFree:         2000000 kB\n\
+                        MemAvailable:    9000000 kB\n\
+                        Buffers:          500000 kB\n\
+                        Cached:          6000000 kB\n\
+                        SwapCached:            0 kB\n\
+                        Shmem:           1000000 kB\n\
+                        SReclaimable:     700000 kB\n\
+                        SUnreclaim:       300000 kB\n\
+                        Dirty:              1234 kB\n\
+                        Writeback:             0 kB\n\
+                        CommitLimit:    12000000 kB\n\
+                        Committed_AS:   20000000 kB\n\
+                        HugePages_Total:      16\n\
+                        HugePages_Free:        4\n\
+                        Hugepagesize:       2048 kB\n";
+        let info = parse_meminfo(contents).unwrap();
+        assert_eq!(info.total, 16_000_000 * 1024);
+        assert_eq!(info.available, 9_000_000 * 1024);
+        assert_eq!(info.dirty, 1234 * 1024);
+        assert_eq!(info.committed, 20_000_000 * 1024);
+        assert_eq!(info.hugepages_total, 16 * 2048 * 1024);
+        assert_eq!(info.hugepages_free, 4 * 2048 * 1024);
+        assert_eq!(info.zswap, None);
+
+        assert_eq!(info.used(), 6_500_000 * 1024);
+        assert_eq!(info.component(MemComponent::Cached), 5_000_000 * 1024);
+        assert_eq!(info.component(MemComponent::Slab), 1_000_000 * 1024);
+        let sum: u64 = MemComponent::ALL.iter().map(|&c| info.component(c)).sum();
+        assert_eq!(sum, info.total);
+
+        assert_eq!(parse_meminfo(""), None);
+    }
+}
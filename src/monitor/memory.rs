@@ -1,3 +1,7 @@
+use super::cgroup_limits::{CgroupLimitReader, CgroupLimits};
+use super::meminfo::{MemComponent, MemInfo};
+use super::swap::SwapDetails;
+use super::vmstat::{MemoryEvents, VmStat};
 use std::collections::VecDeque;
 use sysinfo::{MemoryRefreshKind, RefreshKind, System};
 
@@ -7,26 +11,78 @@ pub struct MemoryMonitor {
     system: System,
     mem_history: VecDeque<f32>,
     swap_history: VecDeque<f32>,
+    cgroup: CgroupLimitReader,
+    limits: CgroupLimits,
+    meminfo: Option<MemInfo>,
+    /// Share of total memory per breakdown component, indexed like `MemComponent::ALL`
+    breakdown_history: Vec<VecDeque<f64>>,
+    swap_details: SwapDetails,
+    vmstat: Option<VmStat>,
+    /// Pages swapped in and out per second
+    swap_rates: (f64, f64),
+    swap_in_history: VecDeque<f64>,
+    swap_out_history: VecDeque<f64>,
+    oom_kills_at_start: u64,
+    events: MemoryEvents,
 }
 
 impl MemoryMonitor {
-    pub fn new() -> Self {
+    pub fn new(cgroup: CgroupLimitReader) -> Self {
         let system = System::new_with_specifics(
             RefreshKind::new().with_memory(MemoryRefreshKind::everything()),
         );
 
+        let limits = cgroup.read();
+        let meminfo = MemInfo::read();
+        let vmstat = VmStat::read();
+        let oom_kills_at_start = vmstat.as_ref().and_then(|v| v.get("oom_kill")).unwrap_or(0);
+        let events = vmstat.as_ref().map_or_else(MemoryEvents::default, |v| {
+            MemoryEvents::from_samples(v, None, oom_kills_at_start)
+        });
         Self {
             system,
             mem_history: VecDeque::from(vec![0.0; HISTORY_SIZE]),
             swap_history: VecDeque::from(vec![0.0; HISTORY_SIZE]),
+            cgroup,
+            limits,
+            meminfo,
+            breakdown_history: MemComponent::ALL
+                .iter()
+                .map(|_| VecDeque::from(vec![0.0; HISTORY_SIZE]))
+                .collect(),
+            swap_details: SwapDetails::read(meminfo.as_ref()),
+            vmstat,
+            swap_rates: (0.0, 0.0),
+            swap_in_history: VecDeque::from(vec![0.0; HISTORY_SIZE]),
+            swap_out_history: VecDeque::from(vec![0.0; HISTORY_SIZE]),
+            oom_kills_at_start,
+            events,
         }
     }
 
     pub fn update(&mut self) {
         self.system.refresh_memory();
+        self.limits = self.cgroup.read();
+        self.meminfo = MemInfo::read();
+        self.swap_details = SwapDetails::read(self.meminfo.as_ref());
 
-        let mem_percent =
-            (self.system.used_memory() as f64 / self.system.total_memory() as f64 * 100.0) as f32;
+        let vmstat = VmStat::read();
+        self.swap_rates = match (&vmstat, &self.vmstat) {
+            (Some(now), Some(before)) => (now.rate(before, "pswpin"), now.rate(before, "pswpout")),
+            _ => (0.0, 0.0),
+        };
+        if let Some(now) = &vmstat {
+            self.events =
+                MemoryEvents::from_samples(now, self.vmstat.as_ref(), self.oom_kills_at_start);
+        }
+        self.vmstat = vmstat;
+        self.swap_in_history.pop_front();
+        self.swap_in_history.push_back(self.swap_rates.0);
+        self.swap_out_history.pop_front();
+        self.swap_out_history.push_back(self.swap_rates.1);
+
+        let (used, total) = self.used_and_total();
+        let mem_percent = (used as f64 / total.max(1) as f64 * 100.0) as f32;
         let swap_percent = if self.system.total_swap() > 0 {
             (self.system.used_swap() as f64 / self.system.total_swap() as f64 * 100.0) as f32
         } else {
@@ -38,18 +94,82 @@ impl MemoryMonitor {
 
         self.swap_history.pop_front();
         self.swap_history.push_back(swap_percent);
+
+        for (component, history) in MemComponent::ALL.iter().zip(&mut self.breakdown_history) {
+            let percent = self.meminfo.map_or(0.0, |info| {
+                info.component(*component) as f64 / info.total.max(1) as f64 * 100.0
+            });
+            history.pop_front();
+            history.push_back(percent);
+        }
+    }
+
+    /// Used and total memory; inside a memory-limited cgroup, its usage against the limit
+    fn used_and_total(&self) -> (u64, u64) {
+        let total = self.system.total_memory();
+        match (self.memory_limit(), self.limits.memory_usage) {
+            (Some(limit), Some(usage)) => (usage.min(limit), limit),
+            _ => (self.system.used_memory(), total),
+        }
+    }
+
+    /// Cgroup memory limit when it is below the host's memory
+    pub fn memory_limit(&self) -> Option<u64> {
+        self.limits
+            .memory_limit
+            .filter(|&limit| limit < self.system.total_memory())
+    }
+
+    /// Memory that can be handed out without swapping; inside a memory-limited cgroup,
+    /// what is left of the limit
+    pub fn available(&self) -> u64 {
+        let (used, total) = self.used_and_total();
+        match (self.memory_limit(), self.meminfo) {
+            (Some(_), _) => total.saturating_sub(used),
+            (None, Some(info)) => info.available,
+            (None, None) => self.system.available_memory(),
+        }
+    }
+
+    /// Host-wide /proc/meminfo breakdown (Linux only)
+    pub fn meminfo(&self) -> Option<&MemInfo> {
+        self.meminfo.as_ref()
+    }
+
+    /// Share of total memory a breakdown component held over time, oldest first
+    pub fn breakdown_history(&self, component: MemComponent) -> &VecDeque<f64> {
+        let index = MemComponent::ALL
+            .iter()
+            .position(|c| *c == component)
+            .unwrap_or(0);
+        &self.breakdown_history[index]
+    }
+
+    /// Swap devices, zram and zswap
+    pub fn swap_details(&self) -> &SwapDetails {
+        &self.swap_details
+    }
+
+    /// Pages swapped in and out per second over the last refresh
+    pub fn swap_rates(&self) -> (f64, f64) {
+        self.swap_rates
+    }
+
+    /// Swap-in and swap-out page rates over time, oldest first
+    pub fn swap_rate_history(&self) -> (&VecDeque<f64>, &VecDeque<f64>) {
+        (&self.swap_in_history, &self.swap_out_history)
+    }
+
+    /// OOM kills, page faults and reclaim activity from /proc/vmstat
+    pub fn events(&self) -> &MemoryEvents {
+        &self.events
     }
 
     pub fn get_memory_data(&self) -> (f32, Vec<f32>, u64, u64) {
-        let percent =
-            (self.system.used_memory() as f64 / self.system.total_memory() as f64 * 100.0) as f32;
+        let (used, total) = self.used_and_total();
+        let percent = (used as f64 / total.max(1) as f64 * 100.0) as f32;
         let history: Vec<f32> = self.mem_history.iter().copied().collect();
-        (
-            percent,
-            history,
-            self.system.used_memory(),
-            self.system.total_memory(),
-        )
+        (percent, history, used, total)
     }
 
     pub fn get_swap_data(&self) -> (f32, Vec<f32>, u64, u64) {
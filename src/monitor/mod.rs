@@ -1,26 +1,43 @@
 pub mod battery;
+pub mod cgroup;
+pub mod cgroup_limits;
 pub mod cpu;
+pub mod cpu_times;
+pub mod cpu_topology;
 pub mod disk;
 pub mod diskio;
+pub mod exited;
 pub mod gpu;
+pub mod latency;
+pub mod meminfo;
 pub mod memory;
 pub mod network;
 pub mod npu;
+pub mod pressure;
 pub mod process;
+pub mod process_detail;
+pub mod sched;
+pub mod swap;
 pub mod system;
 pub mod temp;
+pub mod vmstat;
 
 pub use battery::BatteryMonitor;
-pub use cpu::CpuMonitor;
+pub use cpu::{CpuCoreInfo, CpuMonitor};
 pub use disk::DiskMonitor;
 pub use diskio::DiskIOMonitor;
 #[allow(unused_imports)]
 pub use gpu::{GpuInfo, GpuMonitor, GpuVendor};
+pub use latency::LatencyMonitor;
 pub use memory::MemoryMonitor;
 pub use network::NetworkMonitor;
 #[allow(unused_imports)]
 pub use npu::{NpuInfo, NpuMonitor, NpuVendor};
+pub use pressure::PressureMonitor;
 #[allow(unused_imports)]
-pub use process::{ProcessInfo, ProcessMonitor, ProcessSignal, SortOrder};
+pub use process::{
+    ProcessHistory, ProcessInfo, ProcessKey, ProcessMonitor, ProcessSignal, SortOrder,
+};
+pub use process_detail::ProcessDetailMonitor;
 pub use system::SystemMonitor;
 pub use temp::TempMonitor;
@@ -1,150 +1,272 @@
-use std::collections::VecDeque;
-use std::process::Command;
-use std::time::{Duration, Instant};
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
 use sysinfo::Networks;
 
+use crate::config::NetworkConfig;
+use crate::utils::glob_match;
+
 const HISTORY_SIZE: usize = 61;
 
+/// Snapshot of a single network interface
+#[derive(Debug, Clone, Default)]
+pub struct InterfaceInfo {
+    pub name: String,
+    pub rx_rate: u64, // bytes/sec
+    pub tx_rate: u64, // bytes/sec
+    pub total_rx: u64,
+    pub total_tx: u64,
+    pub rx_errors: u64,
+    pub tx_errors: u64,
+    pub rx_dropped: u64,
+    pub tx_dropped: u64,
+    pub oper_state: String,
+    pub mtu: Option<u32>,
+    pub speed_mbps: Option<u32>,
+    pub addresses: Vec<String>,
+}
+
+impl InterfaceInfo {
+    pub fn is_up(&self) -> bool {
+        // Loopback and some virtual drivers report "unknown" while passing traffic
+        self.oper_state == "up" || self.oper_state == "unknown"
+    }
+}
+
+struct InterfaceHistory {
+    rx: VecDeque<u64>,
+    tx: VecDeque<u64>,
+    last_rx: u64,
+    last_tx: u64,
+}
+
 pub struct NetworkMonitor {
     networks: Networks,
+    include: Vec<String>,
+    exclude: Vec<String>,
+    interfaces: Vec<InterfaceInfo>,
+    histories: HashMap<String, InterfaceHistory>,
     rx_history: VecDeque<u64>,
     tx_history: VecDeque<u64>,
     total_rx: u64,
     total_tx: u64,
-    last_rx: u64,
-    last_tx: u64,
-    ping_latency: Option<f32>,
-    last_ping_check: Instant,
-    active_interface: String,
+    last_update: Instant,
+    /// Interface pinned by the user; `None` shows the aggregate of all monitored interfaces
+    selected: Option<String>,
 }
 
 impl NetworkMonitor {
-    pub fn new() -> Self {
-        let networks = Networks::new_with_refreshed_list();
-
-        let (total_rx, total_tx) = networks.iter().fold((0u64, 0u64), |(rx, tx), (_, data)| {
-            (rx + data.total_received(), tx + data.total_transmitted())
-        });
-
-        Self {
-            networks,
+    pub fn new(config: &NetworkConfig) -> Self {
+        let mut monitor = Self {
+            networks: Networks::new_with_refreshed_list(),
+            include: config.include.clone(),
+            exclude: config.exclude.clone(),
+            interfaces: Vec::new(),
+            histories: HashMap::new(),
             rx_history: VecDeque::from(vec![0; HISTORY_SIZE]),
             tx_history: VecDeque::from(vec![0; HISTORY_SIZE]),
-            total_rx,
-            total_tx,
-            last_rx: total_rx,
-            last_tx: total_tx,
-            ping_latency: None,
-            last_ping_check: Instant::now(),
-            active_interface: String::from("eth0"),
+            total_rx: 0,
+            total_tx: 0,
+            last_update: Instant::now(),
+            selected: None,
+        };
+
+        // Seed per-interface counters so the first update reports a delta, not the lifetime total
+        for (name, data) in monitor.networks.iter() {
+            if monitor.is_monitored(name) {
+                monitor.histories.insert(
+                    name.clone(),
+                    InterfaceHistory {
+                        rx: VecDeque::from(vec![0; HISTORY_SIZE]),
+                        tx: VecDeque::from(vec![0; HISTORY_SIZE]),
+                        last_rx: data.total_received(),
+                        last_tx: data.total_transmitted(),
+                    },
+                );
+            }
         }
+
+        monitor
     }
 
     pub fn update(&mut self) {
-        self.networks.refresh();
+        // refresh_list also picks up interfaces created after startup (new containers, VPNs)
+        self.networks.refresh_list();
+
+        // Rates are derived from the measured interval, so they stay in bytes/sec regardless
+        // of the configured refresh rate, minimal mode, or time spent paused
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_update).as_secs_f64();
+        self.last_update = now;
+
+        let mut interfaces = Vec::new();
+        let (mut sum_rx_rate, mut sum_tx_rate) = (0u64, 0u64);
+        let (mut sum_rx, mut sum_tx) = (0u64, 0u64);
+
+        for (name, data) in self.networks.iter() {
+            if !self.is_monitored(name) {
+                continue;
+            }
+
+            let current_rx = data.total_received();
+            let current_tx = data.total_transmitted();
+
+            let history = self
+                .histories
+                .entry(name.clone())
+                .or_insert_with(|| InterfaceHistory {
+                    rx: VecDeque::from(vec![0; HISTORY_SIZE]),
+                    tx: VecDeque::from(vec![0; HISTORY_SIZE]),
+                    last_rx: current_rx,
+                    last_tx: current_tx,
+                });
+
+            let rx_rate = per_second(current_rx.saturating_sub(history.last_rx), elapsed);
+            let tx_rate = per_second(current_tx.saturating_sub(history.last_tx), elapsed);
+            history.last_rx = current_rx;
+            history.last_tx = current_tx;
+
+            history.rx.pop_front();
+            history.rx.push_back(rx_rate);
+            history.tx.pop_front();
+            history.tx.push_back(tx_rate);
 
-        let (current_rx, current_tx) = self
-            .networks
-            .iter()
-            .fold((0u64, 0u64), |(rx, tx), (_, data)| {
-                (rx + data.total_received(), tx + data.total_transmitted())
+            sum_rx_rate += rx_rate;
+            sum_tx_rate += tx_rate;
+            sum_rx += current_rx;
+            sum_tx += current_tx;
+
+            let sys_path = Path::new("/sys/class/net").join(name);
+            interfaces.push(InterfaceInfo {
+                name: name.clone(),
+                rx_rate,
+                tx_rate,
+                total_rx: current_rx,
+                total_tx: current_tx,
+                rx_errors: data.total_errors_on_received(),
+                tx_errors: data.total_errors_on_transmitted(),
+                rx_dropped: read_sys_u64(&sys_path.join("statistics/rx_dropped")).unwrap_or(0),
+                tx_dropped: read_sys_u64(&sys_path.join("statistics/tx_dropped")).unwrap_or(0),
+                oper_state: fs::read_to_string(sys_path.join("operstate"))
+                    .map(|s| s.trim().to_string())
+                    .unwrap_or_else(|_| "unknown".to_string()),
+                mtu: read_sys_u64(&sys_path.join("mtu")).map(|v| v as u32),
+                // Reported as -1 for virtual links and links without carrier
+                speed_mbps: fs::read_to_string(sys_path.join("speed"))
+                    .ok()
+                    .and_then(|s| s.trim().parse::<i64>().ok())
+                    .filter(|&v| v > 0)
+                    .map(|v| v as u32),
+                addresses: data.ip_networks().iter().map(|ip| ip.to_string()).collect(),
             });
+        }
 
-        let rx_sec = current_rx.saturating_sub(self.last_rx);
-        let tx_sec = current_tx.saturating_sub(self.last_tx);
+        interfaces.sort_by(|a, b| a.name.cmp(&b.name));
 
-        self.rx_history.pop_front();
-        self.rx_history.push_back(rx_sec);
+        // Forget interfaces that went away, and unpin a vanished selection
+        self.histories
+            .retain(|name, _| interfaces.iter().any(|i| &i.name == name));
+        if let Some(selected) = &self.selected {
+            if !interfaces.iter().any(|i| &i.name == selected) {
+                self.selected = None;
+            }
+        }
 
+        self.rx_history.pop_front();
+        self.rx_history.push_back(sum_rx_rate);
         self.tx_history.pop_front();
-        self.tx_history.push_back(tx_sec);
+        self.tx_history.push_back(sum_tx_rate);
+        self.total_rx = sum_rx;
+        self.total_tx = sum_tx;
+        self.interfaces = interfaces;
+    }
 
-        self.last_rx = current_rx;
-        self.last_tx = current_tx;
-        self.total_rx = current_rx;
-        self.total_tx = current_tx;
+    fn is_monitored(&self, name: &str) -> bool {
+        let included = self.include.is_empty() || self.include.iter().any(|p| glob_match(p, name));
+        included && !self.exclude.iter().any(|p| glob_match(p, name))
     }
 
+    /// Rate history, current rates (bytes/sec) and cumulative byte counters for the
+    /// displayed interface (pinned one, or the aggregate)
     pub fn get_network_data(&self) -> (Vec<u64>, Vec<u64>, u64, u64, u64, u64) {
-        let rx_history: Vec<u64> = self.rx_history.iter().copied().collect();
-        let tx_history: Vec<u64> = self.tx_history.iter().copied().collect();
-        let rx_sec = *self.rx_history.back().unwrap_or(&0);
-        let tx_sec = *self.tx_history.back().unwrap_or(&0);
+        let selected = self
+            .selected
+            .as_ref()
+            .and_then(|name| Some((self.histories.get(name)?, self.get_interface(name)?)));
+
+        let (rx_history, tx_history, total_rx, total_tx) = match selected {
+            Some((history, info)) => (&history.rx, &history.tx, info.total_rx, info.total_tx),
+            None => (
+                &self.rx_history,
+                &self.tx_history,
+                self.total_rx,
+                self.total_tx,
+            ),
+        };
 
         (
-            rx_history,
-            tx_history,
-            rx_sec,
-            tx_sec,
-            self.total_rx,
-            self.total_tx,
+            rx_history.iter().copied().collect(),
+            tx_history.iter().copied().collect(),
+            *rx_history.back().unwrap_or(&0),
+            *tx_history.back().unwrap_or(&0),
+            total_rx,
+            total_tx,
         )
     }
 
-    pub fn get_ping_latency(&self) -> Option<f32> {
-        self.ping_latency
+    /// All monitored interfaces (after include/exclude filtering), sorted by name
+    pub fn get_interfaces(&self) -> &[InterfaceInfo] {
+        &self.interfaces
     }
 
-    pub fn get_active_interface(&self) -> &str {
-        // Get the first active interface
-        if let Some((name, _data)) = self
-            .networks
-            .iter()
-            .find(|(_, data)| data.total_received() > 0 || data.total_transmitted() > 0)
-        {
-            name
-        } else {
-            &self.active_interface
-        }
+    pub fn get_interface(&self, name: &str) -> Option<&InterfaceInfo> {
+        self.interfaces.iter().find(|i| i.name == name)
     }
 
-    pub fn update_ping(&mut self) {
-        // Only check ping every 3 seconds to avoid overhead
-        if self.last_ping_check.elapsed() < Duration::from_secs(3) {
-            return;
-        }
-
-        self.last_ping_check = Instant::now();
-
-        // Try ping to common DNS servers (Google, Cloudflare)
-        let hosts = ["8.8.8.8", "1.1.1.1"];
+    /// Cycle the pinned interface: aggregate -> first -> ... -> last -> aggregate
+    pub fn cycle_interface(&mut self) {
+        let next = match &self.selected {
+            None => self.interfaces.first(),
+            Some(current) => self
+                .interfaces
+                .iter()
+                .position(|i| &i.name == current)
+                .and_then(|pos| self.interfaces.get(pos + 1)),
+        };
+        self.selected = next.map(|i| i.name.clone());
+    }
 
-        for host in &hosts {
-            if let Some(latency) = self.ping_host(host) {
-                self.ping_latency = Some(latency);
-                return;
-            }
-        }
+    pub fn selected_interface(&self) -> Option<&str> {
+        self.selected.as_deref()
+    }
 
-        self.ping_latency = None;
+    pub fn get_active_interface(&self) -> &str {
+        self.selected.as_deref().unwrap_or("all")
     }
+}
 
-    fn ping_host(&self, host: &str) -> Option<f32> {
-        // Use ping command with 1 packet, 1 second timeout
-        let output = Command::new("ping")
-            .args(["-c", "1", "-W", "1", host])
-            .output()
-            .ok()?;
+/// Convert a counter delta over `elapsed_secs` into a per-second rate
+fn per_second(delta: u64, elapsed_secs: f64) -> u64 {
+    if elapsed_secs <= 0.0 {
+        return 0;
+    }
+    (delta as f64 / elapsed_secs).round() as u64
+}
 
-        if !output.status.success() {
-            return None;
-        }
+fn read_sys_u64(path: &Path) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-
-        // Parse ping output for latency (works on Linux/macOS)
-        // Example: "time=14.2 ms"
-        for line in stdout.lines() {
-            if let Some(time_pos) = line.find("time=") {
-                let time_str = &line[time_pos + 5..];
-                if let Some(space_pos) = time_str.find(' ') {
-                    if let Ok(latency) = time_str[..space_pos].parse::<f32>() {
-                        return Some(latency);
-                    }
-                }
-            }
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        None
+    #[test]
+    fn test_per_second_uses_elapsed_time() {
+        assert_eq!(per_second(1000, 1.0), 1000);
+        assert_eq!(per_second(1000, 2.0), 500);
+        assert_eq!(per_second(1000, 0.5), 2000);
+        assert_eq!(per_second(1000, 0.0), 0);
     }
 }
@@ -0,0 +1,260 @@
+/// Pressure Stall Information: the share of time tasks were stalled waiting for CPU,
+/// memory or I/O, system-wide (/proc/pressure) and for the container rtop runs in
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::cgroup_limits::CgroupLimitReader;
+
+const HISTORY_SIZE: usize = 61;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PressureResource {
+    Cpu,
+    Memory,
+    Io,
+}
+
+impl PressureResource {
+    pub const ALL: [PressureResource; 3] = [
+        PressureResource::Cpu,
+        PressureResource::Memory,
+        PressureResource::Io,
+    ];
+
+    /// File name below /proc/pressure; cgroups append ".pressure"
+    fn file(self) -> &'static str {
+        match self {
+            PressureResource::Cpu => "cpu",
+            PressureResource::Memory => "memory",
+            PressureResource::Io => "io",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PressureResource::Cpu => "CPU",
+            PressureResource::Memory => "Memory",
+            PressureResource::Io => "I/O",
+        }
+    }
+}
+
+/// One line of a pressure file: percentages over 10 s, 60 s and 300 s, and the total
+/// stall time in microseconds
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PsiAverages {
+    pub avg10: f64,
+    pub avg60: f64,
+    pub avg300: f64,
+    pub total: u64,
+}
+
+/// "some": at least one task stalled; "full": all non-idle tasks stalled at once
+/// (not reported for CPU at the system level on older kernels)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Pressure {
+    pub some: PsiAverages,
+    pub full: Option<PsiAverages>,
+}
+
+pub fn parse_pressure(contents: &str) -> Option<Pressure> {
+    let mut some = None;
+    let mut full = None;
+    for line in contents.lines() {
+        let mut fields = line.split_whitespace();
+        let kind = fields.next();
+        let mut averages = PsiAverages::default();
+        for field in fields {
+            let Some((key, value)) = field.split_once('=') else {
+                continue;
+            };
+            match key {
+                "avg10" => averages.avg10 = value.parse().unwrap_or(0.0),
+                "avg60" => averages.avg60 = value.parse().unwrap_or(0.0),
+                "avg300" => averages.avg300 = value.parse().unwrap_or(0.0),
+                "total" => averages.total = value.parse().unwrap_or(0),
+                _ => {}
+            }
+        }
+        match kind {
+            Some("some") => some = Some(averages),
+            Some("full") => full = Some(averages),
+            _ => {}
+        }
+    }
+    Some(Pressure { some: some?, full })
+}
+
+/// Read `resource`'s pressure file from `dir`: "cpu" etc. below /proc/pressure,
+/// "cpu.pressure" etc. (`suffix` ".pressure") in a cgroup directory
+pub fn read_pressure(dir: &Path, suffix: &str, resource: PressureResource) -> Option<Pressure> {
+    let path = dir.join(format!("{}{}", resource.file(), suffix));
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| parse_pressure(&contents))
+}
+
+/// Pressure files of one scope (the system or a cgroup) with their avg10 history
+pub struct PressureSource {
+    dir: PathBuf,
+    suffix: &'static str,
+    current: [Option<Pressure>; 3],
+    some_history: [VecDeque<f64>; 3],
+}
+
+impl PressureSource {
+    fn new(dir: PathBuf, suffix: &'static str) -> Self {
+        let history = || VecDeque::from(vec![0.0; HISTORY_SIZE]);
+        let mut source = Self {
+            dir,
+            suffix,
+            current: [None; 3],
+            some_history: [history(), history(), history()],
+        };
+        source.update();
+        source
+    }
+
+    fn update(&mut self) {
+        for (i, resource) in PressureResource::ALL.iter().enumerate() {
+            let pressure = read_pressure(&self.dir, self.suffix, *resource);
+            self.current[i] = pressure;
+
+            let history = &mut self.some_history[i];
+            history.pop_front();
+            history.push_back(pressure.map_or(0.0, |p| p.some.avg10));
+        }
+    }
+
+    fn is_available(&self) -> bool {
+        self.current.iter().any(Option::is_some)
+    }
+
+    pub fn get(&self, resource: PressureResource) -> Option<Pressure> {
+        self.current[Self::index(resource)]
+    }
+
+    /// "some" avg10 over time, oldest first
+    pub fn some_history(&self, resource: PressureResource) -> &VecDeque<f64> {
+        &self.some_history[Self::index(resource)]
+    }
+
+    fn index(resource: PressureResource) -> usize {
+        PressureResource::ALL
+            .iter()
+            .position(|r| *r == resource)
+            .unwrap_or(0)
+    }
+}
+
+pub struct PressureMonitor {
+    system: PressureSource,
+    /// Cgroup of the container rtop runs in, when it exposes pressure files. On a host
+    /// rtop's own cgroup is just the terminal's session scope, so it is left out there;
+    /// the group view shows pressure per service and container instead
+    cgroup: Option<PressureSource>,
+}
+
+impl PressureMonitor {
+    pub fn new(reader: &CgroupLimitReader) -> Self {
+        let cgroup = reader
+            .unified_dir()
+            .filter(|_| reader.in_container())
+            .map(|dir| PressureSource::new(dir.to_path_buf(), ".pressure"))
+            .filter(PressureSource::is_available);
+        Self::with_sources(Path::new("/proc/pressure"), cgroup)
+    }
+
+    fn with_sources(system_dir: &Path, cgroup: Option<PressureSource>) -> Self {
+        Self {
+            system: PressureSource::new(system_dir.to_path_buf(), ""),
+            cgroup,
+        }
+    }
+
+    pub fn update(&mut self) {
+        self.system.update();
+        if let Some(cgroup) = &mut self.cgroup {
+            cgroup.update();
+        }
+    }
+
+    /// PSI needs Linux 4.20+ built with CONFIG_PSI (and psi=1 on some distributions)
+    pub fn is_available(&self) -> bool {
+        self.system.is_available()
+    }
+
+    pub fn system(&self) -> &PressureSource {
+        &self.system
+    }
+
+    pub fn cgroup(&self) -> Option<&PressureSource> {
+        self.cgroup.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_pressure() {
+        let memory = "some avg10=1.53 avg60=0.87 avg300=0.22 total=123456\n\
+                      full avg10=0.50 avg60=0.25 avg300=0.05 total=4567\n";
+        let pressure = parse_pressure(memory).unwrap();
+        assert_eq!(
+            pressure.some,
+            PsiAverages {
+                avg10: 1.53,
+                avg60: 0.87,
+                avg300: 0.22,
+                total: 123456,
+            }
+        );
+        assert_eq!(pressure.full.unwrap().avg10, 0.50);
+
+        let cpu = "some avg10=0.00 avg60=0.00 avg300=0.00 total=0\n";
+        assert_eq!(parse_pressure(cpu).unwrap().full, None);
+        assert_eq!(parse_pressure(""), None);
+    }
+
+    #[test]
+    fn test_sources_from_fixture() {
+        let system = TempDir::new().unwrap();
+        fs::write(
+            system.path().join("io"),
+            "some avg10=12.00 avg60=5.00 avg300=1.00 total=99\n\
+             full avg10=8.00 avg60=3.00 avg300=0.50 total=50\n",
+        )
+        .unwrap();
+        let cgroup = TempDir::new().unwrap();
+        fs::write(
+            cgroup.path().join("cpu.pressure"),
+            "some avg10=40.00 avg60=20.00 avg300=10.00 total=7\n\
+             full avg10=0.00 avg60=0.00 avg300=0.00 total=0\n",
+        )
+        .unwrap();
+
+        let mut monitor = PressureMonitor::with_sources(
+            system.path(),
+            Some(PressureSource::new(
+                cgroup.path().to_path_buf(),
+                ".pressure",
+            )),
+        );
+        monitor.update();
+
+        assert!(monitor.is_available());
+        assert_eq!(monitor.system().get(PressureResource::Cpu), None);
+        let io = monitor.system().get(PressureResource::Io).unwrap();
+        assert_eq!(io.full.unwrap().avg10, 8.0);
+        let history = monitor.system().some_history(PressureResource::Io);
+        assert_eq!(history.len(), HISTORY_SIZE);
+        assert_eq!(history.back(), Some(&12.0));
+
+        let cgroup = monitor.cgroup().unwrap();
+        assert_eq!(cgroup.get(PressureResource::Cpu).unwrap().some.avg60, 20.0);
+    }
+}
@@ -1,14 +1,77 @@
-use std::collections::HashMap;
-use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, RefreshKind, Signal, System};
+use super::cgroup;
+use super::exited::{ExitTracker, ExitedProcess};
+use super::process_detail::{self, ThreadInfo, ThreadSampler};
+use super::sched::{self, IoPriority};
+use crate::config::{DisplayConfig, ProcessColumn, ProcessConfig};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use sysinfo::{
+    Pid, Process, ProcessRefreshKind, ProcessStatus, ProcessesToUpdate, RefreshKind, Signal,
+    System, ThreadKind, Uid, Users,
+};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-#[allow(dead_code)]
+/// Samples kept per process; enough for the widest sparkline column
+const HISTORY_SIZE: usize = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SortOrder {
     Pid,
+    Ppid,
+    User,
+    State,
+    Nice,
+    Threads,
     Cpu,
     Memory,
+    Virtual,
+    Shared,
+    ReadRate,
+    WriteRate,
+    /// Read plus write rate
+    IoRate,
+    MinorFaults,
+    MajorFaults,
+    StartTime,
+    CpuTime,
     Name,
-    User,
+    Command,
+    Cgroup,
+    Unit,
+    Container,
+}
+
+impl From<ProcessColumn> for SortOrder {
+    fn from(column: ProcessColumn) -> Self {
+        match column {
+            ProcessColumn::Pid => SortOrder::Pid,
+            ProcessColumn::Ppid => SortOrder::Ppid,
+            ProcessColumn::User => SortOrder::User,
+            ProcessColumn::State => SortOrder::State,
+            ProcessColumn::Nice => SortOrder::Nice,
+            ProcessColumn::Threads => SortOrder::Threads,
+            ProcessColumn::Cpu | ProcessColumn::CpuGraph => SortOrder::Cpu,
+            ProcessColumn::MemPercent | ProcessColumn::Rss | ProcessColumn::MemGraph => {
+                SortOrder::Memory
+            }
+            ProcessColumn::Virt => SortOrder::Virtual,
+            ProcessColumn::Shared => SortOrder::Shared,
+            ProcessColumn::ReadRate => SortOrder::ReadRate,
+            ProcessColumn::WriteRate => SortOrder::WriteRate,
+            ProcessColumn::IoGraph => SortOrder::IoRate,
+            ProcessColumn::MinorFaults => SortOrder::MinorFaults,
+            ProcessColumn::MajorFaults => SortOrder::MajorFaults,
+            ProcessColumn::StartTime => SortOrder::StartTime,
+            ProcessColumn::CpuTime => SortOrder::CpuTime,
+            ProcessColumn::Name => SortOrder::Name,
+            ProcessColumn::Command => SortOrder::Command,
+            ProcessColumn::Cgroup => SortOrder::Cgroup,
+            ProcessColumn::Unit => SortOrder::Unit,
+            ProcessColumn::Container => SortOrder::Container,
+        }
+    }
 }
 
 /// Process signal types (matching btop's comprehensive signal support)
@@ -27,7 +90,7 @@ pub enum ProcessSignal {
 }
 
 impl ProcessSignal {
-    #[allow(dead_code)]
+    #[cfg_attr(unix, allow(dead_code))]
     pub fn to_sysinfo_signal(self) -> Signal {
         match self {
             ProcessSignal::Term => Signal::Term,
@@ -42,7 +105,21 @@ impl ProcessSignal {
         }
     }
 
-    #[allow(dead_code)]
+    #[cfg(unix)]
+    pub fn to_libc_signal(self) -> libc::c_int {
+        match self {
+            ProcessSignal::Term => libc::SIGTERM,
+            ProcessSignal::Kill => libc::SIGKILL,
+            ProcessSignal::Int => libc::SIGINT,
+            ProcessSignal::Hup => libc::SIGHUP,
+            ProcessSignal::Quit => libc::SIGQUIT,
+            ProcessSignal::Stop => libc::SIGSTOP,
+            ProcessSignal::Cont => libc::SIGCONT,
+            ProcessSignal::Usr1 => libc::SIGUSR1,
+            ProcessSignal::Usr2 => libc::SIGUSR2,
+        }
+    }
+
     pub fn name(&self) -> &str {
         match self {
             ProcessSignal::Term => "TERM (15)",
@@ -57,7 +134,6 @@ impl ProcessSignal {
         }
     }
 
-    #[allow(dead_code)]
     pub fn description(&self) -> &str {
         match self {
             ProcessSignal::Term => "Graceful termination",
@@ -72,7 +148,6 @@ impl ProcessSignal {
         }
     }
 
-    #[allow(dead_code)]
     pub fn all_signals() -> Vec<ProcessSignal> {
         vec![
             ProcessSignal::Term,
@@ -88,24 +163,78 @@ impl ProcessSignal {
     }
 }
 
-#[derive(Debug, Clone)]
+/// Identifies a process across refreshes; the start time tells a reused PID apart
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ProcessKey {
+    pub pid: u32,
+    pub start_time: u64,
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct ProcessInfo {
     pub pid: u32,
     pub ppid: Option<u32>,
     pub name: String,
     pub cpu_usage: f32,
     pub memory: u64,
+    /// Name of the real user (falls back to the numeric UID)
     pub user: String,
-    #[allow(dead_code)]
+    /// Name of the effective user when it differs from the real one (setuid, sudo)
+    pub effective_user: Option<String>,
+    /// One-letter state as in /proc/<pid>/stat (R, S, D, Z, T, ...)
     pub state: String,
-    #[allow(dead_code)]
+    /// Thread count from /proc/<pid>/status; 0 on thread rows
     pub threads: usize,
+    /// Row is a thread of the process listed above it (`pid` holds the TID)
+    pub is_thread: bool,
+    pub nice: i32,
+    pub virtual_memory: u64,
+    /// Resident file-backed and shmem pages, like SHR in top
+    pub shared_memory: u64,
     #[allow(dead_code)]
     pub read_bytes: u64,
     #[allow(dead_code)]
     pub write_bytes: u64,
+    /// Disk I/O in bytes/sec over the last refresh interval
+    pub read_rate: f64,
+    pub write_rate: f64,
+    /// Page faults/sec over the last refresh; major faults had to read from disk
+    pub minor_fault_rate: f64,
+    pub major_fault_rate: f64,
+    /// User plus system CPU time in seconds
+    pub cpu_time: f64,
+    /// Full command line; the name in brackets for kernel threads
+    pub command: String,
+    pub cgroup: String,
+    /// systemd unit the process runs in
+    pub unit: Option<String>,
+    /// Short container ID derived from the cgroup path
+    pub container: Option<String>,
+    /// Name the container runtime gives the container
+    pub container_name: Option<String>,
+    /// Seconds since the epoch; together with `pid` identifies a process across PID reuse
+    pub start_time: u64,
     pub children: Vec<u32>,
     pub tree_depth: usize,
+    /// Indent guides drawn before the name in tree view (e.g. "│ ├─")
+    pub tree_prefix: String,
+    /// Subtree is folded in tree view; its descendants are not listed
+    pub collapsed: bool,
+    /// Number of descendants hidden by collapsing
+    pub hidden_descendants: usize,
+    /// CPU of this process plus all hidden descendants
+    pub subtree_cpu: f32,
+    /// Memory of this process plus all hidden descendants
+    pub subtree_memory: u64,
+}
+
+impl ProcessInfo {
+    pub fn key(&self) -> ProcessKey {
+        ProcessKey {
+            pid: self.pid,
+            start_time: self.start_time,
+        }
+    }
 }
 
 pub struct ProcessMonitor {
@@ -114,29 +243,521 @@ pub struct ProcessMonitor {
     reverse: bool,
     tree_view: bool,
     process_tree: HashMap<u32, Vec<u32>>,
+    collapsed: HashSet<u32>,
+    users: Users,
+    /// UID -> user name; filled on refresh so lookups while listing are cheap
+    user_names: HashMap<String, String>,
+    /// PID -> figures sysinfo does not provide, read from /proc on refresh
+    proc_stats: HashMap<u32, ProcStats>,
+    /// Cgroup path per process; a process rarely moves, so it is read once
+    cgroups: HashMap<ProcessKey, String>,
+    last_refresh: Instant,
+    /// Seconds covered by the last refresh, for the disk I/O rates
+    refresh_interval: f64,
+    clock_ticks: f64,
+    /// Processes expanded into their individual threads
+    expanded_threads: HashMap<u32, ExpandedThreads>,
+    /// Recent samples per process, dropped when the process exits
+    histories: HashMap<ProcessKey, ProcessHistory>,
+    /// Processes listed by the last refresh, to describe the ones gone by the next
+    last_seen: HashMap<u32, SeenProcess>,
+    exits: ExitTracker,
+    containers: cgroup::ContainerNames,
+    /// Mount point of the cgroup v2 hierarchy, when there is one
+    cgroup_root: Option<PathBuf>,
+    /// Previous counters per aggregated cgroup, for rates
+    cgroup_samples: HashMap<String, cgroup::CgroupStats>,
+    group_usage: Vec<GroupUsage>,
+    visibility: Visibility,
+}
+
+/// The kernel-thread and self options, applied to live processes and exits alike
+#[derive(Debug, Clone, Copy)]
+struct Visibility {
+    show_kernel_threads: bool,
+    show_self: bool,
+    self_pid: u32,
+}
+
+impl Visibility {
+    fn shows(&self, pid: u32, kernel_thread: bool) -> bool {
+        (self.show_self || pid != self.self_pid) && (self.show_kernel_threads || !kernel_thread)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct ProcStats {
+    threads: usize,
+    nice: i32,
+    shared_memory: u64,
+    cpu_time: f64,
+    /// Clock ticks after boot; tells a reused PID apart when computing fault rates
+    start_ticks: u64,
+    minor_faults: u64,
+    major_faults: u64,
+    minor_fault_rate: f64,
+    major_fault_rate: f64,
+}
+
+struct SeenProcess {
+    start_time: u64,
+    kernel_thread: bool,
+    name: String,
+    command: String,
+    cpu_time: f64,
+}
+
+/// Bounded CPU, RSS and disk I/O history of one process, oldest sample first
+#[derive(Debug, Clone, Default)]
+pub struct ProcessHistory {
+    pub cpu: VecDeque<f64>,
+    pub memory: VecDeque<f64>,
+    /// Read plus write, bytes/sec
+    pub io: VecDeque<f64>,
+}
+
+impl ProcessHistory {
+    fn push(&mut self, cpu: f64, memory: f64, io: f64) {
+        for (series, value) in [
+            (&mut self.cpu, cpu),
+            (&mut self.memory, memory),
+            (&mut self.io, io),
+        ] {
+            series.push_back(value);
+            if series.len() > HISTORY_SIZE {
+                series.pop_front();
+            }
+        }
+    }
+}
+
+struct ExpandedThreads {
+    sampler: ThreadSampler,
+    threads: Vec<ThreadInfo>,
+}
+
+/// Resource usage summed over all processes of one user
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UserUsage {
+    pub user: String,
+    pub process_count: usize,
+    pub cpu_usage: f32,
+    pub memory: u64,
+}
+
+/// Resource usage of a container, systemd unit or other cgroup
+#[derive(Debug, Clone, Default)]
+pub struct GroupUsage {
+    /// Container name or ID, unit name, or the cgroup path
+    pub label: String,
+    /// "container", the unit type ("service", "scope", ...) or "cgroup"
+    pub kind: &'static str,
+    pub path: String,
+    pub process_count: usize,
+    pub cpu_usage: f32,
+    pub memory: u64,
+    pub read_rate: f64,
+    pub write_rate: f64,
+    /// "some" avg10 stall percentage per `PressureResource::ALL`; absent without PSI
+    pub pressure: [Option<f64>; 3],
 }
 
 impl ProcessMonitor {
-    pub fn new() -> Self {
+    pub fn new(display: &DisplayConfig, config: &ProcessConfig) -> Self {
         let mut system = System::new_with_specifics(
             RefreshKind::new().with_processes(ProcessRefreshKind::everything()),
         );
         system.refresh_processes(ProcessesToUpdate::All, true);
 
-        Self {
+        let mut monitor = Self {
             system,
             sort_order: SortOrder::Cpu,
             reverse: false,
             tree_view: false,
             process_tree: HashMap::new(),
-        }
+            collapsed: HashSet::new(),
+            users: Users::new_with_refreshed_list(),
+            user_names: HashMap::new(),
+            proc_stats: HashMap::new(),
+            cgroups: HashMap::new(),
+            last_refresh: Instant::now(),
+            refresh_interval: 0.0,
+            clock_ticks: process_detail::clock_ticks_per_second(),
+            expanded_threads: HashMap::new(),
+            histories: HashMap::new(),
+            last_seen: HashMap::new(),
+            exits: ExitTracker::new(
+                Duration::from_secs(config.exited_retention),
+                config.proc_events,
+            ),
+            containers: cgroup::ContainerNames::default(),
+            cgroup_root: cgroup::unified_root(),
+            cgroup_samples: HashMap::new(),
+            group_usage: Vec::new(),
+            visibility: Visibility {
+                show_kernel_threads: display.show_kernel_processes,
+                show_self: display.show_self,
+                self_pid: std::process::id(),
+            },
+        };
+        monitor.resolve_user_names();
+        monitor.read_proc_stats();
+        monitor.sample_groups();
+        monitor
     }
 
     pub fn update(&mut self) {
         self.system.refresh_processes(ProcessesToUpdate::All, true);
+        let now = Instant::now();
+        self.refresh_interval = now.duration_since(self.last_refresh).as_secs_f64();
+        self.last_refresh = now;
         if self.tree_view {
             self.build_process_tree();
         }
+        // Forget collapse state of processes that exited so a reused PID starts expanded
+        let system = &self.system;
+        self.collapsed
+            .retain(|pid| system.process(Pid::from_u32(*pid)).is_some());
+        self.expanded_threads
+            .retain(|pid, _| system.process(Pid::from_u32(*pid)).is_some());
+        self.cgroups.retain(|key, _| {
+            system
+                .process(Pid::from_u32(key.pid))
+                .is_some_and(|p| p.start_time() == key.start_time)
+        });
+        self.record_exits();
+        self.resolve_user_names();
+        self.read_proc_stats();
+        self.record_histories();
+        self.sample_groups();
+        for (pid, expanded) in &mut self.expanded_threads {
+            expanded.threads = expanded
+                .sampler
+                .sample(Path::new(&format!("/proc/{}", pid)));
+        }
+    }
+
+    /// Collect exits reported by proc events, and hand processes listed last time but
+    /// gone now to the exit tracker; last_seen is rebuilt by read_proc_stats
+    fn record_exits(&mut self) {
+        let system = &self.system;
+        let last_seen = &self.last_seen;
+        let visibility = self.visibility;
+        self.exits.update(
+            |pid| last_seen.contains_key(&pid) || system.process(Pid::from_u32(pid)).is_some(),
+            |exit| visibility.shows(exit.pid, exit.kernel_thread),
+        );
+
+        let now = SystemTime::now();
+        let epoch_secs = now.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        for (pid, seen) in self.last_seen.drain() {
+            let alive = self
+                .system
+                .process(Pid::from_u32(pid))
+                .is_some_and(|p| p.start_time() == seen.start_time);
+            if !alive {
+                self.exits.record_polled(ExitedProcess {
+                    pid,
+                    kernel_thread: seen.kernel_thread,
+                    name: seen.name,
+                    command: seen.command,
+                    cpu_time: Some(seen.cpu_time),
+                    lifetime: Some(epoch_secs.saturating_sub(seen.start_time) as f64),
+                    exited_at: now,
+                    status: None,
+                    short_lived: false,
+                });
+            }
+        }
+    }
+
+    /// Aggregate listed processes per container or unit; the group's own cgroup counters
+    /// are preferred over process sums since they also cover processes that came and went
+    fn sample_groups(&mut self) {
+        let ids = self
+            .cgroups
+            .values()
+            .filter_map(|path| cgroup::container_id(path));
+        self.containers
+            .resolve(ids.collect::<Vec<_>>().iter().map(String::as_str));
+
+        let interval = self.refresh_interval;
+        let mut groups: HashMap<String, GroupUsage> = HashMap::new();
+        for (pid, process) in self.system.processes() {
+            if !self.is_listed(pid, process) {
+                continue;
+            }
+            let key = ProcessKey {
+                pid: pid.as_u32(),
+                start_time: process.start_time(),
+            };
+            let Some(path) = self.cgroups.get(&key) else {
+                continue;
+            };
+            let path = cgroup::group_path(path);
+            let usage = groups
+                .entry(path.clone())
+                .or_insert_with(|| self.group_usage_for(path));
+            let disk = process.disk_usage();
+            usage.process_count += 1;
+            usage.cpu_usage += process.cpu_usage();
+            usage.memory += process.memory();
+            if interval > 0.0 {
+                usage.read_rate += disk.read_bytes as f64 / interval;
+                usage.write_rate += disk.written_bytes as f64 / interval;
+            }
+        }
+
+        let mut samples = HashMap::new();
+        if let Some(root) = &self.cgroup_root {
+            // The root group's counters describe the whole system, not its own processes
+            for usage in groups.values_mut().filter(|usage| usage.path != "/") {
+                let Some(stats) = cgroup::read_cgroup_stats(root, &usage.path) else {
+                    continue;
+                };
+                if let Some(memory) = stats.memory {
+                    usage.memory = memory;
+                }
+                usage.pressure = stats.pressure.map(|p| p.map(|p| p.some.avg10));
+                if let Some(previous) = self.cgroup_samples.get(&usage.path) {
+                    if interval > 0.0 {
+                        let delta = |now: u64, before: u64| now.saturating_sub(before) as f64;
+                        usage.cpu_usage =
+                            (delta(stats.cpu_usec, previous.cpu_usec) / 1e6 / interval * 100.0)
+                                as f32;
+                        usage.read_rate = delta(stats.read_bytes, previous.read_bytes) / interval;
+                        usage.write_rate =
+                            delta(stats.write_bytes, previous.write_bytes) / interval;
+                    }
+                }
+                samples.insert(usage.path.clone(), stats);
+            }
+        }
+        self.cgroup_samples = samples;
+
+        let mut usage: Vec<GroupUsage> = groups.into_values().collect();
+        usage.sort_by(|a, b| {
+            b.cpu_usage
+                .total_cmp(&a.cpu_usage)
+                .then_with(|| b.memory.cmp(&a.memory))
+        });
+        self.group_usage = usage;
+    }
+
+    fn group_usage_for(&self, path: String) -> GroupUsage {
+        let (label, kind) = if let Some(id) = cgroup::container_id(&path) {
+            let name = self.containers.name(&id).map(str::to_string);
+            (name.unwrap_or(id), "container")
+        } else if let Some(unit) = cgroup::systemd_unit(&path) {
+            let kind = cgroup::unit_type(&unit).unwrap_or("cgroup");
+            (unit, kind)
+        } else {
+            (path.clone(), "cgroup")
+        };
+        GroupUsage {
+            label,
+            kind,
+            path,
+            ..GroupUsage::default()
+        }
+    }
+
+    /// Usage per container and systemd unit, busiest first
+    pub fn group_summary(&self) -> &[GroupUsage] {
+        &self.group_usage
+    }
+
+    pub fn exits(&self) -> &ExitTracker {
+        &self.exits
+    }
+
+    /// Exits of processes the kernel-thread and self options would list, newest first
+    pub fn recent_exits(&self) -> impl Iterator<Item = &ExitedProcess> {
+        self.exits
+            .recent()
+            .filter(|exit| self.visibility.shows(exit.pid, exit.kernel_thread))
+    }
+
+    fn record_histories(&mut self) {
+        let interval = self.refresh_interval;
+        let mut histories = HashMap::with_capacity(self.histories.len());
+        for (pid, process) in self.system.processes() {
+            if is_userland_thread(process) {
+                continue;
+            }
+            let key = ProcessKey {
+                pid: pid.as_u32(),
+                start_time: process.start_time(),
+            };
+            let disk = process.disk_usage();
+            let io = if interval > 0.0 {
+                (disk.read_bytes + disk.written_bytes) as f64 / interval
+            } else {
+                0.0
+            };
+            // Rebuilding the map drops the histories of processes that exited
+            let mut history = self.histories.remove(&key).unwrap_or_default();
+            history.push(process.cpu_usage() as f64, process.memory() as f64, io);
+            histories.insert(key, history);
+        }
+        self.histories = histories;
+    }
+
+    pub fn history(&self, key: ProcessKey) -> Option<&ProcessHistory> {
+        self.histories.get(&key)
+    }
+
+    fn read_proc_stats(&mut self) {
+        let previous = std::mem::take(&mut self.proc_stats);
+        let interval = self.refresh_interval;
+        for (pid, process) in self.system.processes() {
+            if is_userland_thread(process) {
+                continue;
+            }
+            let status = fs::read_to_string(format!("/proc/{}/status", pid)).unwrap_or_default();
+            let fields = process_detail::parse_status(&status);
+            let stat = process_detail::read_stat(Path::new(&format!("/proc/{}/stat", pid)));
+            let start_ticks = stat.as_ref().map_or(0, |s| s.starttime);
+            let minor_faults = stat.as_ref().map_or(0, |s| s.minflt);
+            let major_faults = stat.as_ref().map_or(0, |s| s.majflt);
+            let fault_rate = |now: u64, before: fn(&ProcStats) -> u64| {
+                previous
+                    .get(&pid.as_u32())
+                    .filter(|p| p.start_ticks == start_ticks && interval > 0.0)
+                    .map_or(0.0, |p| now.saturating_sub(before(p)) as f64 / interval)
+            };
+            let stats = ProcStats {
+                threads: parse_thread_count(&status)
+                    .or_else(|| process.tasks().map(|tasks| tasks.len().max(1)))
+                    .unwrap_or(1),
+                nice: stat.as_ref().map_or(0, |s| s.nice as i32),
+                shared_memory: (process_detail::status_u64(&fields, "RssFile")
+                    + process_detail::status_u64(&fields, "RssShmem"))
+                    * 1024,
+                cpu_time: stat.map_or(0.0, |s| (s.utime + s.stime) as f64 / self.clock_ticks),
+                start_ticks,
+                minor_faults,
+                major_faults,
+                minor_fault_rate: fault_rate(minor_faults, |p| p.minor_faults),
+                major_fault_rate: fault_rate(major_faults, |p| p.major_faults),
+            };
+            if self.is_listed(pid, process) {
+                self.last_seen.insert(
+                    pid.as_u32(),
+                    SeenProcess {
+                        start_time: process.start_time(),
+                        kernel_thread: is_kernel_process(pid, process),
+                        name: process.name().to_string_lossy().to_string(),
+                        command: command_line(process),
+                        cpu_time: stats.cpu_time,
+                    },
+                );
+            }
+            self.proc_stats.insert(pid.as_u32(), stats);
+
+            let key = ProcessKey {
+                pid: pid.as_u32(),
+                start_time: process.start_time(),
+            };
+            self.cgroups
+                .entry(key)
+                .or_insert_with(|| cgroup::read_proc_cgroup(key.pid));
+        }
+    }
+
+    pub fn toggle_kernel_threads(&mut self) -> bool {
+        self.visibility.show_kernel_threads = !self.visibility.show_kernel_threads;
+        self.visibility.show_kernel_threads
+    }
+
+    pub fn toggle_self(&mut self) -> bool {
+        self.visibility.show_self = !self.visibility.show_self;
+        self.visibility.show_self
+    }
+
+    /// Whether a process is listed at all under the kernel-thread and self options;
+    /// userland thread entries are never listed as processes
+    fn is_listed(&self, pid: &Pid, process: &Process) -> bool {
+        !is_userland_thread(process)
+            && self
+                .visibility
+                .shows(pid.as_u32(), is_kernel_process(pid, process))
+    }
+
+    /// Show or hide the threads of `pid` below its row; returns whether they are now shown
+    pub fn toggle_threads(&mut self, pid: u32) -> bool {
+        if self.expanded_threads.remove(&pid).is_some() {
+            return false;
+        }
+        let mut sampler = ThreadSampler::new();
+        let threads = sampler.sample(Path::new(&format!("/proc/{}", pid)));
+        self.expanded_threads
+            .insert(pid, ExpandedThreads { sampler, threads });
+        true
+    }
+
+    /// Cache names for UIDs not seen before; the user list is only re-read on a miss
+    fn resolve_user_names(&mut self) {
+        let unknown: Vec<Uid> = self
+            .system
+            .processes()
+            .values()
+            .flat_map(|p| [p.user_id(), p.effective_user_id()])
+            .flatten()
+            .filter(|uid| !self.user_names.contains_key(&uid.to_string()))
+            .cloned()
+            .collect();
+        if unknown.is_empty() {
+            return;
+        }
+
+        // Accounts may have been created since startup
+        self.users.refresh_list();
+        for uid in unknown {
+            let name = self
+                .users
+                .get_user_by_id(&uid)
+                .map(|u| u.name().to_string())
+                .unwrap_or_else(|| uid.to_string());
+            self.user_names.insert(uid.to_string(), name);
+        }
+    }
+
+    fn user_name(&self, uid: Option<&Uid>) -> String {
+        match uid {
+            Some(uid) => {
+                let uid = uid.to_string();
+                self.user_names.get(&uid).cloned().unwrap_or(uid)
+            }
+            None => "unknown".to_string(),
+        }
+    }
+
+    /// CPU, memory and process count per user, heaviest CPU users first
+    pub fn user_summary(&self) -> Vec<UserUsage> {
+        aggregate_by_user(&self.get_sorted_processes())
+    }
+
+    /// Distinct real users owning at least one process, sorted by name
+    pub fn user_list(&self) -> Vec<String> {
+        let mut users: Vec<String> = self
+            .system
+            .processes()
+            .iter()
+            .filter(|(pid, p)| self.is_listed(pid, p))
+            .map(|(_, p)| self.user_name(p.user_id()))
+            .collect();
+        users.sort();
+        users.dedup();
+        users
+    }
+
+    pub fn sort_order(&self) -> SortOrder {
+        self.sort_order
+    }
+
+    pub fn is_reverse(&self) -> bool {
+        self.reverse
     }
 
     pub fn set_sort_order(&mut self, order: SortOrder) {
@@ -148,7 +769,6 @@ impl ProcessMonitor {
         }
     }
 
-    #[allow(dead_code)]
     pub fn toggle_tree_view(&mut self) {
         self.tree_view = !self.tree_view;
         if self.tree_view {
@@ -156,15 +776,35 @@ impl ProcessMonitor {
         }
     }
 
-    #[allow(dead_code)]
     pub fn is_tree_view(&self) -> bool {
         self.tree_view
     }
 
+    /// Whether the process identified by `key` is still in the last snapshot
+    pub fn is_alive(&self, key: ProcessKey) -> bool {
+        self.system
+            .process(Pid::from_u32(key.pid))
+            .is_some_and(|p| p.start_time() == key.start_time)
+    }
+
+    /// Fold the subtree below `pid` (tree view only)
+    pub fn collapse(&mut self, pid: u32) {
+        if self.process_tree.contains_key(&pid) {
+            self.collapsed.insert(pid);
+        }
+    }
+
+    pub fn expand(&mut self, pid: u32) {
+        self.collapsed.remove(&pid);
+    }
+
     fn build_process_tree(&mut self) {
         self.process_tree.clear();
 
         for (pid, process) in self.system.processes() {
+            if !self.is_listed(pid, process) {
+                continue;
+            }
             if let Some(parent) = process.parent() {
                 self.process_tree
                     .entry(parent.as_u32())
@@ -183,6 +823,7 @@ impl ProcessMonitor {
             .system
             .processes()
             .iter()
+            .filter(|(pid, process)| self.is_listed(pid, process))
             .map(|(pid, process)| {
                 let ppid = process.parent().map(|p| p.as_u32());
                 let children = if self.tree_view {
@@ -190,69 +831,175 @@ impl ProcessMonitor {
                 } else {
                     Vec::new()
                 };
-
-                // Get process state
-                let state = if process.status().to_string().contains("Run") {
-                    "R".to_string()
-                } else if process.status().to_string().contains("Sleep") {
-                    "S".to_string()
-                } else if process.status().to_string().contains("Zombie") {
-                    "Z".to_string()
-                } else {
-                    "?".to_string()
+                let stats = self
+                    .proc_stats
+                    .get(&pid.as_u32())
+                    .copied()
+                    .unwrap_or_default();
+                let name = process.name().to_string_lossy().to_string();
+                let command = command_line(process);
+                let cgroup = self
+                    .cgroups
+                    .get(&ProcessKey {
+                        pid: pid.as_u32(),
+                        start_time: process.start_time(),
+                    })
+                    .cloned()
+                    .unwrap_or_default();
+                let container = cgroup::container_id(&cgroup);
+                let rate = |bytes: u64| {
+                    if self.refresh_interval > 0.0 {
+                        bytes as f64 / self.refresh_interval
+                    } else {
+                        0.0
+                    }
                 };
 
                 ProcessInfo {
                     pid: pid.as_u32(),
                     ppid,
-                    name: process.name().to_string_lossy().to_string(),
+                    name,
                     cpu_usage: process.cpu_usage(),
                     memory: process.memory(),
-                    user: process
-                        .user_id()
-                        .map(|uid| uid.to_string())
-                        .unwrap_or_else(|| "unknown".to_string()),
-                    state,
-                    threads: 1, // sysinfo doesn't provide thread count directly
+                    user: self.user_name(process.user_id()),
+                    effective_user: process
+                        .effective_user_id()
+                        .filter(|euid| Some(*euid) != process.user_id())
+                        .map(|euid| self.user_name(Some(euid))),
+                    state: state_letter(process.status()).to_string(),
+                    threads: stats.threads.max(1),
+                    is_thread: false,
+                    nice: stats.nice,
+                    virtual_memory: process.virtual_memory(),
+                    shared_memory: stats.shared_memory,
                     read_bytes: process.disk_usage().read_bytes,
                     write_bytes: process.disk_usage().written_bytes,
+                    read_rate: rate(process.disk_usage().read_bytes),
+                    write_rate: rate(process.disk_usage().written_bytes),
+                    minor_fault_rate: stats.minor_fault_rate,
+                    major_fault_rate: stats.major_fault_rate,
+                    cpu_time: stats.cpu_time,
+                    command,
+                    unit: cgroup::systemd_unit(&cgroup),
+                    container_name: container
+                        .as_deref()
+                        .and_then(|id| self.containers.name(id))
+                        .map(str::to_string),
+                    container,
+                    cgroup,
+                    start_time: process.start_time(),
                     children,
                     tree_depth: 0,
+                    tree_prefix: String::new(),
+                    collapsed: false,
+                    hidden_descendants: 0,
+                    subtree_cpu: process.cpu_usage(),
+                    subtree_memory: process.memory(),
                 }
             })
             .collect();
 
         if self.tree_view {
-            return self.build_tree_list(processes);
+            processes = self.build_tree_list(processes);
+        } else {
+            self.sort_processes(&mut processes);
         }
 
-        match self.sort_order {
-            SortOrder::Pid => {
-                processes.sort_by_key(|p| p.pid);
-            }
-            SortOrder::Cpu => {
-                processes.sort_by(|a, b| {
-                    b.cpu_usage
-                        .partial_cmp(&a.cpu_usage)
-                        .unwrap_or(std::cmp::Ordering::Equal)
-                });
-            }
-            SortOrder::Memory => {
-                processes.sort_by(|a, b| b.memory.cmp(&a.memory));
-            }
-            SortOrder::Name => {
-                processes.sort_by(|a, b| a.name.cmp(&b.name));
-            }
-            SortOrder::User => {
-                processes.sort_by(|a, b| a.user.cmp(&b.user));
-            }
+        if self.expanded_threads.is_empty() {
+            return processes;
         }
+        self.insert_thread_rows(processes)
+    }
+
+    /// Put the threads of every expanded process right below it, busiest first
+    fn insert_thread_rows(&self, processes: Vec<ProcessInfo>) -> Vec<ProcessInfo> {
+        let mut result = Vec::with_capacity(processes.len());
+        for process in processes {
+            let Some(expanded) = self.expanded_threads.get(&process.pid) else {
+                result.push(process);
+                continue;
+            };
+
+            let mut threads: Vec<&ThreadInfo> = expanded.threads.iter().collect();
+            threads.sort_by(|a, b| {
+                b.cpu_percent
+                    .partial_cmp(&a.cpu_percent)
+                    .unwrap_or(Ordering::Equal)
+                    .then(a.tid.cmp(&b.tid))
+            });
+            let indent = " ".repeat(process.tree_prefix.chars().count());
+            let rows: Vec<ProcessInfo> = threads
+                .into_iter()
+                .map(|thread| ProcessInfo {
+                    pid: thread.tid,
+                    ppid: Some(process.pid),
+                    name: thread.name.clone(),
+                    cpu_usage: thread.cpu_percent as f32,
+                    user: process.user.clone(),
+                    effective_user: process.effective_user.clone(),
+                    state: thread.state.to_string(),
+                    is_thread: true,
+                    // sysinfo lists tasks too; its start time keeps the key checkable
+                    start_time: self
+                        .system
+                        .process(Pid::from_u32(thread.tid))
+                        .map_or(process.start_time, |p| p.start_time()),
+                    tree_depth: process.tree_depth + 1,
+                    tree_prefix: format!("{}↳ ", indent),
+                    ..ProcessInfo::default()
+                })
+                .collect();
+            result.push(process);
+            result.extend(rows);
+        }
+        result
+    }
+
+    fn compare(&self, a: &ProcessInfo, b: &ProcessInfo) -> Ordering {
+        let ordering = match self.sort_order {
+            SortOrder::Pid => a.pid.cmp(&b.pid),
+            SortOrder::Cpu => b
+                .cpu_usage
+                .partial_cmp(&a.cpu_usage)
+                .unwrap_or(Ordering::Equal),
+            SortOrder::Memory => b.memory.cmp(&a.memory),
+            SortOrder::Name => a.name.cmp(&b.name),
+            SortOrder::User => a.user.cmp(&b.user),
+            SortOrder::Ppid => a.ppid.cmp(&b.ppid),
+            SortOrder::State => a.state.cmp(&b.state),
+            SortOrder::Nice => a.nice.cmp(&b.nice),
+            SortOrder::Threads => b.threads.cmp(&a.threads),
+            SortOrder::Virtual => b.virtual_memory.cmp(&a.virtual_memory),
+            SortOrder::Shared => b.shared_memory.cmp(&a.shared_memory),
+            SortOrder::ReadRate => b.read_rate.total_cmp(&a.read_rate),
+            SortOrder::WriteRate => b.write_rate.total_cmp(&a.write_rate),
+            SortOrder::IoRate => {
+                (b.read_rate + b.write_rate).total_cmp(&(a.read_rate + a.write_rate))
+            }
+            SortOrder::MinorFaults => b.minor_fault_rate.total_cmp(&a.minor_fault_rate),
+            SortOrder::MajorFaults => b.major_fault_rate.total_cmp(&a.major_fault_rate),
+            // Newest first, like the other "most interesting on top" orders
+            SortOrder::StartTime => b.start_time.cmp(&a.start_time),
+            SortOrder::CpuTime => b.cpu_time.total_cmp(&a.cpu_time),
+            SortOrder::Command => a.command.cmp(&b.command),
+            SortOrder::Cgroup => a.cgroup.cmp(&b.cgroup),
+            SortOrder::Unit => a.unit.cmp(&b.unit),
+            SortOrder::Container => a
+                .container_name
+                .as_ref()
+                .or(a.container.as_ref())
+                .cmp(&b.container_name.as_ref().or(b.container.as_ref())),
+        };
 
         if self.reverse {
-            processes.reverse();
+            ordering.reverse()
+        } else {
+            ordering
         }
+    }
 
-        processes
+    fn sort_processes(&self, processes: &mut [ProcessInfo]) {
+        processes.sort_by(|a, b| self.compare(a, b));
     }
 
     fn build_tree_list(&self, mut processes: Vec<ProcessInfo>) -> Vec<ProcessInfo> {
@@ -260,51 +1007,160 @@ impl ProcessMonitor {
         let proc_map: HashMap<u32, ProcessInfo> = processes.drain(..).map(|p| (p.pid, p)).collect();
 
         // Find root processes (those without parents or with non-existent parents)
-        let root_pids: Vec<u32> = proc_map
+        let mut roots: Vec<&ProcessInfo> = proc_map
             .values()
-            .filter(|p| p.ppid.is_none() || !proc_map.contains_key(&p.ppid.unwrap()))
-            .map(|p| p.pid)
+            .filter(|p| p.ppid.is_none_or(|ppid| !proc_map.contains_key(&ppid)))
             .collect();
+        roots.sort_by(|a, b| self.compare(a, b));
+        let root_pids: Vec<u32> = roots.iter().map(|p| p.pid).collect();
 
         // Recursively build tree
         for pid in root_pids {
-            self.add_process_tree(&proc_map, pid, 0, &mut result);
+            self.add_process_tree(&proc_map, pid, 0, "", None, &mut result);
         }
 
         result
     }
 
+    /// Children present in `proc_map`, ordered by the active sort order
+    fn sorted_children<'a>(
+        &self,
+        proc_map: &'a HashMap<u32, ProcessInfo>,
+        pid: u32,
+    ) -> Vec<&'a ProcessInfo> {
+        let mut children: Vec<&ProcessInfo> = proc_map
+            .get(&pid)
+            .map(|p| {
+                p.children
+                    .iter()
+                    .filter_map(|child| proc_map.get(child))
+                    .collect()
+            })
+            .unwrap_or_default();
+        children.sort_by(|a, b| self.compare(a, b));
+        children
+    }
+
+    /// Sum CPU and memory over every descendant of `pid`, returning the descendant count too
+    fn subtree_totals(&self, proc_map: &HashMap<u32, ProcessInfo>, pid: u32) -> (f32, u64, usize) {
+        let mut totals = (0.0, 0, 0);
+        let mut stack = vec![pid];
+        while let Some(current) = stack.pop() {
+            for child in self.sorted_children(proc_map, current) {
+                totals.0 += child.cpu_usage;
+                totals.1 += child.memory;
+                totals.2 += 1;
+                stack.push(child.pid);
+            }
+        }
+        totals
+    }
+
+    /// `is_last` is `None` for roots, otherwise whether this node is its parent's last child
     fn add_process_tree(
         &self,
         proc_map: &HashMap<u32, ProcessInfo>,
         pid: u32,
         depth: usize,
+        parent_prefix: &str,
+        is_last: Option<bool>,
         result: &mut Vec<ProcessInfo>,
     ) {
-        if let Some(mut proc) = proc_map.get(&pid).cloned() {
-            proc.tree_depth = depth;
-            let children = proc.children.clone();
+        let Some(mut proc) = proc_map.get(&pid).cloned() else {
+            return;
+        };
+
+        let (connector, continuation) = match is_last {
+            None => ("", ""),
+            Some(true) => ("└─", "  "),
+            Some(false) => ("├─", "│ "),
+        };
+        proc.tree_depth = depth;
+        proc.tree_prefix = format!("{}{}", parent_prefix, connector);
+        let child_prefix = format!("{}{}", parent_prefix, continuation);
+
+        let children = self.sorted_children(proc_map, pid);
+        if !children.is_empty() && self.collapsed.contains(&pid) {
+            let (cpu, memory, count) = self.subtree_totals(proc_map, pid);
+            proc.collapsed = true;
+            proc.hidden_descendants = count;
+            proc.subtree_cpu += cpu;
+            proc.subtree_memory += memory;
             result.push(proc);
+            return;
+        }
 
-            for child_pid in children {
-                self.add_process_tree(proc_map, child_pid, depth + 1, result);
-            }
+        result.push(proc);
+
+        let last_index = children.len().saturating_sub(1);
+        for (i, child) in children.iter().enumerate() {
+            self.add_process_tree(
+                proc_map,
+                child.pid,
+                depth + 1,
+                &child_prefix,
+                Some(i == last_index),
+                result,
+            );
         }
     }
 
     /// Send a signal to a process
-    #[allow(dead_code)]
     pub fn send_signal(&self, pid: u32, signal: ProcessSignal) -> Result<bool, String> {
         let sysinfo_pid = Pid::from_u32(pid);
 
-        if let Some(process) = self.system.process(sysinfo_pid) {
-            match process.kill_with(signal.to_sysinfo_signal()) {
-                Some(true) => Ok(true),
-                Some(false) => Err("Failed to send signal".to_string()),
-                None => Err("Signal not supported on this platform".to_string()),
-            }
-        } else {
-            Err("Process not found".to_string())
+        let Some(process) = self.system.process(sysinfo_pid) else {
+            return Err("Process not found".to_string());
+        };
+
+        deliver_signal(process, signal)
+    }
+
+    /// Send a signal only if the PID still belongs to the process identified by `key`,
+    /// so a selection made before the process exited never hits whatever reused its PID
+    pub fn send_signal_checked(
+        &mut self,
+        key: ProcessKey,
+        signal: ProcessSignal,
+    ) -> Result<bool, String> {
+        self.verify_identity(key)?;
+        self.send_signal(key.pid, signal)
+    }
+
+    /// Shift the nice value of the process identified by `key` by `delta` (clamped to -20..=19)
+    /// and return the new value
+    pub fn renice(&mut self, key: ProcessKey, delta: i32) -> Result<i32, String> {
+        self.verify_identity(key)?;
+        let current = sched::get_nice(key.pid).ok_or("Cannot read nice value")?;
+        let nice = (current + delta).clamp(-20, 19);
+        sched::set_nice(key.pid, nice)?;
+        Ok(nice)
+    }
+
+    pub fn set_nice(&mut self, key: ProcessKey, nice: i32) -> Result<(), String> {
+        self.verify_identity(key)?;
+        sched::set_nice(key.pid, nice)
+    }
+
+    pub fn set_io_priority(&mut self, key: ProcessKey, priority: IoPriority) -> Result<(), String> {
+        self.verify_identity(key)?;
+        sched::set_io_priority(key.pid, priority)
+    }
+
+    pub fn set_affinity(&mut self, key: ProcessKey, cpus: &[usize]) -> Result<(), String> {
+        self.verify_identity(key)?;
+        sched::set_affinity(key.pid, cpus)
+    }
+
+    fn verify_identity(&mut self, key: ProcessKey) -> Result<(), String> {
+        let sysinfo_pid = Pid::from_u32(key.pid);
+        self.system
+            .refresh_processes(ProcessesToUpdate::Some(&[sysinfo_pid]), true);
+
+        match self.system.process(sysinfo_pid) {
+            Some(process) if process.start_time() == key.start_time => Ok(()),
+            Some(_) => Err("PID was reused by another process".to_string()),
+            None => Err("Process no longer exists".to_string()),
         }
     }
 
@@ -324,3 +1180,364 @@ impl ProcessMonitor {
         self.system.processes().len()
     }
 }
+
+/// Thread entries of a process; sysinfo lists them alongside processes on Linux
+fn is_userland_thread(process: &Process) -> bool {
+    process.thread_kind() == Some(ThreadKind::Userland)
+}
+
+/// Command line joined with spaces; the name in brackets when there is none
+fn command_line(process: &Process) -> String {
+    if process.cmd().is_empty() {
+        format!("[{}]", process.name().to_string_lossy())
+    } else {
+        process
+            .cmd()
+            .iter()
+            .map(|arg| arg.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+fn is_kernel_process(pid: &Pid, process: &Process) -> bool {
+    is_kernel_thread(
+        process.thread_kind(),
+        pid.as_u32(),
+        process.parent().map(|p| p.as_u32()),
+        process.cmd().is_empty(),
+        process.status() == ProcessStatus::Zombie,
+    )
+}
+
+/// Kernel threads carry PF_KTHREAD (reported by sysinfo as `ThreadKind::Kernel`); kthreadd
+/// (PID 2) and its children, or a live process without a command line, count as well
+pub fn is_kernel_thread(
+    kind: Option<ThreadKind>,
+    pid: u32,
+    ppid: Option<u32>,
+    empty_cmdline: bool,
+    zombie: bool,
+) -> bool {
+    kind == Some(ThreadKind::Kernel)
+        || pid == 2
+        || ppid == Some(2)
+        // Zombies have released their command line already
+        || (empty_cmdline && !zombie)
+}
+
+/// "Threads:" line of /proc/<pid>/status
+pub fn parse_thread_count(status: &str) -> Option<usize> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("Threads:"))
+        .and_then(|value| value.trim().parse().ok())
+}
+
+/// Letter used by ps and /proc/<pid>/stat for a process state
+fn state_letter(status: ProcessStatus) -> char {
+    match status {
+        ProcessStatus::Run => 'R',
+        ProcessStatus::Sleep => 'S',
+        ProcessStatus::UninterruptibleDiskSleep => 'D',
+        ProcessStatus::Zombie => 'Z',
+        ProcessStatus::Stop => 'T',
+        ProcessStatus::Tracing => 't',
+        ProcessStatus::Dead => 'X',
+        ProcessStatus::Idle => 'I',
+        ProcessStatus::Wakekill => 'K',
+        ProcessStatus::Waking => 'W',
+        ProcessStatus::Parked => 'P',
+        _ => '?',
+    }
+}
+
+pub fn aggregate_by_user(processes: &[ProcessInfo]) -> Vec<UserUsage> {
+    let mut by_user: HashMap<&str, UserUsage> = HashMap::new();
+    for process in processes.iter().filter(|p| !p.is_thread) {
+        let usage = by_user
+            .entry(process.user.as_str())
+            .or_insert_with(|| UserUsage {
+                user: process.user.clone(),
+                ..UserUsage::default()
+            });
+        usage.process_count += 1;
+        usage.cpu_usage += process.cpu_usage;
+        usage.memory += process.memory;
+    }
+
+    let mut summary: Vec<UserUsage> = by_user.into_values().collect();
+    summary.sort_by(|a, b| {
+        b.cpu_usage
+            .partial_cmp(&a.cpu_usage)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| b.memory.cmp(&a.memory))
+    });
+    summary
+}
+
+#[cfg(unix)]
+fn deliver_signal(process: &Process, signal: ProcessSignal) -> Result<bool, String> {
+    // kill(2) directly rather than sysinfo's kill_with, which hides errno
+    // SAFETY: kill takes plain integers; the pid comes from a listed process, never 0 or -1
+    let pid = process.pid().as_u32() as libc::pid_t;
+    if unsafe { libc::kill(pid, signal.to_libc_signal()) } == 0 {
+        return Ok(true);
+    }
+
+    Err(sched::os_error_message(&std::io::Error::last_os_error()))
+}
+
+#[cfg(not(unix))]
+fn deliver_signal(process: &Process, signal: ProcessSignal) -> Result<bool, String> {
+    match process.kill_with(signal.to_sysinfo_signal()) {
+        Some(true) => Ok(true),
+        Some(false) => Err("Failed to send signal".to_string()),
+        None => Err("Signal not supported on this platform".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(sort_order: SortOrder) -> ProcessMonitor {
+        ProcessMonitor {
+            system: System::new(),
+            sort_order,
+            reverse: false,
+            tree_view: true,
+            process_tree: HashMap::new(),
+            collapsed: HashSet::new(),
+            users: Users::new(),
+            user_names: HashMap::new(),
+            proc_stats: HashMap::new(),
+            cgroups: HashMap::new(),
+            last_refresh: Instant::now(),
+            refresh_interval: 0.0,
+            clock_ticks: 100.0,
+            expanded_threads: HashMap::new(),
+            histories: HashMap::new(),
+            last_seen: HashMap::new(),
+            exits: ExitTracker::new(Duration::from_secs(30), false),
+            containers: cgroup::ContainerNames::default(),
+            cgroup_root: None,
+            cgroup_samples: HashMap::new(),
+            group_usage: Vec::new(),
+            visibility: Visibility {
+                show_kernel_threads: true,
+                show_self: true,
+                self_pid: std::process::id(),
+            },
+        }
+    }
+
+    fn proc(pid: u32, ppid: Option<u32>, children: &[u32], cpu: f32) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            ppid,
+            name: format!("p{}", pid),
+            cpu_usage: cpu,
+            memory: 100,
+            children: children.to_vec(),
+            subtree_cpu: cpu,
+            subtree_memory: 100,
+            ..ProcessInfo::default()
+        }
+    }
+
+    fn sample() -> Vec<ProcessInfo> {
+        vec![
+            proc(1, None, &[10, 20], 1.0),
+            proc(10, Some(1), &[11], 5.0),
+            proc(11, Some(10), &[], 2.0),
+            proc(20, Some(1), &[], 9.0),
+        ]
+    }
+
+    #[test]
+    fn test_aggregate_by_user() {
+        let mut processes = sample();
+        processes[0].user = "root".to_string();
+        processes[1].user = "alice".to_string();
+        processes[2].user = "alice".to_string();
+        processes[3].user = "bob".to_string();
+
+        let summary = aggregate_by_user(&processes);
+        let users: Vec<&str> = summary.iter().map(|u| u.user.as_str()).collect();
+        assert_eq!(users, vec!["bob", "alice", "root"]);
+        assert_eq!(summary[1].process_count, 2);
+        assert_eq!(summary[1].cpu_usage, 7.0);
+        assert_eq!(summary[1].memory, 200);
+    }
+
+    #[test]
+    fn test_tree_guides_and_sibling_order() {
+        let tree = monitor(SortOrder::Cpu).build_tree_list(sample());
+        let rows: Vec<(u32, &str)> = tree
+            .iter()
+            .map(|p| (p.pid, p.tree_prefix.as_str()))
+            .collect();
+        // Siblings follow the sort order (CPU descending): 20 before 10
+        assert_eq!(rows, vec![(1, ""), (20, "├─"), (10, "└─"), (11, "  └─")]);
+        assert_eq!(tree[3].tree_depth, 2);
+    }
+
+    #[test]
+    fn test_collapsed_subtree_aggregates() {
+        let mut monitor = monitor(SortOrder::Pid);
+        monitor.collapsed.insert(1);
+        let tree = monitor.build_tree_list(sample());
+        assert_eq!(tree.len(), 1);
+        assert!(tree[0].collapsed);
+        assert_eq!(tree[0].hidden_descendants, 3);
+        assert_eq!(tree[0].subtree_cpu, 17.0);
+        assert_eq!(tree[0].subtree_memory, 400);
+    }
+
+    #[test]
+    fn test_process_history_is_bounded() {
+        let mut history = ProcessHistory::default();
+        for i in 0..HISTORY_SIZE + 5 {
+            history.push(i as f64, 0.0, 0.0);
+        }
+        assert_eq!(history.cpu.len(), HISTORY_SIZE);
+        assert_eq!(history.cpu.front(), Some(&5.0));
+        assert_eq!(history.io.len(), HISTORY_SIZE);
+    }
+
+    #[test]
+    fn test_is_kernel_thread() {
+        assert!(is_kernel_thread(
+            Some(ThreadKind::Kernel),
+            57,
+            Some(2),
+            true,
+            false
+        ));
+        assert!(is_kernel_thread(None, 2, Some(0), true, false));
+        // Without the flag, kthreadd's children are still recognised
+        assert!(is_kernel_thread(None, 812, Some(2), true, false));
+        assert!(!is_kernel_thread(None, 1, None, false, false));
+        assert!(!is_kernel_thread(None, 3000, Some(1), true, true));
+        assert!(is_kernel_thread(None, 3000, Some(1), true, false));
+    }
+
+    #[test]
+    fn test_parse_thread_count() {
+        let status = "Name:\tjava\nState:\tS (sleeping)\nThreads:\t42\nSigQ:\t0/63304\n";
+        assert_eq!(parse_thread_count(status), Some(42));
+        assert_eq!(parse_thread_count("Name:\tinit\n"), None);
+    }
+
+    #[test]
+    fn test_thread_rows_follow_owner_busiest_first() {
+        let mut monitor = monitor(SortOrder::Pid);
+        let thread = |tid: u32, cpu_percent: f64| ThreadInfo {
+            tid,
+            name: format!("worker-{}", tid),
+            state: 'R',
+            cpu_percent,
+        };
+        monitor.expanded_threads.insert(
+            10,
+            ExpandedThreads {
+                sampler: ThreadSampler::new(),
+                threads: vec![thread(10, 1.0), thread(12, 80.0), thread(13, 5.0)],
+            },
+        );
+
+        let rows = monitor.insert_thread_rows(monitor.build_tree_list(sample()));
+        let pids: Vec<u32> = rows.iter().map(|p| p.pid).collect();
+        assert_eq!(pids, vec![1, 10, 12, 13, 10, 11, 20]);
+        assert!(rows[2].is_thread);
+        assert_eq!(rows[2].ppid, Some(10));
+        assert_eq!(rows[2].tree_prefix, "  ↳ ");
+
+        // Threads never count as extra processes of their user
+        assert_eq!(aggregate_by_user(&rows)[0].process_count, 4);
+    }
+
+    #[test]
+    fn test_hidden_kernel_thread_exits_are_not_listed() {
+        let mut monitor = monitor(SortOrder::Cpu);
+        monitor.visibility.show_kernel_threads = false;
+        for (pid, kernel_thread) in [(900, true), (901, false)] {
+            monitor.exits.record_polled(ExitedProcess {
+                pid,
+                kernel_thread,
+                name: format!("p{}", pid),
+                command: String::new(),
+                cpu_time: None,
+                lifetime: None,
+                exited_at: SystemTime::now(),
+                status: None,
+                short_lived: false,
+            });
+        }
+        let pids = |m: &ProcessMonitor| m.recent_exits().map(|e| e.pid).collect::<Vec<_>>();
+        assert_eq!(pids(&monitor), vec![901]);
+        monitor.toggle_kernel_threads();
+        assert_eq!(pids(&monitor), vec![901, 900]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hiding_self_keeps_children() {
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        let pid = child.id();
+        let display = DisplayConfig {
+            show_self: false,
+            ..DisplayConfig::default()
+        };
+        let mut monitor = ProcessMonitor::new(&display, &ProcessConfig::default());
+        assert!(monitor.system.process(Pid::from_u32(pid)).is_some());
+        // Only rtop's own process is hidden, not the processes it started
+        assert!(!monitor.last_seen.contains_key(&std::process::id()));
+        assert!(monitor.last_seen.contains_key(&pid));
+
+        child.kill().unwrap();
+        child.wait().unwrap();
+        // Give the proc events thread, when running as root, time to report the exit
+        std::thread::sleep(Duration::from_millis(100));
+        monitor.update();
+        assert!(monitor.recent_exits().any(|e| e.pid == pid));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_send_signal_checked_guards_pid_reuse() {
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        let pid = child.id();
+        let mut monitor = ProcessMonitor::new(&DisplayConfig::default(), &ProcessConfig::default());
+        let start_time = monitor
+            .system
+            .process(Pid::from_u32(pid))
+            .unwrap()
+            .start_time();
+
+        let key = ProcessKey { pid, start_time };
+        let stale = ProcessKey {
+            start_time: start_time + 1,
+            ..key
+        };
+
+        // A stale start time means the PID now belongs to someone else
+        assert!(monitor
+            .send_signal_checked(stale, ProcessSignal::Kill)
+            .is_err());
+        assert!(monitor.renice(stale, 1).is_err());
+        assert!(monitor.renice(key, 1).is_ok());
+        assert_eq!(
+            monitor.send_signal_checked(key, ProcessSignal::Kill),
+            Ok(true)
+        );
+        child.wait().unwrap();
+    }
+}
@@ -0,0 +1,564 @@
+/// Detailed view of a single process, read from /proc/<pid> (Linux)
+use super::process::ProcessKey;
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+const HISTORY_SIZE: usize = 61;
+
+/// Fields of /proc/<pid>/stat (and task/<tid>/stat) used by the detail view
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProcStat {
+    pub comm: String,
+    pub state: char,
+    pub ppid: u32,
+    pub utime: u64,
+    pub stime: u64,
+    /// Page faults served from memory and ones that had to read from disk
+    pub minflt: u64,
+    pub majflt: u64,
+    pub priority: i64,
+    pub nice: i64,
+    pub num_threads: u64,
+    /// Clock ticks after boot
+    pub starttime: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ThreadInfo {
+    pub tid: u32,
+    pub name: String,
+    pub state: char,
+    pub cpu_percent: f64,
+}
+
+/// One row of /proc/<pid>/limits
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResourceLimit {
+    pub name: String,
+    pub soft: String,
+    pub hard: String,
+    pub units: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ProcessDetail {
+    pub pid: u32,
+    pub name: String,
+    pub state: char,
+    pub ppid: u32,
+    pub cmdline: Vec<String>,
+    pub cwd: Option<String>,
+    pub exe: Option<String>,
+    pub environ: Vec<String>,
+    pub threads: Vec<ThreadInfo>,
+    pub fd_count: Option<usize>,
+    pub limits: Vec<ResourceLimit>,
+    pub cgroups: Vec<String>,
+    pub namespaces: Vec<String>,
+    /// Seconds since the epoch
+    pub start_time: Option<u64>,
+    pub nice: i64,
+    pub priority: i64,
+    pub voluntary_ctxt_switches: u64,
+    pub nonvoluntary_ctxt_switches: u64,
+    pub cpu_percent: f64,
+    pub rss: u64,
+    pub read_rate: f64,  // bytes/sec
+    pub write_rate: f64, // bytes/sec
+    pub read_total: u64,
+    pub write_total: u64,
+}
+
+/// Counters kept between refreshes to turn cumulative values into rates
+struct Sample {
+    at: Instant,
+    cpu_ticks: u64,
+    read_bytes: u64,
+    write_bytes: u64,
+}
+
+/// Per-thread CPU usage from /proc/<pid>/task/*/stat, derived from tick deltas between samples
+pub struct ThreadSampler {
+    last_at: Option<Instant>,
+    ticks: HashMap<u32, u64>,
+    clock_ticks: f64,
+}
+
+impl ThreadSampler {
+    pub fn new() -> Self {
+        Self {
+            last_at: None,
+            ticks: HashMap::new(),
+            clock_ticks: clock_ticks_per_second(),
+        }
+    }
+
+    /// Threads of the process at `proc_path`, sorted by TID; CPU is 0 on the first sample
+    pub fn sample(&mut self, proc_path: &Path) -> Vec<ThreadInfo> {
+        let now = Instant::now();
+        let elapsed = self
+            .last_at
+            .map(|at| now.duration_since(at).as_secs_f64())
+            .unwrap_or(0.0);
+
+        let mut ticks = HashMap::new();
+        let mut threads = Vec::new();
+        if let Ok(entries) = fs::read_dir(proc_path.join("task")) {
+            for entry in entries.flatten() {
+                let Ok(tid) = entry.file_name().to_string_lossy().parse::<u32>() else {
+                    continue;
+                };
+                let Some(task) = read_stat(&entry.path().join("stat")) else {
+                    continue;
+                };
+                let total = task.utime + task.stime;
+                let cpu_percent = match self.ticks.get(&tid) {
+                    Some(&previous) if elapsed > 0.0 => {
+                        total.saturating_sub(previous) as f64 / elapsed / self.clock_ticks * 100.0
+                    }
+                    _ => 0.0,
+                };
+                threads.push(ThreadInfo {
+                    tid,
+                    name: task.comm,
+                    state: task.state,
+                    cpu_percent,
+                });
+                ticks.insert(tid, total);
+            }
+        }
+        threads.sort_by_key(|t| t.tid);
+
+        self.ticks = ticks;
+        self.last_at = Some(now);
+        threads
+    }
+}
+
+impl Default for ThreadSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tracks one process while its detail page is open
+pub struct ProcessDetailMonitor {
+    pid: u32,
+    proc_path: PathBuf,
+    starttime: Option<u64>,
+    detail: Option<ProcessDetail>,
+    last_sample: Option<Sample>,
+    thread_sampler: ThreadSampler,
+    cpu_history: VecDeque<f64>,
+    rss_history: VecDeque<u64>,
+    clock_ticks: f64,
+    boot_time: Option<u64>,
+}
+
+impl ProcessDetailMonitor {
+    /// Start tracking the process `key` names, refusing a PID that now belongs to another
+    /// process
+    pub fn open(key: ProcessKey) -> Result<Self, String> {
+        let monitor = Self::new(key.pid);
+        match monitor.detail().map(|detail| detail.start_time) {
+            None => Err("Process no longer exists".to_string()),
+            Some(Some(start_time)) if start_time != key.start_time => {
+                Err("PID was reused by another process".to_string())
+            }
+            Some(_) => Ok(monitor),
+        }
+    }
+
+    fn new(pid: u32) -> Self {
+        let proc_path = Path::new("/proc").join(pid.to_string());
+        let starttime = read_stat(&proc_path.join("stat")).map(|s| s.starttime);
+
+        let mut monitor = Self {
+            pid,
+            proc_path,
+            starttime,
+            detail: None,
+            last_sample: None,
+            thread_sampler: ThreadSampler::new(),
+            cpu_history: VecDeque::from(vec![0.0; HISTORY_SIZE]),
+            rss_history: VecDeque::from(vec![0; HISTORY_SIZE]),
+            clock_ticks: clock_ticks_per_second(),
+            boot_time: fs::read_to_string("/proc/stat")
+                .ok()
+                .and_then(|s| parse_boot_time(&s)),
+        };
+        monitor.update();
+        monitor
+    }
+
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    pub fn update(&mut self) {
+        let Some(stat) = read_stat(&self.proc_path.join("stat")) else {
+            self.detail = None;
+            return;
+        };
+        // A different start time means the PID was reused; stop tracking
+        if Some(stat.starttime) != self.starttime {
+            self.detail = None;
+            return;
+        }
+
+        let now = Instant::now();
+        let status = fs::read_to_string(self.proc_path.join("status"))
+            .map(|s| parse_status(&s))
+            .unwrap_or_default();
+        let io = fs::read_to_string(self.proc_path.join("io"))
+            .map(|s| parse_status(&s))
+            .unwrap_or_default();
+        let read_bytes = status_u64(&io, "read_bytes");
+        let write_bytes = status_u64(&io, "write_bytes");
+        let cpu_ticks = stat.utime + stat.stime;
+
+        let elapsed = self
+            .last_sample
+            .as_ref()
+            .map(|s| now.duration_since(s.at).as_secs_f64())
+            .unwrap_or(0.0);
+        let rate = |current: u64, previous: Option<u64>| match previous {
+            Some(prev) if elapsed > 0.0 => current.saturating_sub(prev) as f64 / elapsed,
+            _ => 0.0,
+        };
+        let cpu_percent = rate(cpu_ticks, self.last_sample.as_ref().map(|s| s.cpu_ticks))
+            / self.clock_ticks
+            * 100.0;
+
+        let threads = self.thread_sampler.sample(&self.proc_path);
+
+        let previous_io = self
+            .last_sample
+            .as_ref()
+            .map(|s| (s.read_bytes, s.write_bytes));
+        let rss = status_u64(&status, "VmRSS") * 1024;
+
+        let detail = ProcessDetail {
+            pid: self.pid,
+            name: stat.comm,
+            state: stat.state,
+            ppid: stat.ppid,
+            cmdline: fs::read(self.proc_path.join("cmdline"))
+                .map(|b| split_nul(&b))
+                .unwrap_or_default(),
+            cwd: read_link(&self.proc_path.join("cwd")),
+            exe: read_link(&self.proc_path.join("exe")),
+            environ: fs::read(self.proc_path.join("environ"))
+                .map(|b| split_nul(&b))
+                .unwrap_or_default(),
+            threads,
+            fd_count: fs::read_dir(self.proc_path.join("fd"))
+                .ok()
+                .map(|entries| entries.count()),
+            limits: fs::read_to_string(self.proc_path.join("limits"))
+                .map(|s| parse_limits(&s))
+                .unwrap_or_default(),
+            cgroups: fs::read_to_string(self.proc_path.join("cgroup"))
+                .map(|s| s.lines().map(str::to_string).collect())
+                .unwrap_or_default(),
+            namespaces: read_namespaces(&self.proc_path.join("ns")),
+            start_time: self
+                .boot_time
+                .map(|boot| boot + (stat.starttime as f64 / self.clock_ticks) as u64),
+            nice: stat.nice,
+            priority: stat.priority,
+            voluntary_ctxt_switches: status_u64(&status, "voluntary_ctxt_switches"),
+            nonvoluntary_ctxt_switches: status_u64(&status, "nonvoluntary_ctxt_switches"),
+            cpu_percent,
+            rss,
+            read_rate: rate(read_bytes, previous_io.map(|(r, _)| r)),
+            write_rate: rate(write_bytes, previous_io.map(|(_, w)| w)),
+            read_total: read_bytes,
+            write_total: write_bytes,
+        };
+
+        self.cpu_history.pop_front();
+        self.cpu_history.push_back(cpu_percent);
+        self.rss_history.pop_front();
+        self.rss_history.push_back(rss);
+
+        self.last_sample = Some(Sample {
+            at: now,
+            cpu_ticks,
+            read_bytes,
+            write_bytes,
+        });
+        self.detail = Some(detail);
+    }
+
+    /// Latest snapshot; `None` once the process has exited
+    pub fn detail(&self) -> Option<&ProcessDetail> {
+        self.detail.as_ref()
+    }
+
+    /// CPU usage history (percent of one core)
+    pub fn cpu_history(&self) -> Vec<f64> {
+        self.cpu_history.iter().copied().collect()
+    }
+
+    /// Resident set size history in bytes
+    pub fn rss_history(&self) -> Vec<u64> {
+        self.rss_history.iter().copied().collect()
+    }
+}
+
+/// Human-readable name of a /proc state letter
+pub fn state_name(state: char) -> &'static str {
+    match state {
+        'R' => "running",
+        'S' => "sleeping",
+        'D' => "disk sleep",
+        'Z' => "zombie",
+        'T' => "stopped",
+        't' => "tracing stop",
+        'X' | 'x' => "dead",
+        'I' => "idle",
+        'K' => "wakekill",
+        'W' => "waking",
+        'P' => "parked",
+        _ => "unknown",
+    }
+}
+
+pub fn read_stat(path: &Path) -> Option<ProcStat> {
+    parse_stat(&fs::read_to_string(path).ok()?)
+}
+
+/// Parse /proc/<pid>/stat; the command name may itself contain spaces and parentheses
+pub fn parse_stat(contents: &str) -> Option<ProcStat> {
+    let open = contents.find('(')?;
+    let close = contents.rfind(')')?;
+    let comm = contents.get(open + 1..close)?.to_string();
+    let fields: Vec<&str> = contents.get(close + 1..)?.split_whitespace().collect();
+    if fields.len() < 20 {
+        return None;
+    }
+
+    // Index 0 here is field 3 (state) in proc(5) numbering
+    let num = |i: usize| fields[i].parse::<u64>().unwrap_or(0);
+    let signed = |i: usize| fields[i].parse::<i64>().unwrap_or(0);
+
+    Some(ProcStat {
+        comm,
+        state: fields[0].chars().next().unwrap_or('?'),
+        ppid: num(1) as u32,
+        utime: num(11),
+        stime: num(12),
+        minflt: num(7),
+        majflt: num(9),
+        priority: signed(15),
+        nice: signed(16),
+        num_threads: num(17),
+        starttime: num(19),
+    })
+}
+
+/// Parse "Key: value" files such as /proc/<pid>/status and /proc/<pid>/io
+pub fn parse_status(contents: &str) -> HashMap<String, String> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            Some((key.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+/// First number of a status value ("1234 kB" -> 1234)
+pub fn status_u64(map: &HashMap<String, String>, key: &str) -> u64 {
+    map.get(key)
+        .and_then(|v| v.split_whitespace().next())
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Parse /proc/<pid>/limits, whose columns are fixed-width and whose names contain spaces
+pub fn parse_limits(contents: &str) -> Vec<ResourceLimit> {
+    let mut lines = contents.lines();
+    let Some(header) = lines.next() else {
+        return Vec::new();
+    };
+    let (Some(soft_col), Some(hard_col), Some(units_col)) = (
+        header.find("Soft Limit"),
+        header.find("Hard Limit"),
+        header.find("Units"),
+    ) else {
+        return Vec::new();
+    };
+
+    let column = |line: &str, start: usize, end: usize| {
+        line.get(start..end.min(line.len()))
+            .unwrap_or("")
+            .trim()
+            .to_string()
+    };
+
+    lines
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| ResourceLimit {
+            name: column(line, 0, soft_col),
+            soft: column(line, soft_col, hard_col),
+            hard: column(line, hard_col, units_col),
+            units: column(line, units_col, line.len()),
+        })
+        .collect()
+}
+
+/// Boot time (seconds since the epoch) from the "btime" line of /proc/stat
+pub fn parse_boot_time(contents: &str) -> Option<u64> {
+    contents
+        .lines()
+        .find_map(|line| line.strip_prefix("btime "))
+        .and_then(|v| v.trim().parse().ok())
+}
+
+fn split_nul(bytes: &[u8]) -> Vec<String> {
+    bytes
+        .split(|&b| b == 0)
+        .filter(|part| !part.is_empty())
+        .map(|part| String::from_utf8_lossy(part).to_string())
+        .collect()
+}
+
+fn read_link(path: &Path) -> Option<String> {
+    fs::read_link(path)
+        .ok()
+        .map(|p| p.to_string_lossy().to_string())
+}
+
+/// Namespace links such as "net:[4026531840]", sorted by type
+fn read_namespaces(path: &Path) -> Vec<String> {
+    let mut namespaces: Vec<String> = fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| read_link(&entry.path()))
+                .collect()
+        })
+        .unwrap_or_default();
+    namespaces.sort();
+    namespaces
+}
+
+#[cfg(unix)]
+pub fn clock_ticks_per_second() -> f64 {
+    // SAFETY: sysconf has no preconditions
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if ticks > 0 {
+        ticks as f64
+    } else {
+        100.0
+    }
+}
+
+#[cfg(not(unix))]
+pub fn clock_ticks_per_second() -> f64 {
+    100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stat_with_parens_in_name() {
+        let line = "1234 (tmux: server (1)) S 1 1234 1234 0 -1 4194560 100 0 7 0 \
+                    250 50 0 0 20 0 3 0 98765 1000000 500 18446744073709551615";
+        let stat = parse_stat(line).unwrap();
+        assert_eq!(stat.comm, "tmux: server (1)");
+        assert_eq!(stat.state, 'S');
+        assert_eq!(stat.ppid, 1);
+        assert_eq!(stat.utime, 250);
+        assert_eq!(stat.stime, 50);
+        assert_eq!(stat.minflt, 100);
+        assert_eq!(stat.majflt, 7);
+        assert_eq!(stat.priority, 20);
+        assert_eq!(stat.nice, 0);
+        assert_eq!(stat.num_threads, 3);
+        assert_eq!(stat.starttime, 98765);
+        assert!(parse_stat("1234 (truncated) S 1").is_none());
+    }
+
+    #[test]
+    fn test_parse_status() {
+        let status =
+            parse_status("Name:\tbash\nVmRSS:\t    4096 kB\nvoluntary_ctxt_switches:\t42\n");
+        assert_eq!(status.get("Name").map(String::as_str), Some("bash"));
+        assert_eq!(status_u64(&status, "VmRSS"), 4096);
+        assert_eq!(status_u64(&status, "voluntary_ctxt_switches"), 42);
+        assert_eq!(status_u64(&status, "missing"), 0);
+    }
+
+    #[test]
+    fn test_parse_limits() {
+        let contents = "\
+Limit                     Soft Limit           Hard Limit           Units
+Max cpu time              unlimited            unlimited            seconds
+Max open files            1024                 524288               files
+";
+        let limits = parse_limits(contents);
+        assert_eq!(limits.len(), 2);
+        assert_eq!(
+            limits[1],
+            ResourceLimit {
+                name: "Max open files".to_string(),
+                soft: "1024".to_string(),
+                hard: "524288".to_string(),
+                units: "files".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_boot_time() {
+        assert_eq!(
+            parse_boot_time("cpu  1 2 3\nbtime 1700000000\nprocesses 5\n"),
+            Some(1700000000)
+        );
+        assert_eq!(parse_boot_time("cpu  1 2 3\n"), None);
+    }
+
+    #[test]
+    fn test_split_nul() {
+        assert_eq!(
+            split_nul(b"/usr/bin/rtop\0--minimal\0"),
+            vec!["/usr/bin/rtop", "--minimal"]
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_monitor_reads_own_process() {
+        let monitor = ProcessDetailMonitor::new(std::process::id());
+        let detail = monitor.detail().unwrap();
+        assert!(!detail.threads.is_empty());
+        assert!(detail.rss > 0);
+        assert!(!detail.cmdline.is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_open_checks_start_time() {
+        let pid = std::process::id();
+        let start_time = ProcessDetailMonitor::new(pid)
+            .detail()
+            .and_then(|d| d.start_time)
+            .unwrap();
+        assert!(ProcessDetailMonitor::open(ProcessKey { pid, start_time }).is_ok());
+        let reused = ProcessKey {
+            pid,
+            start_time: start_time + 1,
+        };
+        assert_eq!(
+            ProcessDetailMonitor::open(reused).err().as_deref(),
+            Some("PID was reused by another process")
+        );
+    }
+}
@@ -0,0 +1,289 @@
+/// Scheduling controls for other processes: nice value, I/O priority and CPU affinity
+use std::io;
+
+/// I/O scheduling class, as understood by ioprio_set(2)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IoClass {
+    None,
+    Realtime,
+    BestEffort,
+    Idle,
+}
+
+impl IoClass {
+    pub fn next(self) -> Self {
+        match self {
+            IoClass::None => IoClass::BestEffort,
+            IoClass::BestEffort => IoClass::Idle,
+            IoClass::Idle => IoClass::Realtime,
+            IoClass::Realtime => IoClass::None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            IoClass::None => "none",
+            IoClass::Realtime => "realtime",
+            IoClass::BestEffort => "best-effort",
+            IoClass::Idle => "idle",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IoPriority {
+    pub class: IoClass,
+    /// 0 (highest) to 7 (lowest); ignored for the idle class
+    pub level: u8,
+}
+
+const IOPRIO_CLASS_SHIFT: i32 = 13;
+const IOPRIO_PRIO_MASK: i32 = (1 << IOPRIO_CLASS_SHIFT) - 1;
+
+impl IoPriority {
+    pub fn from_raw(raw: i32) -> Self {
+        let class = match raw >> IOPRIO_CLASS_SHIFT {
+            1 => IoClass::Realtime,
+            2 => IoClass::BestEffort,
+            3 => IoClass::Idle,
+            _ => IoClass::None,
+        };
+        Self {
+            class,
+            level: (raw & IOPRIO_PRIO_MASK).min(7) as u8,
+        }
+    }
+
+    pub fn to_raw(self) -> i32 {
+        let class = match self.class {
+            IoClass::None => 0,
+            IoClass::Realtime => 1,
+            IoClass::BestEffort => 2,
+            IoClass::Idle => 3,
+        };
+        (class << IOPRIO_CLASS_SHIFT) | self.level.min(7) as i32
+    }
+}
+
+impl Default for IoPriority {
+    fn default() -> Self {
+        Self {
+            class: IoClass::BestEffort,
+            level: 4,
+        }
+    }
+}
+
+/// Turn an OS error into the message shown in the status line
+pub fn os_error_message(err: &io::Error) -> String {
+    #[cfg(unix)]
+    match err.raw_os_error() {
+        Some(libc::EPERM) | Some(libc::EACCES) => return "Permission denied".to_string(),
+        Some(libc::ESRCH) => return "Process not found".to_string(),
+        _ => {}
+    }
+    err.to_string()
+}
+
+/// Location of the calling thread's errno
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn errno_location() -> *mut libc::c_int {
+    // SAFETY: returns a pointer to thread-local storage, valid for the thread's lifetime
+    unsafe { libc::__errno_location() }
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+fn errno_location() -> *mut libc::c_int {
+    // SAFETY: returns a pointer to thread-local storage, valid for the thread's lifetime
+    unsafe { libc::__error() }
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd"
+))]
+pub fn get_nice(pid: u32) -> Option<i32> {
+    // getpriority returns -1 both for nice -1 and on error, so errno tells them apart
+    // SAFETY: errno_location points to this thread's errno; getpriority takes integers
+    unsafe {
+        *errno_location() = 0;
+        let nice = libc::getpriority(libc::PRIO_PROCESS, pid as libc::id_t);
+        (nice != -1 || *errno_location() == 0).then_some(nice)
+    }
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd"
+)))]
+pub fn get_nice(_pid: u32) -> Option<i32> {
+    None
+}
+
+#[cfg(unix)]
+pub fn set_nice(pid: u32, nice: i32) -> Result<(), String> {
+    let nice = nice.clamp(-20, 19);
+    // SAFETY: plain integer syscall, no pointers involved
+    if unsafe { libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, nice) } == 0 {
+        Ok(())
+    } else {
+        Err(os_error_message(&io::Error::last_os_error()))
+    }
+}
+
+#[cfg(not(unix))]
+pub fn set_nice(_pid: u32, _nice: i32) -> Result<(), String> {
+    Err("Renice not supported on this platform".to_string())
+}
+
+#[cfg(target_os = "linux")]
+const IOPRIO_WHO_PROCESS: libc::c_long = 1;
+
+#[cfg(target_os = "linux")]
+pub fn get_io_priority(pid: u32) -> Option<IoPriority> {
+    // SAFETY: ioprio_get takes two integers and returns an integer
+    let raw = unsafe {
+        libc::syscall(
+            libc::SYS_ioprio_get,
+            IOPRIO_WHO_PROCESS,
+            pid as libc::c_long,
+        )
+    };
+    (raw >= 0).then(|| IoPriority::from_raw(raw as i32))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_io_priority(_pid: u32) -> Option<IoPriority> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+pub fn set_io_priority(pid: u32, priority: IoPriority) -> Result<(), String> {
+    // SAFETY: ioprio_set takes three integers
+    let result = unsafe {
+        libc::syscall(
+            libc::SYS_ioprio_set,
+            IOPRIO_WHO_PROCESS,
+            pid as libc::c_long,
+            priority.to_raw() as libc::c_long,
+        )
+    };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(os_error_message(&io::Error::last_os_error()))
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_io_priority(_pid: u32, _priority: IoPriority) -> Result<(), String> {
+    Err("I/O priority not supported on this platform".to_string())
+}
+
+/// CPUs the process may run on
+#[cfg(target_os = "linux")]
+pub fn get_affinity(pid: u32) -> Option<Vec<usize>> {
+    // SAFETY: cpu_set_t is plain data; sched_getaffinity writes at most its size
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        if libc::sched_getaffinity(
+            pid as libc::pid_t,
+            std::mem::size_of::<libc::cpu_set_t>(),
+            &mut set,
+        ) != 0
+        {
+            return None;
+        }
+        Some(
+            (0..libc::CPU_SETSIZE as usize)
+                .filter(|&cpu| libc::CPU_ISSET(cpu, &set))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_affinity(_pid: u32) -> Option<Vec<usize>> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+pub fn set_affinity(pid: u32, cpus: &[usize]) -> Result<(), String> {
+    if cpus.is_empty() {
+        return Err("At least one CPU must be selected".to_string());
+    }
+
+    // SAFETY: cpu_set_t is plain data; CPU_SET indices are bounded by CPU_SETSIZE
+    let result = unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        for &cpu in cpus.iter().filter(|&&cpu| cpu < libc::CPU_SETSIZE as usize) {
+            libc::CPU_SET(cpu, &mut set);
+        }
+        libc::sched_setaffinity(
+            pid as libc::pid_t,
+            std::mem::size_of::<libc::cpu_set_t>(),
+            &set,
+        )
+    };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(os_error_message(&io::Error::last_os_error()))
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_affinity(_pid: u32, _cpus: &[usize]) -> Result<(), String> {
+    Err("CPU affinity not supported on this platform".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_io_priority_round_trip() {
+        let priority = IoPriority {
+            class: IoClass::BestEffort,
+            level: 7,
+        };
+        assert_eq!(priority.to_raw(), (2 << 13) | 7);
+        assert_eq!(IoPriority::from_raw(priority.to_raw()), priority);
+        assert_eq!(IoPriority::from_raw(0).class, IoClass::None);
+        assert_eq!(IoPriority::from_raw(3 << 13).class, IoClass::Idle);
+    }
+
+    #[test]
+    fn test_io_class_cycles() {
+        let mut class = IoClass::None;
+        for _ in 0..4 {
+            class = class.next();
+        }
+        assert_eq!(class, IoClass::None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_nice_of_missing_process() {
+        assert!(get_nice(std::process::id()).is_some());
+        // Above pid_max, so getpriority fails with ESRCH rather than returning -1
+        assert_eq!(get_nice(i32::MAX as u32), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_affinity_of_own_process() {
+        let pid = std::process::id();
+        let cpus = get_affinity(pid).unwrap();
+        assert!(!cpus.is_empty());
+        // Re-applying the current mask is always allowed
+        assert_eq!(set_affinity(pid, &cpus), Ok(()));
+        assert!(set_affinity(pid, &[]).is_err());
+    }
+}
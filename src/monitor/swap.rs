@@ -0,0 +1,273 @@
+/// Swap devices (/proc/swaps), compressed RAM swap (zram) and the zswap cache
+use std::fs;
+use std::path::Path;
+
+use super::meminfo::MemInfo;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwapDevice {
+    pub name: String,
+    /// "partition" or "file" as the kernel reports it
+    pub kind: String,
+    pub size: u64,
+    pub used: u64,
+    pub priority: i32,
+}
+
+/// A /dev/zramN block device; data is stored compressed in RAM
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZramDevice {
+    pub name: String,
+    pub disksize: u64,
+    /// Uncompressed size of the data stored
+    pub orig_data_size: u64,
+    pub compr_data_size: u64,
+    /// RAM actually used, including allocator overhead
+    pub mem_used_total: u64,
+    pub algorithm: Option<String>,
+}
+
+impl ZramDevice {
+    /// Uncompressed data per byte of RAM used, allocator overhead included
+    pub fn ratio(&self) -> Option<f64> {
+        (self.mem_used_total > 0).then(|| self.orig_data_size as f64 / self.mem_used_total as f64)
+    }
+}
+
+/// Compressed cache in front of the swap devices
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZswapInfo {
+    pub enabled: bool,
+    pub compressor: Option<String>,
+    pub max_pool_percent: Option<u32>,
+    /// RAM used by the compressed pool
+    pub pool_size: Option<u64>,
+    /// Uncompressed size of the pages in the pool
+    pub stored: Option<u64>,
+}
+
+impl ZswapInfo {
+    pub fn ratio(&self) -> Option<f64> {
+        match (self.stored, self.pool_size) {
+            (Some(stored), Some(pool)) if pool > 0 => Some(stored as f64 / pool as f64),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SwapDetails {
+    pub devices: Vec<SwapDevice>,
+    pub zram: Vec<ZramDevice>,
+    pub zswap: Option<ZswapInfo>,
+}
+
+impl SwapDetails {
+    pub fn read(meminfo: Option<&MemInfo>) -> Self {
+        Self::read_from(
+            Path::new("/proc"),
+            Path::new("/sys"),
+            Path::new("/sys/kernel/debug"),
+            meminfo,
+        )
+    }
+
+    fn read_from(proc: &Path, sys: &Path, debugfs: &Path, meminfo: Option<&MemInfo>) -> Self {
+        let devices = fs::read_to_string(proc.join("swaps"))
+            .map(|contents| parse_swaps(&contents))
+            .unwrap_or_default();
+        Self {
+            devices,
+            zram: read_zram_devices(&sys.join("block")),
+            zswap: read_zswap(&sys.join("module/zswap/parameters"), debugfs, meminfo),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.devices.is_empty() && self.zram.is_empty()
+    }
+
+    pub fn zram(&self, device: &SwapDevice) -> Option<&ZramDevice> {
+        let name = device.name.rsplit('/').next()?;
+        self.zram.iter().find(|z| z.name == name)
+    }
+}
+
+pub fn parse_swaps(contents: &str) -> Vec<SwapDevice> {
+    contents
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [name, kind, size, used, priority] = fields[..] else {
+                return None;
+            };
+            Some(SwapDevice {
+                // Paths are octal-escaped like /proc/mounts
+                name: name.replace("\\040", " "),
+                kind: kind.to_string(),
+                size: size.parse::<u64>().ok()? * 1024,
+                used: used.parse::<u64>().ok()? * 1024,
+                priority: priority.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// orig_data_size, compr_data_size and mem_used_total: the first three fields of mm_stat
+pub fn parse_mm_stat(contents: &str) -> Option<(u64, u64, u64)> {
+    let mut fields = contents.split_whitespace().map(|f| f.parse::<u64>().ok());
+    Some((fields.next()??, fields.next()??, fields.next()??))
+}
+
+/// The active entry of a sysfs choice list such as "lzo [lz4] zstd"
+fn selected_choice(contents: &str) -> Option<String> {
+    let start = contents.find('[')?;
+    let end = contents[start..].find(']')? + start;
+    Some(contents[start + 1..end].to_string())
+}
+
+fn read_zram_devices(sys_block: &Path) -> Vec<ZramDevice> {
+    let Ok(entries) = fs::read_dir(sys_block) else {
+        return Vec::new();
+    };
+    let mut devices: Vec<ZramDevice> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !name.starts_with("zram") {
+                return None;
+            }
+            let dir = entry.path();
+            let read = |file: &str| fs::read_to_string(dir.join(file)).ok();
+            let disksize = read("disksize")?.trim().parse().ok()?;
+            // Unused devices have no size configured
+            if disksize == 0 {
+                return None;
+            }
+            let (orig_data_size, compr_data_size, mem_used_total) =
+                parse_mm_stat(&read("mm_stat")?)?;
+            Some(ZramDevice {
+                name,
+                disksize,
+                orig_data_size,
+                compr_data_size,
+                mem_used_total,
+                algorithm: read("comp_algorithm").and_then(|c| selected_choice(&c)),
+            })
+        })
+        .collect();
+    devices.sort_by(|a, b| a.name.cmp(&b.name));
+    devices
+}
+
+/// Pool statistics come from /proc/meminfo on Linux 6.5+, otherwise from debugfs (root only)
+fn read_zswap(parameters: &Path, debugfs: &Path, meminfo: Option<&MemInfo>) -> Option<ZswapInfo> {
+    let read = |path: &Path| fs::read_to_string(path).ok().map(|s| s.trim().to_string());
+    let enabled = read(&parameters.join("enabled"))?;
+    let debug = |file: &str| {
+        read(&debugfs.join("zswap").join(file)).and_then(|value| value.parse::<u64>().ok())
+    };
+
+    Some(ZswapInfo {
+        enabled: enabled == "Y" || enabled == "1",
+        compressor: read(&parameters.join("compressor")),
+        max_pool_percent: read(&parameters.join("max_pool_percent")).and_then(|v| v.parse().ok()),
+        pool_size: meminfo
+            .and_then(|info| info.zswap)
+            .or_else(|| debug("pool_total_size")),
+        stored: meminfo
+            .and_then(|info| info.zswapped)
+            .or_else(|| debug("stored_pages").map(|pages| pages * page_size())),
+    })
+}
+
+#[cfg(unix)]
+fn page_size() -> u64 {
+    unsafe { libc::sysconf(libc::_SC_PAGESIZE) }.max(0) as u64
+}
+
+#[cfg(not(unix))]
+fn page_size() -> u64 {
+    4096
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_swaps() {
+        let contents = "Filename\t\t\t\tType\t\tSize\t\tUsed\t\tPriority\n\
+                        /dev/zram0                              partition\t8388604\t\t102400\t\t100\n\
+                        /var/swap\\040file                       file\t\t2097148\t\t0\t\t-2\n";
+        let devices = parse_swaps(contents);
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].name, "/dev/zram0");
+        assert_eq!(devices[0].used, 102400 * 1024);
+        assert_eq!(devices[0].priority, 100);
+        assert_eq!(devices[1].name, "/var/swap file");
+        assert_eq!(devices[1].kind, "file");
+        assert_eq!(devices[1].priority, -2);
+
+        assert_eq!(
+            parse_mm_stat(
+                "  4096000   1024000   1200000        0  1300000      10      0      0\n"
+            ),
+            Some((4096000, 1024000, 1200000))
+        );
+        assert_eq!(
+            selected_choice("lzo lzo-rle [zstd] lz4\n"),
+            Some("zstd".to_string())
+        );
+    }
+
+    #[test]
+    fn test_swap_details_from_fixture() {
+        let root = TempDir::new().unwrap();
+        let proc = root.path().join("proc");
+        let sys = root.path().join("sys");
+        let zram0 = sys.join("block/zram0");
+        let zram1 = sys.join("block/zram1");
+        let zswap = sys.join("module/zswap/parameters");
+        for dir in [&proc, &zram0, &zram1, &zswap] {
+            fs::create_dir_all(dir).unwrap();
+        }
+        fs::write(
+            proc.join("swaps"),
+            "Filename Type Size Used Priority\n/dev/zram0 partition 4096 1024 100\n",
+        )
+        .unwrap();
+        fs::write(zram0.join("disksize"), "4194304\n").unwrap();
+        fs::write(
+            zram0.join("mm_stat"),
+            "3000000 1000000 1500000 0 1500000 0 0 0\n",
+        )
+        .unwrap();
+        fs::write(zram0.join("comp_algorithm"), "lzo [lz4] zstd\n").unwrap();
+        fs::write(zram1.join("disksize"), "0\n").unwrap();
+        fs::write(zswap.join("enabled"), "Y\n").unwrap();
+        fs::write(zswap.join("compressor"), "zstd\n").unwrap();
+        fs::write(zswap.join("max_pool_percent"), "20\n").unwrap();
+
+        let meminfo = MemInfo {
+            zswap: Some(1000),
+            zswapped: Some(3500),
+            ..MemInfo::default()
+        };
+        let details =
+            SwapDetails::read_from(&proc, &sys, &root.path().join("debug"), Some(&meminfo));
+
+        assert_eq!(details.devices.len(), 1);
+        assert_eq!(details.zram.len(), 1);
+        let zram = details.zram(&details.devices[0]).unwrap();
+        assert_eq!(zram.algorithm.as_deref(), Some("lz4"));
+        assert_eq!(zram.ratio(), Some(2.0));
+
+        let zswap = details.zswap.unwrap();
+        assert!(zswap.enabled);
+        assert_eq!(zswap.max_pool_percent, Some(20));
+        assert_eq!(zswap.ratio(), Some(3.5));
+    }
+}
@@ -0,0 +1,153 @@
+/// Kernel virtual memory event counters from /proc/vmstat
+use std::collections::HashMap;
+use std::fs;
+use std::time::Instant;
+
+/// One reading of /proc/vmstat; counters only ever grow, so rates come from two samples
+pub struct VmStat {
+    counters: HashMap<String, u64>,
+    taken: Instant,
+}
+
+impl VmStat {
+    pub fn read() -> Option<Self> {
+        let contents = fs::read_to_string("/proc/vmstat").ok()?;
+        Some(Self {
+            counters: parse_vmstat(&contents),
+            taken: Instant::now(),
+        })
+    }
+
+    pub fn get(&self, key: &str) -> Option<u64> {
+        self.counters.get(key).copied()
+    }
+
+    /// Per-second increase of `key` since `earlier`
+    pub fn rate(&self, earlier: &VmStat, key: &str) -> f64 {
+        let elapsed = self.taken.duration_since(earlier.taken).as_secs_f64();
+        match (self.get(key), earlier.get(key)) {
+            (Some(now), Some(before)) if elapsed > 0.0 => {
+                now.saturating_sub(before) as f64 / elapsed
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+/// Signs of memory pressure: OOM kills, page faults and page reclaim
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MemoryEvents {
+    /// OOM killer invocations since boot (Linux 4.13+)
+    pub oom_kills: u64,
+    pub oom_kills_since_start: u64,
+    /// Faults since boot; major faults had to read the page from disk
+    pub minor_faults: u64,
+    pub major_faults: u64,
+    /// Pages scanned and reclaimed since boot, by kswapd and in direct reclaim
+    pub pages_scanned: u64,
+    pub pages_stolen: u64,
+    /// Per second over the last refresh
+    pub minor_fault_rate: f64,
+    pub major_fault_rate: f64,
+    pub kswapd_scan_rate: f64,
+    /// Allocating tasks reclaiming memory themselves, which stalls them
+    pub direct_scan_rate: f64,
+    pub steal_rate: f64,
+}
+
+impl MemoryEvents {
+    pub fn from_samples(now: &VmStat, before: Option<&VmStat>, oom_kills_at_start: u64) -> Self {
+        let total = |key: &str| now.get(key).unwrap_or(0);
+        let rate = |key: &str| before.map_or(0.0, |before| now.rate(before, key));
+        let oom_kills = total("oom_kill");
+        // pgfault counts every fault, major ones included
+        let major_faults = total("pgmajfault");
+        let major_fault_rate = rate("pgmajfault");
+        Self {
+            oom_kills,
+            oom_kills_since_start: oom_kills.saturating_sub(oom_kills_at_start),
+            minor_faults: total("pgfault").saturating_sub(major_faults),
+            major_faults,
+            pages_scanned: total("pgscan_kswapd") + total("pgscan_direct"),
+            pages_stolen: total("pgsteal_kswapd") + total("pgsteal_direct"),
+            minor_fault_rate: (rate("pgfault") - major_fault_rate).max(0.0),
+            major_fault_rate,
+            kswapd_scan_rate: rate("pgscan_kswapd"),
+            direct_scan_rate: rate("pgscan_direct"),
+            steal_rate: rate("pgsteal_kswapd") + rate("pgsteal_direct"),
+        }
+    }
+}
+
+pub fn parse_vmstat(contents: &str) -> HashMap<String, u64> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(' ')?;
+            Some((key.to_string(), value.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_vmstat_rate() {
+        let counters = parse_vmstat("nr_free_pages 1000\npswpin 40\npswpout 100\n");
+        assert_eq!(counters.get("pswpin"), Some(&40));
+
+        let start = Instant::now();
+        let earlier = VmStat {
+            counters,
+            taken: start,
+        };
+        let later = VmStat {
+            counters: parse_vmstat("nr_free_pages 900\npswpin 60\npswpout 100\n"),
+            taken: start + Duration::from_secs(2),
+        };
+        assert_eq!(later.rate(&earlier, "pswpin"), 10.0);
+        assert_eq!(later.rate(&earlier, "pswpout"), 0.0);
+        // Gauges that shrink and missing keys read as no activity
+        assert_eq!(later.rate(&earlier, "nr_free_pages"), 0.0);
+        assert_eq!(later.rate(&earlier, "oom_kill"), 0.0);
+    }
+
+    #[test]
+    fn test_memory_events() {
+        let start = Instant::now();
+        let earlier = VmStat {
+            counters: parse_vmstat(
+                "pgfault 1000\npgmajfault 10\npgscan_kswapd 0\npgscan_direct 0\n\
+                 pgsteal_kswapd 0\npgsteal_direct 0\noom_kill 2\n",
+            ),
+            taken: start,
+        };
+        let later = VmStat {
+            counters: parse_vmstat(
+                "pgfault 1300\npgmajfault 30\npgscan_kswapd 500\npgscan_direct 100\n\
+                 pgsteal_kswapd 400\npgsteal_direct 50\noom_kill 3\n",
+            ),
+            taken: start + Duration::from_secs(10),
+        };
+
+        let events = MemoryEvents::from_samples(&later, Some(&earlier), 2);
+        assert_eq!(events.oom_kills, 3);
+        assert_eq!(events.oom_kills_since_start, 1);
+        assert_eq!(events.minor_faults, 1270);
+        assert_eq!(events.major_faults, 30);
+        assert_eq!(events.pages_scanned, 600);
+        assert_eq!(events.pages_stolen, 450);
+        assert_eq!(events.minor_fault_rate, 28.0);
+        assert_eq!(events.major_fault_rate, 2.0);
+        assert_eq!(events.kswapd_scan_rate, 50.0);
+        assert_eq!(events.direct_scan_rate, 10.0);
+        assert_eq!(events.steal_rate, 45.0);
+
+        let first = MemoryEvents::from_samples(&earlier, None, 2);
+        assert_eq!(first.oom_kills_since_start, 0);
+        assert_eq!(first.major_fault_rate, 0.0);
+    }
+}
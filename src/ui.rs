@@ -1,24 +1,87 @@
 use crossterm::event::{
-    self, Event, KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    self, Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
 };
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     symbols,
     text::{Line, Span},
-    widgets::{Axis, Block, Borders, Chart, Dataset, Paragraph, Row, Table},
+    widgets::{Axis, Block, Borders, Chart, Dataset, Paragraph, Row, Table, Wrap},
     Frame,
 };
-use regex::Regex;
-use std::io;
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::io::{self, Write};
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
-use crate::config::Config;
+use crate::config::{Config, ProcessColumn, ProcessColumns};
 use crate::export::*;
-use crate::graphics::GraphSymbol;
+use crate::filter::ProcessFilter;
+use crate::graphics::{GraphRenderer, GraphSymbol};
+use crate::monitor::cgroup_limits::CgroupLimitReader;
+use crate::monitor::cpu_times::{CpuTimeKind, CpuTimeShares};
+use crate::monitor::exited::ExitStatus;
+use crate::monitor::meminfo::{MemComponent, MemInfo};
+use crate::monitor::pressure::{PressureResource, PressureSource, PsiAverages};
+use crate::monitor::sched::{self, IoPriority};
+use crate::monitor::swap::ZramDevice;
 use crate::monitor::*;
 use crate::theme::ThemeManager;
-use crate::utils::{format_bytes, COLORS};
+use crate::utils::{base64_encode, format_bytes, format_cpu_time, COLORS};
+
+/// Signal picker state; the target processes are captured when the menu opens
+struct SignalMenu {
+    targets: Vec<(ProcessKey, String)>,
+    selected: usize,
+}
+
+/// Nice / I/O priority editor; values start from the first target's current settings
+/// and only the fields the user changed are applied to each target
+struct PriorityMenu {
+    targets: Vec<(ProcessKey, String)>,
+    nice: i32,
+    io: IoPriority,
+    /// 0 = nice, 1 = I/O class, 2 = I/O level
+    field: usize,
+    /// Whether each field was changed, indexed like `field`
+    edited: [bool; 3],
+}
+
+/// CPU affinity core picker
+struct AffinityMenu {
+    targets: Vec<(ProcessKey, String)>,
+    /// Kernel CPU number of each online CPU and whether it is in the mask
+    cpus: Vec<(usize, bool)>,
+    cursor: usize,
+}
+
+/// Process column editor; edits apply live and are saved to the config on close
+struct ColumnEditor {
+    cursor: usize,
+    changed: bool,
+}
+
+/// Filter being typed in the footer; the query applies live whenever it parses
+struct FilterInput {
+    /// Filter that was active when input started, restored on Esc
+    original: String,
+    /// Position in the history while browsing it with Up/Down
+    history_index: Option<usize>,
+    /// Text typed before browsing the history
+    draft: String,
+}
+
+/// Result of a user action, shown in the footer for a few seconds
+struct StatusMessage {
+    text: String,
+    is_error: bool,
+    shown_at: Instant,
+}
+
+const STATUS_MESSAGE_DURATION: Duration = Duration::from_secs(5);
+const FILTER_HISTORY_SIZE: usize = 50;
+/// Rows the process table shows at most; scroll bounds are derived from it
+const PROCESS_PAGE_SIZE: usize = 20;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ViewPage {
@@ -32,6 +95,8 @@ pub struct App {
     cpu_monitor: CpuMonitor,
     memory_monitor: MemoryMonitor,
     network_monitor: NetworkMonitor,
+    latency_monitor: LatencyMonitor,
+    pressure_monitor: PressureMonitor,
     disk_monitor: DiskMonitor,
     process_monitor: ProcessMonitor,
     temp_monitor: TempMonitor,
@@ -45,29 +110,49 @@ pub struct App {
     last_disk_update: Instant,
     last_process_update: Instant,
     config: Config,
+    /// File the config was loaded from; the column editor saves its layout there
+    config_path: Option<PathBuf>,
     show_help: bool,
     paused: bool,
     process_filter: String,
-    process_filter_regex: Option<Regex>,
+    process_filter_query: ProcessFilter,
+    /// Why the filter text does not parse; the last valid query stays applied meanwhile
+    filter_error: Option<String>,
+    filter_input: Option<FilterInput>,
+    filter_history: Vec<String>,
     color_enabled: bool,
     current_page: ViewPage,
     process_scroll: usize,
-    process_selected: Option<usize>,
-    show_kill_confirm: bool,
-    _show_signal_menu: bool,
+    process_selected: Option<ProcessKey>,
+    process_tagged: HashSet<ProcessKey>,
+    user_filter: Option<String>,
+    show_user_summary: bool,
+    show_group_summary: bool,
+    show_exited: bool,
+    process_detail: Option<ProcessDetailMonitor>,
+    detail_show_env: bool,
+    detail_scroll: usize,
+    signal_menu: Option<SignalMenu>,
+    priority_menu: Option<PriorityMenu>,
+    affinity_menu: Option<AffinityMenu>,
+    column_editor: Option<ColumnEditor>,
+    status_message: Option<StatusMessage>,
     mouse_enabled: bool,
-    _graph_symbol: GraphSymbol,
+    graph_symbol: GraphSymbol,
     _rounded_corners: bool,
 }
 
 impl App {
     pub fn new(config: Config) -> Self {
+        let cgroup = CgroupLimitReader::detect();
         Self {
-            cpu_monitor: CpuMonitor::new(),
-            memory_monitor: MemoryMonitor::new(),
-            network_monitor: NetworkMonitor::new(),
+            cpu_monitor: CpuMonitor::new(cgroup.clone()),
+            memory_monitor: MemoryMonitor::new(cgroup.clone()),
+            network_monitor: NetworkMonitor::new(&config.network),
+            latency_monitor: LatencyMonitor::new(&config.latency),
+            pressure_monitor: PressureMonitor::new(&cgroup),
             disk_monitor: DiskMonitor::new(),
-            process_monitor: ProcessMonitor::new(),
+            process_monitor: ProcessMonitor::new(&config.display, &config.process),
             temp_monitor: TempMonitor::new(),
             system_monitor: SystemMonitor::new(),
             battery_monitor: BatteryMonitor::new(),
@@ -79,18 +164,33 @@ impl App {
             last_disk_update: Instant::now(),
             last_process_update: Instant::now(),
             config,
+            config_path: Config::config_path().ok(),
             show_help: false,
             paused: false,
             process_filter: String::new(),
-            process_filter_regex: None,
+            process_filter_query: ProcessFilter::default(),
+            filter_error: None,
+            filter_input: None,
+            filter_history: Vec::new(),
             color_enabled: true,
             current_page: ViewPage::Overview,
             process_scroll: 0,
             process_selected: None,
-            show_kill_confirm: false,
-            _show_signal_menu: false,
+            process_tagged: HashSet::new(),
+            user_filter: None,
+            show_user_summary: false,
+            show_group_summary: false,
+            show_exited: false,
+            process_detail: None,
+            detail_show_env: false,
+            detail_scroll: 0,
+            signal_menu: None,
+            priority_menu: None,
+            affinity_menu: None,
+            column_editor: None,
+            status_message: None,
             mouse_enabled: true,
-            _graph_symbol: GraphSymbol::Braille,
+            graph_symbol: GraphSymbol::Braille,
             _rounded_corners: true,
         }
     }
@@ -108,6 +208,10 @@ impl App {
         self.color_enabled = enabled;
     }
 
+    pub fn set_config_path(&mut self, path: PathBuf) {
+        self.config_path = Some(path);
+    }
+
     pub fn update(&mut self) {
         if self.paused {
             return;
@@ -120,7 +224,11 @@ impl App {
             self.cpu_monitor.update();
             self.memory_monitor.update();
             self.network_monitor.update();
-            self.network_monitor.update_ping();
+            if let Some(detail) = &mut self.process_detail {
+                detail.update();
+            }
+            self.latency_monitor.update();
+            self.pressure_monitor.update();
             self.temp_monitor.update();
             self.system_monitor.update();
             self.battery_monitor.update();
@@ -147,6 +255,15 @@ impl App {
         if now.duration_since(self.last_process_update) >= self.config.process_refresh_duration() {
             self.process_monitor.update();
             self.last_process_update = now;
+
+            let monitor = &self.process_monitor;
+            self.process_tagged.retain(|key| monitor.is_alive(*key));
+            if !self
+                .process_selected
+                .is_some_and(|key| monitor.is_alive(key))
+            {
+                self.process_selected = None;
+            }
         }
     }
 
@@ -154,15 +271,139 @@ impl App {
         if event::poll(Duration::from_millis(50))? {
             match event::read()? {
                 Event::Key(key) => {
-                    // Handle kill confirmation first
-                    if self.show_kill_confirm {
+                    if self.filter_input.is_some() {
+                        return Ok(self.handle_filter_input(key));
+                    }
+
+                    if let Some(menu) = &mut self.priority_menu {
+                        match key.code {
+                            KeyCode::Up | KeyCode::BackTab => {
+                                menu.field = (menu.field + 2) % 3;
+                            }
+                            KeyCode::Down | KeyCode::Tab => {
+                                menu.field = (menu.field + 1) % 3;
+                            }
+                            KeyCode::Left | KeyCode::Right => {
+                                let step = if key.code == KeyCode::Right { 1 } else { -1 };
+                                menu.edited[menu.field] = true;
+                                match menu.field {
+                                    0 => menu.nice = (menu.nice + step).clamp(-20, 19),
+                                    1 => menu.io.class = menu.io.class.next(),
+                                    _ => {
+                                        menu.io.level =
+                                            (menu.io.level as i32 + step).clamp(0, 7) as u8
+                                    }
+                                }
+                            }
+                            KeyCode::Enter => self.apply_priority_menu(),
+                            _ => {
+                                self.priority_menu = None;
+                            }
+                        }
+                        return Ok(false);
+                    }
+
+                    if let Some(menu) = &mut self.affinity_menu {
+                        let count = menu.cpus.len();
+                        match key.code {
+                            KeyCode::Left | KeyCode::Up => {
+                                menu.cursor = menu.cursor.saturating_sub(1);
+                            }
+                            KeyCode::Right | KeyCode::Down => {
+                                menu.cursor = (menu.cursor + 1).min(count.saturating_sub(1));
+                            }
+                            KeyCode::Char(' ') => {
+                                if let Some((_, on)) = menu.cpus.get_mut(menu.cursor) {
+                                    *on = !*on;
+                                }
+                            }
+                            KeyCode::Char('a') => {
+                                let all = menu.cpus.iter().all(|&(_, on)| on);
+                                menu.cpus.iter_mut().for_each(|(_, on)| *on = !all);
+                            }
+                            KeyCode::Enter => self.apply_affinity_menu(),
+                            _ => {
+                                self.affinity_menu = None;
+                            }
+                        }
+                        return Ok(false);
+                    }
+
+                    if let Some(editor) = &mut self.column_editor {
+                        let columns = &mut self.config.process.columns;
+                        let items = Self::column_order(columns);
+                        let column = items[editor.cursor.min(items.len() - 1)];
+                        match key.code {
+                            KeyCode::Up => {
+                                editor.cursor = editor.cursor.saturating_sub(1);
+                            }
+                            KeyCode::Down => {
+                                editor.cursor = (editor.cursor + 1).min(items.len() - 1);
+                            }
+                            KeyCode::Char(' ') => {
+                                columns.toggle(column);
+                                editor.changed = true;
+                            }
+                            KeyCode::Char('<') | KeyCode::Char('>') => {
+                                let step = if key.code == KeyCode::Char('>') {
+                                    1
+                                } else {
+                                    -1
+                                };
+                                columns.shift(column, step);
+                                editor.changed = true;
+                            }
+                            KeyCode::Left | KeyCode::Right => {
+                                let step = if key.code == KeyCode::Right { 1 } else { -1 };
+                                columns.resize(column, step);
+                                editor.changed = true;
+                            }
+                            KeyCode::Char('s') => {
+                                self.process_monitor.set_sort_order(column.into());
+                            }
+                            KeyCode::Esc
+                            | KeyCode::Enter
+                            | KeyCode::Char('q')
+                            | KeyCode::Char('C') => {
+                                self.close_column_editor();
+                                return Ok(false);
+                            }
+                            _ => {}
+                        }
+                        // Toggling or moving reorders the list; keep the cursor on the column
+                        if !matches!(key.code, KeyCode::Up | KeyCode::Down) {
+                            if let Some(pos) = Self::column_order(&self.config.process.columns)
+                                .iter()
+                                .position(|&c| c == column)
+                            {
+                                editor.cursor = pos;
+                            }
+                        }
+                        return Ok(false);
+                    }
+
+                    // Signal menu captures all keys while open
+                    if let Some(menu) = &mut self.signal_menu {
+                        let signal_count = ProcessSignal::all_signals().len();
                         match key.code {
-                            KeyCode::Char('y') | KeyCode::Char('Y') => {
-                                self.kill_selected_process();
-                                self.show_kill_confirm = false;
+                            KeyCode::Up => {
+                                menu.selected = menu.selected.saturating_sub(1);
+                            }
+                            KeyCode::Down => {
+                                menu.selected = (menu.selected + 1).min(signal_count - 1);
+                            }
+                            KeyCode::Enter => {
+                                let index = menu.selected;
+                                self.send_signal_from_menu(index);
+                            }
+                            KeyCode::Char(c) if c.is_ascii_digit() => {
+                                let index = c.to_digit(10).unwrap_or(0) as usize;
+                                if (1..=signal_count).contains(&index) {
+                                    self.send_signal_from_menu(index - 1);
+                                }
                             }
                             _ => {
-                                self.show_kill_confirm = false;
+                                self.signal_menu = None;
                             }
                         }
                         return Ok(false);
@@ -174,6 +415,37 @@ impl App {
                         return Ok(false);
                     }
 
+                    if self.process_detail.is_some() {
+                        match key.code {
+                            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                return Ok(true)
+                            }
+                            KeyCode::Esc
+                            | KeyCode::Char('q')
+                            | KeyCode::Enter
+                            | KeyCode::Backspace => {
+                                self.process_detail = None;
+                            }
+                            KeyCode::Char('e') => {
+                                self.detail_show_env = !self.detail_show_env;
+                            }
+                            KeyCode::Up => {
+                                self.detail_scroll = self.detail_scroll.saturating_sub(1);
+                            }
+                            KeyCode::Down => {
+                                self.detail_scroll += 1;
+                            }
+                            KeyCode::PageUp => {
+                                self.detail_scroll = self.detail_scroll.saturating_sub(10);
+                            }
+                            KeyCode::PageDown => {
+                                self.detail_scroll += 10;
+                            }
+                            _ => {}
+                        }
+                        return Ok(false);
+                    }
+
                     match key.code {
                         KeyCode::Char('q') | KeyCode::Esc => return Ok(true),
                         KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
@@ -194,60 +466,141 @@ impl App {
                         KeyCode::Char('m') => {
                             self.process_monitor.set_sort_order(SortOrder::Memory);
                         }
-                        KeyCode::Char('k') if self.process_selected.is_some() => {
-                            self.show_kill_confirm = true;
+                        KeyCode::Char('<') => self.cycle_sort_column(-1),
+                        KeyCode::Char('>') => self.cycle_sort_column(1),
+                        KeyCode::Char('C') => {
+                            self.column_editor = Some(ColumnEditor {
+                                cursor: 0,
+                                changed: false,
+                            });
+                        }
+                        KeyCode::Char('s') => {
+                            self.disk_monitor.cycle_sort_order();
+                        }
+                        KeyCode::Char('i') if self.current_page == ViewPage::Network => {
+                            self.network_monitor.cycle_interface();
+                        }
+                        KeyCode::Char('k') if !self.action_targets().is_empty() => {
+                            self.open_signal_menu();
+                        }
+                        KeyCode::Char('x') => {
+                            self.toggle_tag_selected();
+                        }
+                        KeyCode::Char('u') => {
+                            self.process_tagged.clear();
+                        }
+                        KeyCode::Char('n') => {
+                            self.renice_targets(1);
+                        }
+                        KeyCode::Char('r') => {
+                            self.open_priority_menu();
+                        }
+                        KeyCode::Char('A') => {
+                            self.open_affinity_menu();
+                        }
+                        KeyCode::Char('H') => {
+                            self.toggle_selected_threads();
+                        }
+                        KeyCode::Char('K') => {
+                            let shown = self.process_monitor.toggle_kernel_threads();
+                            let state = if shown { "shown" } else { "hidden" };
+                            self.set_status(format!("Kernel threads {}", state), false);
+                        }
+                        KeyCode::Char('S') => {
+                            let shown = self.process_monitor.toggle_self();
+                            let state = if shown { "shown" } else { "hidden" };
+                            self.set_status(format!("rtop itself {}", state), false);
+                        }
+                        KeyCode::Char('N') => {
+                            self.renice_targets(-1);
+                        }
+                        KeyCode::Char('y') => {
+                            self.copy_target_pids();
+                        }
+                        KeyCode::Char('U') => {
+                            self.cycle_user_filter();
+                        }
+                        KeyCode::Char('a') => {
+                            self.show_user_summary = !self.show_user_summary;
+                            self.show_group_summary = false;
+                        }
+                        KeyCode::Char('g') => {
+                            self.show_group_summary = !self.show_group_summary;
+                            self.show_user_summary = false;
+                        }
+                        KeyCode::Char('X') => {
+                            self.show_exited = !self.show_exited;
+                        }
+                        KeyCode::Char('t') => {
+                            self.process_monitor.toggle_tree_view();
+                            self.process_scroll = 0;
+                            self.scroll_to_selection();
                         }
                         KeyCode::Char('/') => {
-                            self.process_filter.clear();
-                            self.process_filter_regex = None;
+                            self.filter_input = Some(FilterInput {
+                                original: self.process_filter.clone(),
+                                history_index: None,
+                                draft: String::new(),
+                            });
                         }
                         KeyCode::Backspace if !self.process_filter.is_empty() => {
-                            self.process_filter.pop();
-                            self.update_filter_regex();
-                        }
-                        KeyCode::Char(c) if c.is_alphanumeric() || c == '-' || c == '_' => {
-                            if self.process_filter.len() < 30 {
-                                self.process_filter.push(c);
-                                self.update_filter_regex();
-                            }
+                            self.set_filter(String::new());
                         }
                         // Page navigation
                         KeyCode::F(2) => self.current_page = ViewPage::Overview,
                         KeyCode::F(3) => self.current_page = ViewPage::Processes,
                         KeyCode::F(4) => self.current_page = ViewPage::Network,
                         KeyCode::F(5) => self.current_page = ViewPage::Storage,
-                        // Scroll process list
+                        // Move the selection, or scroll the process list when nothing is selected
                         KeyCode::Up => {
-                            if self.process_scroll > 0 {
-                                self.process_scroll -= 1;
-                            }
+                            self.move_selection(-1);
                         }
                         KeyCode::Down => {
-                            let max_processes = self.process_monitor.get_sorted_processes().len();
-                            if self.process_scroll < max_processes.saturating_sub(20) {
-                                self.process_scroll += 1;
-                            }
+                            self.move_selection(1);
                         }
                         KeyCode::PageUp => {
                             self.process_scroll = self.process_scroll.saturating_sub(10);
                         }
                         KeyCode::PageDown => {
-                            let max_processes = self.process_monitor.get_sorted_processes().len();
-                            self.process_scroll =
-                                (self.process_scroll + 10).min(max_processes.saturating_sub(20));
+                            let max_processes = self.visible_processes().len();
+                            self.process_scroll = (self.process_scroll + 10)
+                                .min(max_processes.saturating_sub(PROCESS_PAGE_SIZE));
                         }
                         KeyCode::Home => {
                             self.process_scroll = 0;
                         }
                         KeyCode::End => {
-                            let max_processes = self.process_monitor.get_sorted_processes().len();
-                            self.process_scroll = max_processes.saturating_sub(20);
+                            let max_processes = self.visible_processes().len();
+                            self.process_scroll = max_processes.saturating_sub(PROCESS_PAGE_SIZE);
                         }
                         KeyCode::Enter => {
-                            if self.process_scroll
-                                < self.process_monitor.get_sorted_processes().len()
-                            {
-                                self.process_selected = Some(self.process_scroll);
+                            if self.process_selected.is_none() {
+                                let processes = self.visible_processes();
+                                self.process_selected =
+                                    processes.get(self.process_scroll).map(|p| p.key());
+                            }
+                            if let Some(key) = self.process_selected {
+                                match ProcessDetailMonitor::open(key) {
+                                    Ok(detail) => {
+                                        self.process_detail = Some(detail);
+                                        self.detail_scroll = 0;
+                                    }
+                                    Err(e) => self.set_status(
+                                        format!("Cannot open PID {}: {}", key.pid, e),
+                                        true,
+                                    ),
+                                }
+                            }
+                        }
+                        // Fold/unfold the selected subtree
+                        KeyCode::Left if self.process_monitor.is_tree_view() => {
+                            if let Some(key) = self.process_selected {
+                                self.process_monitor.collapse(key.pid);
+                            }
+                        }
+                        KeyCode::Right if self.process_monitor.is_tree_view() => {
+                            if let Some(key) = self.process_selected {
+                                self.process_monitor.expand(key.pid);
                             }
                         }
                         _ => {}
@@ -264,24 +617,21 @@ impl App {
 
     fn handle_mouse(&mut self, mouse: MouseEvent) {
         match mouse.kind {
-            MouseEventKind::ScrollUp => {
-                if self.process_scroll > 0 {
-                    self.process_scroll -= 1;
-                }
+            MouseEventKind::ScrollUp if self.process_scroll > 0 => {
+                self.process_scroll -= 1;
             }
             MouseEventKind::ScrollDown => {
-                let max_processes = self.process_monitor.get_sorted_processes().len();
-                if self.process_scroll < max_processes.saturating_sub(20) {
+                let max_processes = self.visible_processes().len();
+                if self.process_scroll < max_processes.saturating_sub(PROCESS_PAGE_SIZE) {
                     self.process_scroll += 1;
                 }
             }
             MouseEventKind::Down(MouseButton::Left) => {
-                // Could add click to select process
                 let row = mouse.row as usize;
-                if row > 3 && row < 24 {
+                if row > 3 && row < 4 + PROCESS_PAGE_SIZE {
                     let index = row - 4 + self.process_scroll;
-                    if index < self.process_monitor.get_sorted_processes().len() {
-                        self.process_selected = Some(index);
+                    if let Some(process) = self.visible_processes().get(index) {
+                        self.process_selected = Some(process.key());
                     }
                 }
             }
@@ -289,160 +639,806 @@ impl App {
         }
     }
 
-    fn update_filter_regex(&mut self) {
-        if self.process_filter.is_empty() {
-            self.process_filter_regex = None;
-        } else {
-            self.process_filter_regex = Regex::new(&self.process_filter).ok();
+    /// Keys while the filter is being typed; returns whether to quit
+    fn handle_filter_input(&mut self, key: KeyEvent) -> bool {
+        let Some(input) = &mut self.filter_input else {
+            return false;
+        };
+        let mut text = self.process_filter.clone();
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return true,
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => text.clear(),
+            KeyCode::Esc => {
+                let original = std::mem::take(&mut input.original);
+                self.filter_input = None;
+                self.set_filter(original);
+                return false;
+            }
+            KeyCode::Enter => {
+                // An unparsable query keeps the input open with its error showing
+                if self.filter_error.is_none() {
+                    self.filter_input = None;
+                    self.remember_filter();
+                }
+                return false;
+            }
+            KeyCode::Up if !self.filter_history.is_empty() => {
+                let index = match input.history_index {
+                    None => {
+                        input.draft = text.clone();
+                        self.filter_history.len() - 1
+                    }
+                    Some(index) => index.saturating_sub(1),
+                };
+                input.history_index = Some(index);
+                text = self.filter_history[index].clone();
+            }
+            KeyCode::Down => match input.history_index {
+                Some(index) if index + 1 < self.filter_history.len() => {
+                    input.history_index = Some(index + 1);
+                    text = self.filter_history[index + 1].clone();
+                }
+                Some(_) => {
+                    input.history_index = None;
+                    text = std::mem::take(&mut input.draft);
+                }
+                None => {}
+            },
+            KeyCode::Backspace => {
+                text.pop();
+            }
+            KeyCode::Char(c) => text.push(c),
+            _ => {}
         }
+        self.set_filter(text);
+        false
     }
 
-    fn kill_selected_process(&mut self) {
-        if let Some(index) = self.process_selected {
-            let processes = self.process_monitor.get_sorted_processes();
-            if let Some(process) = processes.get(index) {
-                #[cfg(unix)]
-                {
-                    use std::process::Command;
-                    let _ = Command::new("kill")
-                        .arg(format!("{}", process.pid))
-                        .output();
-                }
-                self.process_selected = None;
+    /// Apply new filter text; on a parse error the previous query stays in effect
+    fn set_filter(&mut self, text: String) {
+        match ProcessFilter::parse(&text) {
+            Ok(query) => {
+                self.process_filter_query = query;
+                self.filter_error = None;
             }
+            Err(e) => self.filter_error = Some(e.to_string()),
         }
+        self.process_filter = text;
+        self.process_scroll = 0;
     }
 
-    pub fn collect_metrics(&self) -> Metrics {
-        let timestamp = chrono::Local::now().to_rfc3339();
-
-        let cpu_data = self.cpu_monitor.get_all_cpu_data();
-        let cores: Vec<CoreMetric> = cpu_data
-            .iter()
-            .enumerate()
-            .map(|(i, (_, usage, _))| CoreMetric {
-                id: i,
-                usage: *usage,
-            })
-            .collect();
-        let cpu_avg = cores.iter().map(|c| c.usage).sum::<f32>() / cores.len() as f32;
-
-        let (mem_percent, _, mem_used, mem_total) = self.memory_monitor.get_memory_data();
-        let (swap_percent, _, swap_used, swap_total) = self.memory_monitor.get_swap_data();
+    fn remember_filter(&mut self) {
+        let text = self.process_filter.trim().to_string();
+        if text.is_empty() {
+            return;
+        }
+        self.filter_history.retain(|entry| *entry != text);
+        self.filter_history.push(text);
+        if self.filter_history.len() > FILTER_HISTORY_SIZE {
+            self.filter_history.remove(0);
+        }
+    }
 
-        let (_, _, rx_rate, tx_rate, total_rx, total_tx) = self.network_monitor.get_network_data();
+    /// Sorted process list with the filter query applied, as shown in the table
+    fn visible_processes(&self) -> Vec<ProcessInfo> {
+        let mut processes = self.process_monitor.get_sorted_processes();
+        // Thread rows stay listed exactly when their process is
+        let mut owner_visible = true;
+        processes.retain(|p| {
+            if p.is_thread {
+                return owner_visible;
+            }
+            owner_visible = self.process_filter_query.matches(p)
+                && self.user_filter.as_ref().is_none_or(|user| &p.user == user);
+            owner_visible
+        });
+        processes
+    }
 
-        let (disk_percent, disk_used, disk_total) = self.disk_monitor.get_disk_data();
+    /// Cycle the user filter: all users -> first -> ... -> last -> all users
+    fn cycle_user_filter(&mut self) {
+        let users = self.process_monitor.user_list();
+        let next = match &self.user_filter {
+            None => users.first(),
+            Some(current) => users
+                .iter()
+                .position(|u| u == current)
+                .and_then(|pos| users.get(pos + 1)),
+        };
+        self.user_filter = next.cloned();
+        self.process_scroll = 0;
+    }
 
-        let processes = self.process_monitor.get_sorted_processes();
+    fn move_selection(&mut self, delta: isize) {
+        let processes = self.visible_processes();
+        let current = self
+            .process_selected
+            .and_then(|key| processes.iter().position(|p| p.key() == key));
+
+        match current {
+            Some(index) => {
+                let target = index.saturating_add_signed(delta).min(processes.len() - 1);
+                self.process_selected = Some(processes[target].key());
+                self.scroll_to_selection();
+            }
+            // Nothing selected yet: start from the top visible row
+            None => {
+                self.process_selected = processes.get(self.process_scroll).map(|p| p.key());
+            }
+        }
+    }
 
-        let temp_data = self.temp_monitor.get_temperature_data();
-        let temperature = if !temp_data.is_empty() {
-            Some(TempMetrics {
-                sensors: temp_data
-                    .iter()
-                    .map(|(name, temp, _)| SensorMetric {
-                        name: name.clone(),
-                        temperature: *temp,
-                    })
-                    .collect(),
-                average: temp_data.iter().map(|(_, t, _)| t).sum::<f32>() / temp_data.len() as f32,
-                max: temp_data.iter().map(|(_, t, _)| *t).fold(0.0, f32::max),
-            })
-        } else {
-            None
+    /// Adjust the scroll offset so the selected row is on screen
+    fn scroll_to_selection(&mut self) {
+        let Some(key) = self.process_selected else {
+            return;
         };
+        if let Some(index) = self.visible_processes().iter().position(|p| p.key() == key) {
+            if index < self.process_scroll {
+                self.process_scroll = index;
+            } else if index >= self.process_scroll + PROCESS_PAGE_SIZE {
+                self.process_scroll = index + 1 - PROCESS_PAGE_SIZE;
+            }
+        }
+    }
 
-        Metrics {
-            timestamp,
-            cpu: CpuMetrics {
-                cores,
-                average: cpu_avg,
-            },
-            memory: MemoryMetrics {
-                total: mem_total,
-                used: mem_used,
-                available: mem_total - mem_used,
-                percent: mem_percent,
-                swap_total,
-                swap_used,
-                swap_percent,
-            },
-            network: NetworkMetrics {
-                received: total_rx,
-                transmitted: total_tx,
-                rx_rate: rx_rate as f64,
-                tx_rate: tx_rate as f64,
-            },
-            disk: vec![DiskMetrics {
-                name: "root".to_string(),
-                mount_point: "/".to_string(),
-                total: disk_total,
-                available: disk_total - disk_used,
-                percent: disk_percent,
-            }],
-            processes: processes
+    /// Visible columns in display order, followed by the hidden ones
+    fn column_order(columns: &ProcessColumns) -> Vec<ProcessColumn> {
+        let mut order = columns.visible.clone();
+        order.extend(
+            ProcessColumn::ALL
                 .iter()
-                .take(20)
-                .map(|p| ProcessMetrics {
-                    pid: p.pid,
-                    name: p.name.clone(),
-                    cpu: p.cpu_usage,
-                    memory: p.memory,
-                    memory_percent: (p.memory as f32 / mem_total as f32) * 100.0,
-                })
-                .collect(),
-            temperature,
-            system: SystemMetrics {
-                hostname: self.system_monitor.hostname(),
-                os: self.system_monitor.os_version(),
-                kernel: self.system_monitor.kernel_version(),
-                uptime: self.system_monitor.uptime(),
-                load_average: self.system_monitor.load_average(),
-            },
+                .filter(|c| !columns.visible.contains(c)),
+        );
+        order
+    }
+
+    fn close_column_editor(&mut self) {
+        let Some(editor) = self.column_editor.take() else {
+            return;
+        };
+        if !editor.changed {
+            return;
+        }
+        // Write back only the column layout so runtime-only settings (--minimal) stay out
+        let Some(path) = self.config_path.clone() else {
+            self.set_status(
+                "No config file to save the column layout to".to_string(),
+                true,
+            );
+            return;
+        };
+        match Config::save_process_columns(&path, &self.config.process.columns) {
+            Ok(()) => self.set_status(format!("Column layout saved to {}", path.display()), false),
+            Err(e) => self.set_status(format!("Could not save column layout: {}", e), true),
         }
     }
 
-    pub fn draw(&self, frame: &mut Frame) {
-        // Apply blue background to entire frame
-        let full_area = frame.area();
-        let background = Block::default().style(Style::default().bg(Color::Rgb(10, 20, 40)));
-        frame.render_widget(background, full_area);
+    /// Sort by the next (or previous) visible column
+    fn cycle_sort_column(&mut self, step: isize) {
+        let mut orders: Vec<SortOrder> = Vec::new();
+        for &column in &self.config.process.columns.visible {
+            let order = SortOrder::from(column);
+            if !orders.contains(&order) {
+                orders.push(order);
+            }
+        }
+        let current = self.process_monitor.sort_order();
+        let next = match orders.iter().position(|&o| o == current) {
+            Some(pos) => orders[(pos as isize + step).rem_euclid(orders.len() as isize) as usize],
+            None => orders[0],
+        };
+        if next != current {
+            self.process_monitor.set_sort_order(next);
+        }
+    }
 
-        let main_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3), // Header
-                Constraint::Min(0),    // Content
-                Constraint::Length(2), // Footer/Status bar
-            ])
-            .split(frame.area());
+    /// Expand the selected process into its threads, or fold them back from any of its rows
+    fn toggle_selected_threads(&mut self) {
+        let Some(key) = self.process_selected else {
+            return;
+        };
+        let processes = self.visible_processes();
+        let Some(selected) = processes.iter().find(|p| p.key() == key) else {
+            return;
+        };
+        let owner = match (selected.is_thread, selected.ppid) {
+            (true, Some(ppid)) => ppid,
+            _ => selected.pid,
+        };
+        let owner_key = processes
+            .iter()
+            .find(|p| p.pid == owner && !p.is_thread)
+            .map(|p| p.key());
 
-        // Draw header
-        self.draw_header(frame, main_chunks[0]);
+        if !self.process_monitor.toggle_threads(owner) {
+            // The selected thread row is gone; keep the cursor on its process
+            self.process_selected = owner_key.or(self.process_selected);
+        }
+        self.scroll_to_selection();
+    }
 
-        // Draw footer/status bar
-        self.draw_footer(frame, main_chunks[2]);
+    fn toggle_tag_selected(&mut self) {
+        let Some(key) = self.process_selected else {
+            return;
+        };
+        if !self.process_tagged.remove(&key) {
+            self.process_tagged.insert(key);
+        }
+        // Advance like htop so several rows can be tagged in a row
+        self.move_selection(1);
+    }
 
-        // Draw different content based on current page
-        match self.current_page {
-            ViewPage::Overview => self.draw_overview_page(frame, main_chunks[1]),
-            ViewPage::Processes => self.draw_processes_page(frame, main_chunks[1]),
-            ViewPage::Network => self.draw_network_page(frame, main_chunks[1]),
-            ViewPage::Storage => self.draw_storage_page(frame, main_chunks[1]),
+    /// Processes a batch action applies to: the tagged set, or else the selection.
+    /// Tagged processes the filter currently hides are left out
+    fn action_targets(&self) -> Vec<ProcessInfo> {
+        let processes = self.visible_processes();
+        if self.process_tagged.is_empty() {
+            processes
+                .into_iter()
+                .filter(|p| Some(p.key()) == self.process_selected)
+                .collect()
+        } else {
+            processes
+                .into_iter()
+                .filter(|p| self.process_tagged.contains(&p.key()))
+                .collect()
         }
+    }
 
-        // Draw help overlay if activated
-        if self.show_help {
-            self.draw_help_overlay(frame, frame.area());
+    /// Tagged processes hidden by the filter, which batch actions skip
+    fn hidden_tagged_count(&self) -> usize {
+        if self.process_tagged.is_empty() {
+            return 0;
         }
+        let visible: HashSet<ProcessKey> =
+            self.visible_processes().iter().map(|p| p.key()).collect();
+        self.process_tagged
+            .iter()
+            .filter(|key| !visible.contains(key))
+            .count()
     }
 
-    fn draw_overview_page(&self, frame: &mut Frame, area: Rect) {
-        // Adjust layout based on temperature sensor availability
-        let has_temp = self.temp_monitor.has_temperature_sensors();
-        let has_gpu = self.gpu_monitor.is_enabled() && self.gpu_monitor.gpu_count() > 0;
-        let has_npu = self.npu_monitor.is_enabled() && self.npu_monitor.npu_count() > 0;
+    fn set_status(&mut self, text: String, is_error: bool) {
+        self.status_message = Some(StatusMessage {
+            text,
+            is_error,
+            shown_at: Instant::now(),
+        });
+    }
+
+    /// Report a batch action: the single-target message verbatim, or a count plus the first error
+    fn report_batch(&mut self, action: &str, total: usize, errors: Vec<String>) {
+        if errors.is_empty() {
+            let mut text = if total == 1 {
+                action.to_string()
+            } else {
+                format!("{} ({} processes)", action, total)
+            };
+            let hidden = self.hidden_tagged_count();
+            if hidden > 0 {
+                text.push_str(&format!(", {} hidden by the filter skipped", hidden));
+            }
+            self.set_status(text, false);
+        } else {
+            let text = format!(
+                "{}: {}/{} failed: {}",
+                action,
+                errors.len(),
+                total,
+                errors[0]
+            );
+            self.set_status(text, true);
+        }
+    }
+
+    fn open_signal_menu(&mut self) {
+        let targets = self.target_keys();
+        if !targets.is_empty() {
+            self.signal_menu = Some(SignalMenu {
+                targets,
+                selected: 0,
+            });
+        }
+    }
+
+    fn target_keys(&self) -> Vec<(ProcessKey, String)> {
+        self.action_targets()
+            .into_iter()
+            .map(|p| (p.key(), p.name))
+            .collect()
+    }
+
+    fn open_priority_menu(&mut self) {
+        let targets = self.target_keys();
+        let Some((first, _)) = targets.first() else {
+            return;
+        };
+        self.priority_menu = Some(PriorityMenu {
+            nice: sched::get_nice(first.pid).unwrap_or(0),
+            io: sched::get_io_priority(first.pid).unwrap_or_default(),
+            targets,
+            field: 0,
+            edited: [false; 3],
+        });
+    }
+
+    fn apply_priority_menu(&mut self) {
+        let Some(menu) = self.priority_menu.take() else {
+            return;
+        };
+
+        let [nice_edited, class_edited, level_edited] = menu.edited;
+        let mut changes = Vec::new();
+        if nice_edited {
+            changes.push(format!("nice {}", menu.nice));
+        }
+        if class_edited {
+            changes.push(format!("io class {}", menu.io.class.name()));
+        }
+        if level_edited {
+            changes.push(format!("io level {}", menu.io.level));
+        }
+        if changes.is_empty() {
+            self.set_status("Priority unchanged".to_string(), false);
+            return;
+        }
+
+        let errors: Vec<String> = menu
+            .targets
+            .iter()
+            .filter_map(|(key, name)| {
+                let mut result = Ok(());
+                if nice_edited {
+                    result = self.process_monitor.set_nice(*key, menu.nice);
+                }
+                if result.is_ok() && (class_edited || level_edited) {
+                    // Keep the target's own value for the I/O field that was not edited
+                    let mut io = sched::get_io_priority(key.pid).unwrap_or_default();
+                    if class_edited {
+                        io.class = menu.io.class;
+                    }
+                    if level_edited {
+                        io.level = menu.io.level;
+                    }
+                    result = self.process_monitor.set_io_priority(*key, io);
+                }
+                result
+                    .err()
+                    .map(|e| format!("{} ({}): {}", name, key.pid, e))
+            })
+            .collect();
+
+        let summary = changes.join(", ");
+        let action = match menu.targets.as_slice() {
+            [(key, name)] => format!("Set {} for {} ({})", summary, name, key.pid),
+            _ => format!("Set {}", summary),
+        };
+        self.report_batch(&action, menu.targets.len(), errors);
+    }
+
+    fn open_affinity_menu(&mut self) {
+        let targets = self.target_keys();
+        let Some((first, _)) = targets.first() else {
+            return;
+        };
+        let allowed = sched::get_affinity(first.pid).unwrap_or_default();
+        let cpus = self
+            .cpu_monitor
+            .cores()
+            .iter()
+            .map(|core| (core.id, allowed.is_empty() || allowed.contains(&core.id)))
+            .collect();
+        self.affinity_menu = Some(AffinityMenu {
+            targets,
+            cpus,
+            cursor: 0,
+        });
+    }
+
+    fn apply_affinity_menu(&mut self) {
+        let Some(menu) = self.affinity_menu.take() else {
+            return;
+        };
+        let cpus: Vec<usize> = menu
+            .cpus
+            .iter()
+            .filter(|&&(_, on)| on)
+            .map(|&(cpu, _)| cpu)
+            .collect();
+
+        let errors: Vec<String> = menu
+            .targets
+            .iter()
+            .filter_map(|(key, name)| {
+                self.process_monitor
+                    .set_affinity(*key, &cpus)
+                    .err()
+                    .map(|e| format!("{} ({}): {}", name, key.pid, e))
+            })
+            .collect();
+
+        let cpu_list = cpus
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        let action = match menu.targets.as_slice() {
+            [(key, name)] => format!("Pinned {} ({}) to CPUs {}", name, key.pid, cpu_list),
+            _ => format!("Pinned to CPUs {}", cpu_list),
+        };
+        self.report_batch(&action, menu.targets.len(), errors);
+    }
+
+    fn send_signal_from_menu(&mut self, index: usize) {
+        let Some(menu) = self.signal_menu.take() else {
+            return;
+        };
+        let Some(signal) = ProcessSignal::all_signals().get(index).copied() else {
+            return;
+        };
+
+        let errors: Vec<String> = menu
+            .targets
+            .iter()
+            .filter_map(|(key, name)| {
+                self.process_monitor
+                    .send_signal_checked(*key, signal)
+                    .err()
+                    .map(|e| format!("{} ({}): {}", name, key.pid, e))
+            })
+            .collect();
+
+        let action = match menu.targets.as_slice() {
+            [(key, name)] => format!("SIG{} to {} ({})", signal.name(), name, key.pid),
+            _ => format!("SIG{}", signal.name()),
+        };
+        self.report_batch(&action, menu.targets.len(), errors);
+    }
+
+    fn renice_targets(&mut self, delta: i32) {
+        let targets = self.action_targets();
+        if targets.is_empty() {
+            return;
+        }
+
+        let mut last_nice = 0;
+        let errors: Vec<String> = targets
+            .iter()
+            .filter_map(|p| match self.process_monitor.renice(p.key(), delta) {
+                Ok(nice) => {
+                    last_nice = nice;
+                    None
+                }
+                Err(e) => Some(format!("{} ({}): {}", p.name, p.pid, e)),
+            })
+            .collect();
+
+        let action = match targets.as_slice() {
+            [p] => format!("Renice {} ({}) to {}", p.name, p.pid, last_nice),
+            _ => format!("Renice {:+}", delta),
+        };
+        self.report_batch(&action, targets.len(), errors);
+    }
+
+    /// Copy the target PIDs to the system clipboard via the terminal (OSC 52)
+    fn copy_target_pids(&mut self) {
+        let pids: Vec<String> = self
+            .action_targets()
+            .iter()
+            .map(|p| p.pid.to_string())
+            .collect();
+        if pids.is_empty() {
+            return;
+        }
+
+        let sequence = format!("\x1b]52;c;{}\x07", base64_encode(pids.join(" ").as_bytes()));
+        let mut stdout = io::stdout();
+        match stdout
+            .write_all(sequence.as_bytes())
+            .and_then(|_| stdout.flush())
+        {
+            Ok(()) => self.set_status(format!("Copied {} PID(s) to clipboard", pids.len()), false),
+            Err(e) => self.set_status(format!("Copy failed: {}", e), true),
+        }
+    }
+
+    pub fn collect_metrics(&self) -> Metrics {
+        let timestamp = chrono::Local::now().to_rfc3339();
+
+        let cores: Vec<CoreMetric> = self
+            .cpu_monitor
+            .cores()
+            .into_iter()
+            .map(|core| CoreMetric {
+                id: core.id,
+                usage: core.usage,
+                frequency: core.frequency.current,
+                min_frequency: core.frequency.min,
+                max_frequency: core.frequency.max,
+                governor: core.frequency.governor,
+                package: core.topology.package,
+                core: core.topology.core,
+                siblings: core.topology.siblings,
+                core_type: core.topology.core_type.map(|t| t.letter().to_string()),
+                times: core.times.map(Self::cpu_time_metrics),
+            })
+            .collect();
+        let cpu_avg = self.cpu_monitor.average();
+
+        let (mem_percent, _, mem_used, mem_total) = self.memory_monitor.get_memory_data();
+        let (swap_percent, _, swap_used, swap_total) = self.memory_monitor.get_swap_data();
+
+        let (_, _, rx_rate, tx_rate, total_rx, total_tx) = self.network_monitor.get_network_data();
+
+        let processes = self.process_monitor.get_sorted_processes();
+
+        let temp_data = self.temp_monitor.get_temperature_data();
+        let temperature = if !temp_data.is_empty() {
+            Some(TempMetrics {
+                sensors: temp_data
+                    .iter()
+                    .map(|(name, temp, _)| SensorMetric {
+                        name: name.clone(),
+                        temperature: *temp,
+                    })
+                    .collect(),
+                average: temp_data.iter().map(|(_, t, _)| t).sum::<f32>() / temp_data.len() as f32,
+                max: temp_data.iter().map(|(_, t, _)| *t).fold(0.0, f32::max),
+            })
+        } else {
+            None
+        };
+
+        Metrics {
+            timestamp,
+            cpu: CpuMetrics {
+                cores,
+                average: cpu_avg,
+                limit: self.cpu_monitor.cpu_limit(),
+                times: self.cpu_monitor.times().map(Self::cpu_time_metrics),
+            },
+            memory: MemoryMetrics {
+                total: mem_total,
+                used: mem_used,
+                available: self.memory_monitor.available(),
+                percent: mem_percent,
+                swap_total,
+                swap_used,
+                swap_percent,
+                breakdown: self.memory_monitor.meminfo().map(|info| MemoryBreakdown {
+                    available: info.available,
+                    cached: info.cached,
+                    buffers: info.buffers,
+                    shared: info.shmem,
+                    slab_reclaimable: info.slab_reclaimable,
+                    slab_unreclaimable: info.slab_unreclaimable,
+                    dirty: info.dirty,
+                    writeback: info.writeback,
+                    committed: info.committed,
+                    commit_limit: info.commit_limit,
+                    hugepages_total: info.hugepages_total,
+                    hugepages_free: info.hugepages_free,
+                }),
+                swap: Some(self.swap_metrics()),
+                events: Some(self.memory_event_metrics()),
+            },
+            network: NetworkMetrics {
+                received: total_rx,
+                transmitted: total_tx,
+                rx_rate: rx_rate as f64,
+                tx_rate: tx_rate as f64,
+            },
+            disk: self
+                .disk_monitor
+                .get_filesystems()
+                .iter()
+                .map(|fs| DiskMetrics {
+                    name: fs.device.clone(),
+                    mount_point: fs.mount_point.clone(),
+                    fs_type: fs.fs_type.clone(),
+                    total: fs.total,
+                    used: fs.used,
+                    available: fs.available,
+                    percent: fs.percent(),
+                    inodes_total: fs.inodes_total,
+                    inodes_used: fs.inodes_used,
+                })
+                .collect(),
+            disk_io: self
+                .diskio_monitor
+                .get_disk_io()
+                .iter()
+                .map(|d| DiskIOMetrics {
+                    device: d.name.clone(),
+                    read_rate: d.read_rate,
+                    write_rate: d.write_rate,
+                    read_total: d.read_total,
+                    write_total: d.write_total,
+                    util_percent: d.util_percent,
+                })
+                .collect(),
+            processes: processes
+                .iter()
+                .filter(|p| !p.is_thread)
+                .take(20)
+                .map(|p| ProcessMetrics {
+                    pid: p.pid,
+                    name: p.name.clone(),
+                    user: p.user.clone(),
+                    threads: p.threads,
+                    cgroup: p.cgroup.clone(),
+                    unit: p.unit.clone(),
+                    container: p.container_name.clone().or_else(|| p.container.clone()),
+                    cpu: p.cpu_usage,
+                    memory: p.memory,
+                    memory_percent: (p.memory as f32 / mem_total as f32) * 100.0,
+                    minor_fault_rate: p.minor_fault_rate,
+                    major_fault_rate: p.major_fault_rate,
+                })
+                .collect(),
+            temperature,
+            system: SystemMetrics {
+                hostname: self.system_monitor.hostname(),
+                os: self.system_monitor.os_version(),
+                kernel: self.system_monitor.kernel_version(),
+                uptime: self.system_monitor.uptime(),
+                load_average: self.system_monitor.load_average(),
+            },
+            pressure: self
+                .pressure_monitor
+                .is_available()
+                .then(|| Self::pressure_metrics(self.pressure_monitor.system())),
+            cgroup_pressure: self.pressure_monitor.cgroup().map(Self::pressure_metrics),
+        }
+    }
+
+    fn memory_event_metrics(&self) -> MemoryEventMetrics {
+        let events = self.memory_monitor.events();
+        MemoryEventMetrics {
+            oom_kills: events.oom_kills,
+            oom_kills_since_start: events.oom_kills_since_start,
+            minor_faults: events.minor_faults,
+            major_faults: events.major_faults,
+            pages_scanned: events.pages_scanned,
+            pages_stolen: events.pages_stolen,
+            minor_fault_rate: events.minor_fault_rate,
+            major_fault_rate: events.major_fault_rate,
+            kswapd_scan_rate: events.kswapd_scan_rate,
+            direct_scan_rate: events.direct_scan_rate,
+            steal_rate: events.steal_rate,
+        }
+    }
+
+    fn swap_metrics(&self) -> SwapMetrics {
+        let details = self.memory_monitor.swap_details();
+        let (pages_in_rate, pages_out_rate) = self.memory_monitor.swap_rates();
+        SwapMetrics {
+            devices: details
+                .devices
+                .iter()
+                .map(|d| SwapDeviceMetrics {
+                    name: d.name.clone(),
+                    kind: d.kind.clone(),
+                    size: d.size,
+                    used: d.used,
+                    priority: d.priority,
+                })
+                .collect(),
+            zram: details
+                .zram
+                .iter()
+                .map(|z| ZramMetrics {
+                    name: z.name.clone(),
+                    disksize: z.disksize,
+                    orig_data_size: z.orig_data_size,
+                    compr_data_size: z.compr_data_size,
+                    mem_used_total: z.mem_used_total,
+                    algorithm: z.algorithm.clone(),
+                })
+                .collect(),
+            zswap: details.zswap.as_ref().map(|z| ZswapMetrics {
+                enabled: z.enabled,
+                compressor: z.compressor.clone(),
+                pool_size: z.pool_size,
+                stored: z.stored,
+            }),
+            pages_in_rate,
+            pages_out_rate,
+        }
+    }
+
+    fn pressure_metrics(source: &PressureSource) -> PressureMetrics {
+        let window = |averages: PsiAverages| PsiWindow {
+            avg10: averages.avg10,
+            avg60: averages.avg60,
+            avg300: averages.avg300,
+            total: averages.total,
+        };
+        let metric = |resource| {
+            source.get(resource).map(|pressure| PsiMetric {
+                some: window(pressure.some),
+                full: pressure.full.map(window),
+            })
+        };
+        PressureMetrics {
+            cpu: metric(PressureResource::Cpu),
+            memory: metric(PressureResource::Memory),
+            io: metric(PressureResource::Io),
+        }
+    }
+
+    fn cpu_time_metrics(times: CpuTimeShares) -> CpuTimeMetrics {
+        CpuTimeMetrics {
+            user: times.user,
+            nice: times.nice,
+            system: times.system,
+            idle: times.idle,
+            iowait: times.iowait,
+            irq: times.irq,
+            softirq: times.softirq,
+            steal: times.steal,
+            guest: times.guest,
+        }
+    }
+
+    pub fn draw(&self, frame: &mut Frame) {
+        // Apply blue background to entire frame
+        let full_area = frame.area();
+        let background = Block::default().style(Style::default().bg(Color::Rgb(10, 20, 40)));
+        frame.render_widget(background, full_area);
+
+        let main_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Header
+                Constraint::Min(0),    // Content
+                Constraint::Length(2), // Footer/Status bar
+            ])
+            .split(frame.area());
+
+        // Draw header
+        self.draw_header(frame, main_chunks[0]);
+
+        // Draw footer/status bar
+        self.draw_footer(frame, main_chunks[2]);
+
+        // Draw different content based on current page
+        if let Some(detail) = &self.process_detail {
+            self.draw_process_detail(frame, main_chunks[1], detail);
+        } else {
+            match self.current_page {
+                ViewPage::Overview => self.draw_overview_page(frame, main_chunks[1]),
+                ViewPage::Processes => self.draw_processes_page(frame, main_chunks[1]),
+                ViewPage::Network => self.draw_network_page(frame, main_chunks[1]),
+                ViewPage::Storage => self.draw_storage_page(frame, main_chunks[1]),
+            }
+        }
+
+        if let Some(menu) = &self.signal_menu {
+            self.draw_signal_menu(frame, frame.area(), menu);
+        }
+        if let Some(menu) = &self.priority_menu {
+            self.draw_priority_menu(frame, frame.area(), menu);
+        }
+        if let Some(menu) = &self.affinity_menu {
+            self.draw_affinity_menu(frame, frame.area(), menu);
+        }
+        if let Some(editor) = &self.column_editor {
+            self.draw_column_editor(frame, frame.area(), editor);
+        }
+
+        // Draw help overlay if activated
+        if self.show_help {
+            self.draw_help_overlay(frame, frame.area());
+        }
+    }
+
+    fn draw_overview_page(&self, frame: &mut Frame, area: Rect) {
+        // Adjust layout based on temperature sensor availability
+        let has_temp = self.temp_monitor.has_temperature_sensors();
+        let has_gpu = self.gpu_monitor.is_enabled() && self.gpu_monitor.gpu_count() > 0;
+        let has_npu = self.npu_monitor.is_enabled() && self.npu_monitor.npu_count() > 0;
 
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -459,12 +1455,29 @@ impl App {
         // Middle section: Memory and Swap on same graph
         self.draw_memory(frame, chunks[1]);
 
-        // Bottom section: Left column (Network, Disk, GPU, NPU), Right column (Processes and Temperature)
+        // Bottom section: Left column (Pressure, Network, Disk, GPU, NPU), Right column (Processes and Temperature)
         let bottom_chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
             .split(chunks[2]);
 
+        // Pressure takes a fixed height on top of the left column when the kernel has PSI
+        let left_area = if self.pressure_monitor.is_available() {
+            let rows = if self.pressure_monitor.cgroup().is_some() {
+                6
+            } else {
+                3
+            };
+            let pressure_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(rows + 3), Constraint::Min(0)])
+                .split(bottom_chunks[0]);
+            self.draw_pressure(frame, pressure_chunks[0]);
+            pressure_chunks[1]
+        } else {
+            bottom_chunks[0]
+        };
+
         // Left column: Network, Disk, GPU, NPU (no temperature here)
         match (has_gpu, has_npu) {
             (true, true) => {
@@ -477,7 +1490,7 @@ impl App {
                         Constraint::Percentage(25), // GPU
                         Constraint::Percentage(20), // NPU
                     ])
-                    .split(bottom_chunks[0]);
+                    .split(left_area);
 
                 self.draw_network(frame, left_chunks[0]);
                 self.draw_disk(frame, left_chunks[1]);
@@ -493,7 +1506,7 @@ impl App {
                         Constraint::Percentage(35), // Disk
                         Constraint::Percentage(25), // NPU
                     ])
-                    .split(bottom_chunks[0]);
+                    .split(left_area);
 
                 self.draw_network(frame, left_chunks[0]);
                 self.draw_disk(frame, left_chunks[1]);
@@ -508,7 +1521,7 @@ impl App {
                         Constraint::Percentage(30), // Disk
                         Constraint::Percentage(30), // GPU
                     ])
-                    .split(bottom_chunks[0]);
+                    .split(left_area);
 
                 self.draw_network(frame, left_chunks[0]);
                 self.draw_disk(frame, left_chunks[1]);
@@ -519,7 +1532,7 @@ impl App {
                 let left_chunks = Layout::default()
                     .direction(Direction::Vertical)
                     .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-                    .split(bottom_chunks[0]);
+                    .split(left_area);
 
                 self.draw_network(frame, left_chunks[0]);
                 self.draw_disk(frame, left_chunks[1]);
@@ -542,8 +1555,17 @@ impl App {
     }
 
     fn draw_processes_page(&self, frame: &mut Frame, area: Rect) {
-        // Full-screen process list
-        self.draw_processes(frame, area);
+        // Full-screen process list, with recently exited processes below on request
+        if self.show_exited {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+                .split(area);
+            self.draw_processes(frame, chunks[0]);
+            self.draw_exited(frame, chunks[1]);
+        } else {
+            self.draw_processes(frame, area);
+        }
     }
 
     fn draw_network_page(&self, frame: &mut Frame, area: Rect) {
@@ -551,13 +1573,25 @@ impl App {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Percentage(50), // Network stats
-                Constraint::Percentage(50), // CPU (shows network impact)
+                Constraint::Percentage(45), // Traffic history
+                Constraint::Percentage(55), // Summary and interface list
             ])
             .split(area);
 
-        self.draw_network(frame, chunks[0]);
-        self.draw_cpu(frame, chunks[1]);
+        let bottom_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(chunks[1]);
+
+        let top_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+            .split(chunks[0]);
+
+        self.draw_network_traffic(frame, top_chunks[0]);
+        self.draw_latency(frame, top_chunks[1]);
+        self.draw_network(frame, bottom_chunks[0]);
+        self.draw_interfaces(frame, bottom_chunks[1]);
     }
 
     fn draw_storage_page(&self, frame: &mut Frame, area: Rect) {
@@ -565,8 +1599,8 @@ impl App {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Percentage(50), // Disk usage
-                Constraint::Percentage(50), // Memory (storage related)
+                Constraint::Percentage(40), // Disk usage
+                Constraint::Percentage(60), // Per-device I/O
             ])
             .split(area);
 
@@ -578,7 +1612,7 @@ impl App {
         self.draw_disk(frame, disk_mem_chunks[0]);
         self.draw_memory_gauges(frame, disk_mem_chunks[1]);
 
-        self.draw_memory(frame, chunks[1]);
+        self.draw_disk_io(frame, chunks[1]);
     }
 
     fn draw_header(&self, frame: &mut Frame, area: Rect) {
@@ -601,6 +1635,34 @@ impl App {
             String::new()
         };
 
+        let limits: Vec<String> = self
+            .cpu_monitor
+            .cpu_limit()
+            .map(Self::format_cpus)
+            .into_iter()
+            .chain(
+                self.memory_monitor
+                    .memory_limit()
+                    .map(|bytes| format_bytes(bytes, false)),
+            )
+            .collect();
+        let limit_badge = if limits.is_empty() {
+            String::new()
+        } else {
+            let scope = if self.cpu_monitor.in_container() {
+                "container"
+            } else {
+                "cgroup"
+            };
+            format!(" 📦 {}: {} ", scope, limits.join(" / "))
+        };
+        let oom_kills = self.memory_monitor.events().oom_kills_since_start;
+        let oom_badge = if oom_kills > 0 {
+            format!(" 💀 OOM kills: {} ", oom_kills)
+        } else {
+            String::new()
+        };
+
         let title = vec![Line::from(vec![
             Span::styled(
                 " ⚡ ",
@@ -626,6 +1688,14 @@ impl App {
                 &npu_indicator,
                 Style::default().fg(Color::Rgb(138, 113, 255)),
             ),
+            Span::styled(&limit_badge, Style::default().fg(Color::LightBlue)),
+            Span::styled(
+                &oom_badge,
+                Style::default()
+                    .fg(Color::White)
+                    .bg(Color::Red)
+                    .add_modifier(Modifier::BOLD),
+            ),
             Span::raw(" │ "),
             Span::styled("◆ ", Style::default().fg(Color::Magenta)),
             Span::styled(
@@ -650,7 +1720,7 @@ impl App {
                     .fg(Color::Green)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::raw(": GPU"),
+            Span::raw(": Groups"),
         ])];
 
         let block = Block::default()
@@ -665,13 +1735,48 @@ impl App {
         frame.render_widget(paragraph, area);
     }
 
+    /// "2 CPUs", "1.5 CPUs"
+    fn format_cpus(cpus: f64) -> String {
+        if cpus.fract() == 0.0 {
+            format!("{} CPU{}", cpus, if cpus == 1.0 { "" } else { "s" })
+        } else {
+            format!("{:.1} CPUs", cpus)
+        }
+    }
+
     fn draw_cpu(&self, frame: &mut Frame, area: Rect) {
-        let cpu_data = self.cpu_monitor.get_all_cpu_data();
+        let cpu_data = self.cpu_monitor.cores();
+
+        // Per-core usage and clock, grouped by package, to the right of the chart
+        let area = match Self::core_panel_width(&cpu_data, area) {
+            Some(width) => {
+                let chunks = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Min(0), Constraint::Length(width)])
+                    .split(area);
+                self.draw_cpu_cores(frame, chunks[1], &cpu_data);
+                chunks[0]
+            }
+            None => area,
+        };
+
+        // Aggregate /proc/stat breakdown with its history, between chart and cores
+        let area = match self.cpu_monitor.times() {
+            Some(times) if area.width >= 120 => {
+                let chunks = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Min(0), Constraint::Length(47)])
+                    .split(area);
+                self.draw_cpu_times(frame, chunks[1], &times);
+                chunks[0]
+            }
+            _ => area,
+        };
 
         // Pre-allocate with known capacity to avoid reallocations
         let mut all_data: Vec<Vec<(f64, f64)>> = Vec::with_capacity(cpu_data.len());
 
-        for (_, _, history) in &cpu_data {
+        for CpuCoreInfo { history, .. } in &cpu_data {
             let mut data = Vec::with_capacity(history.len());
             for (x, &y) in history.iter().enumerate() {
                 data.push((x as f64, y as f64));
@@ -683,18 +1788,18 @@ impl App {
             .iter()
             .zip(all_data.iter())
             .enumerate()
-            .map(|(i, ((name, usage, _), data))| {
+            .map(|(i, (core, data))| {
                 // Couleur dynamique selon l'utilisation
-                let color = if *usage > 80.0 {
+                let color = if core.usage > 80.0 {
                     Color::Red
-                } else if *usage > 60.0 {
+                } else if core.usage > 60.0 {
                     Color::Yellow
                 } else {
                     COLORS[i % COLORS.len()]
                 };
 
                 Dataset::default()
-                    .name(name.clone())
+                    .name(format!("CPU{} {:>5.1}%", core.id, core.usage))
                     .marker(symbols::Marker::Braille)
                     .graph_type(ratatui::widgets::GraphType::Line)
                     .style(Style::default().fg(color).add_modifier(Modifier::BOLD))
@@ -703,8 +1808,12 @@ impl App {
             .collect();
 
         // Calculate average CPU usage for title color
-        let avg_cpu =
-            cpu_data.iter().map(|(_, usage, _)| usage).sum::<f32>() / cpu_data.len().max(1) as f32;
+        let avg_cpu = self.cpu_monitor.average();
+        let limit_note = self
+            .cpu_monitor
+            .cpu_limit()
+            .map(|cpus| format!(" of {}", Self::format_cpus(cpus)))
+            .unwrap_or_default();
         let title_color = if avg_cpu > 80.0 {
             Color::Rgb(235, 112, 112) // Red
         } else if avg_cpu > 60.0 {
@@ -732,7 +1841,7 @@ impl App {
                                 .add_modifier(Modifier::BOLD),
                         ),
                         Span::styled(
-                            format!("[{:.1}%]", avg_cpu),
+                            format!("[{:.1}%{}]", avg_cpu, limit_note),
                             Style::default().fg(title_color),
                         ),
                     ])
@@ -783,7 +1892,259 @@ impl App {
         frame.render_widget(chart, area);
     }
 
+    /// Cores grouped by physical package, siblings of a physical core next to each other
+    fn core_groups(cores: &[CpuCoreInfo]) -> Vec<(Option<u32>, Vec<&CpuCoreInfo>)> {
+        let mut groups: BTreeMap<Option<u32>, Vec<&CpuCoreInfo>> = BTreeMap::new();
+        for core in cores {
+            groups.entry(core.topology.package).or_default().push(core);
+        }
+        groups
+            .into_iter()
+            .map(|(package, mut members)| {
+                members.sort_by_key(|c| (c.topology.core, c.id));
+                (package, members)
+            })
+            .collect()
+    }
+
+    /// Narrowest panel whose columns fit every package in the chart's height; None when the
+    /// chart would get too narrow
+    fn core_panel_width(cores: &[CpuCoreInfo], area: Rect) -> Option<u16> {
+        const CELL_WIDTH: u16 = 22;
+        if area.width < 100 || cores.is_empty() {
+            return None;
+        }
+        let groups = Self::core_groups(cores);
+        let inner_height = area.height.saturating_sub(2) as usize;
+        let max_columns = (area.width / 2 / CELL_WIDTH).max(1) as usize;
+        let columns = (1..=max_columns)
+            .find(|columns| {
+                let rows: usize = groups
+                    .iter()
+                    .map(|(_, members)| 1 + members.len().div_ceil(*columns))
+                    .sum();
+                rows <= inner_height
+            })
+            .unwrap_or(max_columns);
+        Some((columns as u16 * CELL_WIDTH).max(32) + 2)
+    }
+
+    fn draw_cpu_cores(&self, frame: &mut Frame, area: Rect, cores: &[CpuCoreInfo]) {
+        const CELL_WIDTH: usize = 22;
+        let columns = (area.width.saturating_sub(2) as usize / CELL_WIDTH).max(1);
+        let dim = Style::default().fg(Color::DarkGray);
+        let ghz = |mhz: u64| format!("{:.1}", mhz as f64 / 1000.0);
+
+        let mut lines = Vec::new();
+        for (package, members) in Self::core_groups(cores) {
+            // Package header: physical cores / threads, governor and policy range
+            let physical: HashSet<Option<u32>> = members.iter().map(|c| c.topology.core).collect();
+            let mut header = vec![
+                Span::styled(
+                    package.map_or("CPUs".to_string(), |p| format!("Pkg {}", p)),
+                    Style::default()
+                        .fg(Color::Rgb(61, 123, 70))
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!(" {}C/{}T", physical.len(), members.len()), dim),
+            ];
+            if let Some(governor) = members.iter().find_map(|c| c.frequency.governor.as_ref()) {
+                header.push(Span::styled(format!(" {}", governor), dim));
+            }
+            let min = members.iter().filter_map(|c| c.frequency.min).min();
+            let max = members.iter().filter_map(|c| c.frequency.max).max();
+            if let (Some(min), Some(max)) = (min, max) {
+                header.push(Span::styled(format!(" {}-{} GHz", ghz(min), ghz(max)), dim));
+            }
+            lines.push(Line::from(header));
+
+            for row in members.chunks(columns) {
+                let spans: Vec<Span> = row
+                    .iter()
+                    .flat_map(|core| {
+                        let usage_color = if core.usage > 80.0 {
+                            Color::Red
+                        } else if core.usage > 60.0 {
+                            Color::Yellow
+                        } else {
+                            Color::White
+                        };
+                        let core_type = core.topology.core_type.map_or(" ", |t| t.letter());
+                        let mut cell = vec![
+                            Span::styled(format!("{:>3}", core.id), dim),
+                            Span::styled(core_type, Style::default().fg(Color::Cyan)),
+                            Span::raw(" "),
+                        ];
+                        match &core.times {
+                            Some(times) => cell.extend(Self::cpu_time_bar(times, 5)),
+                            None => cell.push(Span::raw("     ")),
+                        }
+                        cell.push(Span::styled(
+                            format!(" {:>3.0}%", core.usage),
+                            Style::default().fg(usage_color),
+                        ));
+                        cell.push(Span::styled(
+                            format!(
+                                " {:>4}G ",
+                                core.frequency.current.map(ghz).unwrap_or_default()
+                            ),
+                            Style::default().fg(Color::Rgb(72, 151, 216)),
+                        ));
+                        cell
+                    })
+                    .collect();
+                lines.push(Line::from(spans));
+            }
+        }
+
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .title(vec![
+                    Span::styled("▦ ", Style::default().fg(Color::Yellow)),
+                    Span::styled("Cores ", Style::default().add_modifier(Modifier::BOLD)),
+                ])
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Rgb(61, 123, 70)))
+                .border_type(ratatui::widgets::BorderType::Rounded),
+        );
+        frame.render_widget(paragraph, area);
+    }
+
+    /// Share per kind of CPU time, then a stacked column per refresh, newest on the right
+    fn draw_cpu_times(&self, frame: &mut Frame, area: Rect, times: &CpuTimeShares) {
+        let inner_width = area.width.saturating_sub(2) as usize;
+        let dim = Style::default().fg(Color::DarkGray);
+
+        let mut lines: Vec<Line> = CpuTimeKind::ALL
+            .chunks(3)
+            .map(|kinds| {
+                let spans: Vec<Span> = kinds
+                    .iter()
+                    .flat_map(|&kind| {
+                        let share = times.get(kind);
+                        // Steal and iowait are the ones worth calling out
+                        let value_style = match kind {
+                            CpuTimeKind::Steal | CpuTimeKind::Iowait if share >= 10.0 => {
+                                Style::default().fg(Color::Red)
+                            }
+                            CpuTimeKind::Steal | CpuTimeKind::Iowait if share >= 1.0 => {
+                                Style::default().fg(Color::Yellow)
+                            }
+                            _ => Style::default(),
+                        };
+                        vec![
+                            Span::styled("■ ", Style::default().fg(Self::cpu_time_color(kind))),
+                            Span::styled(format!("{:<7}", kind.label()), dim),
+                            Span::styled(format!("{:>5.1} ", share), value_style),
+                        ]
+                    })
+                    .collect();
+                Line::from(spans)
+            })
+            .collect();
+
+        let history = self.cpu_monitor.time_history();
+        let height = area.height.saturating_sub(2) as usize;
+        let rows = height.saturating_sub(lines.len());
+        let skip = history.len().saturating_sub(inner_width);
+        let columns: Vec<&CpuTimeShares> = history.iter().skip(skip).collect();
+        for row in 0..rows {
+            // Percent range this row covers, top row highest
+            let low = (rows - row - 1) as f64 / rows as f64 * 100.0;
+            let middle = low + 50.0 / rows as f64;
+            let spans: Vec<Span> = columns
+                .iter()
+                .map(|shares| {
+                    let mut cumulative = 0.0;
+                    let kind = CpuTimeKind::ALL.into_iter().find(|&kind| {
+                        cumulative += shares.get(kind);
+                        middle < cumulative
+                    });
+                    match kind {
+                        Some(kind) => {
+                            Span::styled("█", Style::default().fg(Self::cpu_time_color(kind)))
+                        }
+                        None => Span::raw(" "),
+                    }
+                })
+                .collect();
+            lines.push(Line::from(spans).alignment(ratatui::layout::Alignment::Right));
+        }
+
+        let busy = 100.0 - times.idle - times.iowait;
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .title(vec![
+                    Span::styled("◔ ", Style::default().fg(Color::Yellow)),
+                    Span::styled("CPU Time ", Style::default().add_modifier(Modifier::BOLD)),
+                    Span::styled(format!("[busy {:.1}%]", busy), dim),
+                ])
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Rgb(61, 123, 70)))
+                .border_type(ratatui::widgets::BorderType::Rounded),
+        );
+        frame.render_widget(paragraph, area);
+    }
+
+    /// `width` cells split by kind of CPU time; idle time is left dotted
+    fn cpu_time_bar(times: &CpuTimeShares, width: usize) -> Vec<Span<'static>> {
+        let mut bar = Vec::new();
+        let mut cumulative = 0.0;
+        let mut drawn = 0;
+        for kind in CpuTimeKind::ALL {
+            cumulative += times.get(kind);
+            let end = (cumulative / 100.0 * width as f64).round() as usize;
+            let cells = end.min(width).saturating_sub(drawn);
+            drawn += cells;
+            bar.push(Span::styled(
+                "█".repeat(cells),
+                Style::default().fg(Self::cpu_time_color(kind)),
+            ));
+        }
+        bar.push(Span::styled(
+            "·".repeat(width - drawn),
+            Style::default().fg(Color::DarkGray),
+        ));
+        bar
+    }
+
+    fn cpu_time_color(kind: CpuTimeKind) -> Color {
+        match kind {
+            CpuTimeKind::User => Color::Rgb(144, 224, 163),
+            CpuTimeKind::Nice => Color::Rgb(97, 175, 239),
+            CpuTimeKind::System => Color::Rgb(235, 112, 112),
+            CpuTimeKind::Iowait => Color::Rgb(160, 160, 160),
+            CpuTimeKind::Irq => Color::Rgb(255, 199, 69),
+            CpuTimeKind::Softirq => Color::Rgb(198, 120, 221),
+            CpuTimeKind::Steal => Color::Rgb(86, 182, 194),
+            CpuTimeKind::Guest => Color::Rgb(245, 166, 35),
+        }
+    }
+
     fn draw_memory(&self, frame: &mut Frame, area: Rect) {
+        // The /proc/meminfo breakdown and swap devices sit to the right of the chart when
+        // there is room
+        let meminfo = self.memory_monitor.meminfo().filter(|_| area.width >= 100);
+        let show_swap = !self.memory_monitor.swap_details().is_empty() && area.width >= 150;
+        let mut constraints = vec![Constraint::Min(0)];
+        if meminfo.is_some() {
+            constraints.push(Constraint::Length(56));
+        }
+        if show_swap {
+            constraints.push(Constraint::Length(50));
+        }
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(constraints)
+            .split(area);
+        if let Some(info) = meminfo {
+            self.draw_memory_breakdown(frame, chunks[1], info);
+        }
+        if show_swap {
+            self.draw_swap(frame, chunks[chunks.len() - 1]);
+        }
+        let area = chunks[0];
+
         let (mem_percent, mem_history, _, _) = self.memory_monitor.get_memory_data();
         let (swap_percent, swap_history, _, _) = self.memory_monitor.get_swap_data();
 
@@ -805,6 +2166,16 @@ impl App {
             Color::Rgb(255, 199, 69) // Yellow
         };
 
+        // Major faults and direct reclaim mean tasks are waiting on memory
+        let events = self.memory_monitor.events();
+        let rate_style = |rate: f64, color: Color| {
+            if rate >= 1.0 {
+                Style::default().fg(color)
+            } else {
+                Style::default()
+            }
+        };
+
         let swap_color = if swap_percent > 85.0 {
             Color::Rgb(208, 92, 92)
         } else if swap_percent > 70.0 {
@@ -840,9 +2211,25 @@ impl App {
                                 .add_modifier(Modifier::BOLD),
                         ),
                         Span::styled(
-                            format!("[{:.1}%]", mem_percent),
+                            format!("[{:.1}%] ", mem_percent),
                             Style::default().fg(mem_color),
                         ),
+                        Span::styled("faults ", Style::default().fg(Color::DarkGray)),
+                        Span::raw(format!("{:.0}/s", events.minor_fault_rate)),
+                        Span::styled(" major ", Style::default().fg(Color::DarkGray)),
+                        Span::styled(
+                            format!("{:.0}/s", events.major_fault_rate),
+                            rate_style(events.major_fault_rate, Color::Yellow),
+                        ),
+                        Span::styled(" · reclaim ", Style::default().fg(Color::DarkGray)),
+                        Span::raw(format!("{:.0}/s", events.steal_rate)),
+                        Span::styled(" scan ", Style::default().fg(Color::DarkGray)),
+                        Span::raw(format!("{:.0}/s", events.kswapd_scan_rate)),
+                        Span::styled(" direct ", Style::default().fg(Color::DarkGray)),
+                        Span::styled(
+                            format!("{:.0}/s ", events.direct_scan_rate),
+                            rate_style(events.direct_scan_rate, Color::Red),
+                        ),
                     ])
                     .borders(Borders::ALL)
                     .border_style(Style::default().fg(Color::Rgb(138, 136, 46)))
@@ -891,6 +2278,226 @@ impl App {
         frame.render_widget(chart, area);
     }
 
+    fn draw_memory_breakdown(&self, frame: &mut Frame, area: Rect, info: &MemInfo) {
+        let inner_width = area.width.saturating_sub(2) as usize;
+        let total = info.total.max(1) as f64;
+        let dim = Style::default().fg(Color::DarkGray);
+
+        // Stacked bar: each segment rounded at its cumulative end so the bar fills exactly
+        let mut bar = Vec::new();
+        let mut cumulative = 0;
+        let mut drawn = 0;
+        for component in MemComponent::ALL {
+            cumulative += info.component(component);
+            let end = (cumulative as f64 / total * inner_width as f64).round() as usize;
+            let cells = end.saturating_sub(drawn);
+            drawn += cells;
+            let symbol = if component == MemComponent::Free {
+                "░"
+            } else {
+                "█"
+            };
+            bar.push(Span::styled(
+                symbol.repeat(cells),
+                Style::default().fg(Self::mem_component_color(component)),
+            ));
+        }
+        let mut lines = vec![Line::from(bar)];
+
+        // Colored key, size, share and history per component
+        let graph_width = inner_width.saturating_sub(2 + 8 + 11 + 7 + 1) as u16;
+        for component in MemComponent::ALL {
+            let color = Self::mem_component_color(component);
+            let bytes = info.component(component);
+            lines.push(Line::from(vec![
+                Span::styled("■ ", Style::default().fg(color)),
+                Span::raw(format!("{:<8}", component.label())),
+                Span::raw(format!("{:>11}", format_bytes(bytes, false))),
+                Span::styled(format!("{:>6.1}% ", bytes as f64 / total * 100.0), dim),
+                // Scaled to all of memory so the rows compare like the bar segments
+                Span::styled(
+                    self.sparkline(
+                        self.memory_monitor.breakdown_history(component),
+                        100.0,
+                        graph_width,
+                    ),
+                    Style::default().fg(color),
+                ),
+            ]));
+        }
+
+        lines.push(Line::from(vec![
+            Span::styled("Avail ", dim),
+            Span::raw(format_bytes(info.available, false)),
+            Span::styled("  Dirty ", dim),
+            Span::raw(format_bytes(info.dirty, false)),
+            Span::styled("  Wback ", dim),
+            Span::raw(format_bytes(info.writeback, false)),
+        ]));
+
+        // Committed past the limit is only enforced with vm.overcommit_memory=2
+        let commit_style = if info.commit_limit > 0 && info.committed > info.commit_limit {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default()
+        };
+        // Shown in the bottom border so the panel fits the Overview's memory row
+        let mut commit = vec![
+            Span::styled(" Commit ", dim),
+            Span::styled(format_bytes(info.committed, false), commit_style),
+            Span::styled(" / ", dim),
+            Span::raw(format_bytes(info.commit_limit, false)),
+        ];
+        if info.hugepages_total > 0 {
+            commit.push(Span::styled("  Huge free ", dim));
+            commit.push(Span::raw(format!(
+                "{} / {} ",
+                format_bytes(info.hugepages_free, false),
+                format_bytes(info.hugepages_total, false)
+            )));
+        } else {
+            commit.push(Span::raw(" "));
+        }
+
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .title(vec![
+                    Span::styled("▤ ", Style::default().fg(Color::Rgb(245, 166, 35))),
+                    Span::styled(
+                        "Breakdown ",
+                        Style::default()
+                            .fg(Color::Rgb(138, 136, 46))
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(format_bytes(info.total, false), dim),
+                ])
+                .title_bottom(commit)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Rgb(138, 136, 46)))
+                .border_type(ratatui::widgets::BorderType::Rounded),
+        );
+        frame.render_widget(paragraph, area);
+    }
+
+    fn draw_swap(&self, frame: &mut Frame, area: Rect) {
+        let details = self.memory_monitor.swap_details();
+        let (pages_in, pages_out) = self.memory_monitor.swap_rates();
+        let (in_history, out_history) = self.memory_monitor.swap_rate_history();
+        let dim = Style::default().fg(Color::DarkGray);
+        let graph_width = area.width.saturating_sub(2 + 4 + 8 + 6);
+
+        let rate_line = |label: &'static str, rate: f64, history, color| {
+            Line::from(vec![
+                Span::styled(label, dim),
+                Span::styled(format!("{:>8.0}", rate), Style::default().fg(color)),
+                Span::styled(" pg/s ", dim),
+                Span::styled(
+                    self.sparkline(history, 10.0, graph_width),
+                    Style::default().fg(color),
+                ),
+            ])
+        };
+        let mut lines = vec![
+            rate_line("In  ", pages_in, in_history, Color::Rgb(97, 175, 239)),
+            rate_line("Out ", pages_out, out_history, Color::Rgb(245, 166, 35)),
+        ];
+
+        let ratio_style = Style::default().fg(Color::Rgb(144, 224, 163));
+        let device_line =
+            |name: &str, kind: &str, used: u64, size: u64, zram: Option<&ZramDevice>| {
+                let mut spans = vec![
+                    Span::raw(format!(
+                        "{:<12.12} ",
+                        name.rsplit('/').next().unwrap_or(name)
+                    )),
+                    Span::styled(format!("{:<4.4} ", kind), dim),
+                    Span::raw(format!("{:>10}", format_bytes(used, false))),
+                    Span::styled(" / ", dim),
+                    Span::raw(format!("{:<10}", format_bytes(size, false))),
+                ];
+                if let Some(ratio) = zram.and_then(ZramDevice::ratio) {
+                    spans.push(Span::styled(format!(" {:.1}x", ratio), ratio_style));
+                }
+                Line::from(spans)
+            };
+        for device in &details.devices {
+            let zram = details.zram(device);
+            let kind = if zram.is_some() { "zram" } else { &device.kind };
+            lines.push(device_line(
+                &device.name,
+                kind,
+                device.used,
+                device.size,
+                zram,
+            ));
+        }
+        // zram devices used for something other than swap, such as /tmp
+        for zram in &details.zram {
+            if !details
+                .devices
+                .iter()
+                .any(|d| details.zram(d) == Some(zram))
+            {
+                lines.push(device_line(
+                    &zram.name,
+                    "zram",
+                    zram.orig_data_size,
+                    zram.disksize,
+                    Some(zram),
+                ));
+            }
+        }
+
+        if let Some(zswap) = &details.zswap {
+            let mut spans = vec![Span::styled("zswap ", dim)];
+            if zswap.enabled {
+                spans.push(Span::styled("on", ratio_style));
+                if let Some(compressor) = &zswap.compressor {
+                    spans.push(Span::raw(format!(" {}", compressor)));
+                }
+                if let Some(pool) = zswap.pool_size {
+                    spans.push(Span::styled(" · pool ", dim));
+                    spans.push(Span::raw(format_bytes(pool, false)));
+                }
+                if let Some(ratio) = zswap.ratio() {
+                    spans.push(Span::styled(format!(" {:.1}x", ratio), ratio_style));
+                }
+            } else {
+                spans.push(Span::styled("off", dim));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .title(vec![
+                    Span::styled("⇅ ", Style::default().fg(Color::Rgb(245, 166, 35))),
+                    Span::styled(
+                        "Swap ",
+                        Style::default()
+                            .fg(Color::Rgb(138, 136, 46))
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(format!("[{}]", details.devices.len()), dim),
+                ])
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Rgb(138, 136, 46)))
+                .border_type(ratatui::widgets::BorderType::Rounded),
+        );
+        frame.render_widget(paragraph, area);
+    }
+
+    fn mem_component_color(component: MemComponent) -> Color {
+        match component {
+            MemComponent::Used => Color::Rgb(144, 224, 163),
+            MemComponent::Shared => Color::Rgb(198, 120, 221),
+            MemComponent::Buffers => Color::Rgb(97, 175, 239),
+            MemComponent::Cached => Color::Rgb(255, 199, 69),
+            MemComponent::Slab => Color::Rgb(245, 166, 35),
+            MemComponent::Free => Color::DarkGray,
+        }
+    }
+
     fn draw_memory_gauges(&self, frame: &mut Frame, area: Rect) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -1020,7 +2627,7 @@ impl App {
         let tx_total = format_bytes(total_tx, false);
 
         // Get ping latency and interface name
-        let ping_latency = self.network_monitor.get_ping_latency();
+        let ping_latency = self.latency_monitor.current();
         let interface = self.network_monitor.get_active_interface();
 
         // Calculate max speed from current rates (keep track of peaks)
@@ -1114,8 +2721,16 @@ impl App {
                 Style::default().fg(ping_color).add_modifier(Modifier::BOLD),
             ));
         } else {
+            let placeholder = if self.latency_monitor.is_enabled() {
+                "--- ms"
+            } else {
+                "off"
+            };
             info_line.push(Span::styled("⚡ ", Style::default().fg(Color::DarkGray)));
-            info_line.push(Span::styled("--- ms", Style::default().fg(Color::DarkGray)));
+            info_line.push(Span::styled(
+                placeholder,
+                Style::default().fg(Color::DarkGray),
+            ));
         }
 
         text.push(Line::from(info_line));
@@ -1135,265 +2750,1849 @@ impl App {
         frame.render_widget(paragraph, area);
     }
 
-    fn draw_disk(&self, frame: &mut Frame, area: Rect) {
-        let (percent, used, total) = self.disk_monitor.get_disk_data();
+    fn draw_network_traffic(&self, frame: &mut Frame, area: Rect) {
+        let (rx_history, tx_history, rx_sec, tx_sec, _, _) =
+            self.network_monitor.get_network_data();
+
+        let max_rate = rx_history
+            .iter()
+            .chain(tx_history.iter())
+            .copied()
+            .max()
+            .unwrap_or(0)
+            .max(1024);
+
+        let rx_data: Vec<(f64, f64)> = rx_history
+            .iter()
+            .enumerate()
+            .map(|(x, &y)| (x as f64, y as f64))
+            .collect();
+        let tx_data: Vec<(f64, f64)> = tx_history
+            .iter()
+            .enumerate()
+            .map(|(x, &y)| (x as f64, y as f64))
+            .collect();
+
+        let datasets = vec![
+            Dataset::default()
+                .name(format!("▼ {}/s", format_bytes(rx_sec, false)))
+                .marker(symbols::Marker::Braille)
+                .graph_type(ratatui::widgets::GraphType::Line)
+                .style(
+                    Style::default()
+                        .fg(Color::Blue)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .data(&rx_data),
+            Dataset::default()
+                .name(format!("▲ {}/s", format_bytes(tx_sec, false)))
+                .marker(symbols::Marker::Braille)
+                .graph_type(ratatui::widgets::GraphType::Line)
+                .style(
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .data(&tx_data),
+        ];
+
+        let chart = Chart::new(datasets)
+            .block(
+                Block::default()
+                    .title(vec![
+                        Span::styled("🌐 ", Style::default().fg(Color::Cyan)),
+                        Span::styled("Traffic ", Style::default().add_modifier(Modifier::BOLD)),
+                        Span::styled(
+                            self.network_monitor.get_active_interface().to_string(),
+                            Style::default()
+                                .fg(Color::Yellow)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(" [", Style::default().fg(Color::DarkGray)),
+                        Span::styled("i", Style::default().fg(Color::Yellow)),
+                        Span::styled("]", Style::default().fg(Color::DarkGray)),
+                        Span::raw("Interface"),
+                    ])
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .border_type(ratatui::widgets::BorderType::Rounded),
+            )
+            .x_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([0.0, 60.0]),
+            )
+            .y_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([0.0, max_rate as f64])
+                    .labels(vec![
+                        Span::raw("0"),
+                        Span::raw(format!("{}/s", format_bytes(max_rate, false))),
+                    ]),
+            )
+            .legend_position(Some(ratatui::widgets::LegendPosition::TopLeft));
+
+        frame.render_widget(chart, area);
+    }
+
+    fn draw_latency(&self, frame: &mut Frame, area: Rect) {
+        let history = self.latency_monitor.history();
+        let max_latency = history.iter().flatten().fold(10.0_f32, |a, &b| a.max(b)) as f64 * 1.2;
+
+        // Answered probes as a line, lost probes as markers pinned to the top of the chart
+        let latency_data: Vec<(f64, f64)> = history
+            .iter()
+            .enumerate()
+            .filter_map(|(x, s)| s.map(|ms| (x as f64, ms as f64)))
+            .collect();
+        let loss_data: Vec<(f64, f64)> = history
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.is_none())
+            .map(|(x, _)| (x as f64, max_latency))
+            .collect();
 
-        let disk_color = if percent > 90.0 {
+        let loss = self.latency_monitor.loss_percent();
+        let loss_color = if loss > 10.0 {
             Color::Red
-        } else if percent > 80.0 {
+        } else if loss > 0.0 {
             Color::Yellow
         } else {
-            COLORS[5]
+            Color::Green
         };
 
-        // Create a compact horizontal bar
-        let available_width = area.width.saturating_sub(6).max(20) as usize;
-        let bar_length = available_width.min(40);
-        let filled = ((percent / 100.0 * bar_length as f32) as usize).min(bar_length);
-        let mut bar = String::new();
-        for i in 0..bar_length {
-            if i < filled {
-                bar.push('█');
-            } else if i == filled {
-                bar.push('▓');
-            } else {
-                bar.push('░');
-            }
-        }
+        let datasets = vec![
+            Dataset::default()
+                .name(match self.latency_monitor.average() {
+                    Some(avg) => format!("avg {:.1} ms", avg),
+                    None => "avg --- ms".to_string(),
+                })
+                .marker(symbols::Marker::Braille)
+                .graph_type(ratatui::widgets::GraphType::Line)
+                .style(Style::default().fg(Color::Yellow))
+                .data(&latency_data),
+            Dataset::default()
+                .name(format!("loss {:.0}%", loss))
+                .marker(symbols::Marker::Dot)
+                .graph_type(ratatui::widgets::GraphType::Scatter)
+                .style(Style::default().fg(loss_color))
+                .data(&loss_data),
+        ];
 
-        let available = total.saturating_sub(used);
+        let target = match (
+            self.latency_monitor.is_enabled(),
+            self.latency_monitor.last_target(),
+        ) {
+            (false, _) => "disabled".to_string(),
+            (true, Some(target)) => format!("{} {}", self.latency_monitor.mode().name(), target),
+            (true, None) => "waiting...".to_string(),
+        };
 
-        let lines = vec![
-            Line::from(""),
+        let chart = Chart::new(datasets)
+            .block(
+                Block::default()
+                    .title(vec![
+                        Span::styled("⚡ ", Style::default().fg(Color::Yellow)),
+                        Span::styled("Latency ", Style::default().add_modifier(Modifier::BOLD)),
+                        Span::styled(target, Style::default().fg(Color::DarkGray)),
+                    ])
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .border_type(ratatui::widgets::BorderType::Rounded),
+            )
+            .x_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([0.0, 60.0]),
+            )
+            .y_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([0.0, max_latency])
+                    .labels(vec![
+                        Span::raw("0"),
+                        Span::raw(format!("{:.0}ms", max_latency)),
+                    ]),
+            )
+            .legend_position(Some(ratatui::widgets::LegendPosition::TopLeft))
+            .hidden_legend_constraints((Constraint::Ratio(1, 1), Constraint::Ratio(1, 1)));
+
+        frame.render_widget(chart, area);
+    }
+
+    fn draw_pressure(&self, frame: &mut Frame, area: Rect) {
+        // Resource + six averages, with one column of spacing between each
+        const FIXED_WIDTH: u16 = 8 + 6 * 6 + 7;
+        let graph_width = area.width.saturating_sub(2 + FIXED_WIDTH);
+        let value = |avg: f64| {
+            let color = if avg >= 10.0 {
+                Color::Red
+            } else if avg >= 1.0 {
+                Color::Yellow
+            } else {
+                Color::DarkGray
+            };
+            Span::styled(format!("{:>6.2}", avg), Style::default().fg(color))
+        };
+        let averages = |avg: Option<PsiAverages>| -> [Span<'static>; 3] {
+            match avg {
+                Some(a) => [value(a.avg10), value(a.avg60), value(a.avg300)],
+                None => [
+                    Span::raw(format!("{:>6}", "-")),
+                    Span::raw(format!("{:>6}", "-")),
+                    Span::raw(format!("{:>6}", "-")),
+                ],
+            }
+        };
+
+        let sources = std::iter::once(("", self.pressure_monitor.system()))
+            .chain(self.pressure_monitor.cgroup().map(|c| ("cg ", c)));
+        let rows: Vec<Row> = sources
+            .flat_map(|(prefix, source)| {
+                PressureResource::ALL
+                    .into_iter()
+                    .map(move |resource| (prefix, source, resource))
+            })
+            .enumerate()
+            .map(|(i, (prefix, source, resource))| {
+                let style = if i % 2 == 0 {
+                    Style::default()
+                } else {
+                    Style::default().bg(Color::Rgb(20, 20, 30))
+                };
+                let pressure = source.get(resource);
+                let mut cells = vec![Span::raw(format!("{}{}", prefix, resource.label()))];
+                cells.extend(averages(pressure.map(|p| p.some)));
+                cells.extend(averages(pressure.and_then(|p| p.full)));
+                cells.push(Span::styled(
+                    self.sparkline(source.some_history(resource), 10.0, graph_width),
+                    Style::default().fg(Color::Magenta),
+                ));
+                Row::new(cells).style(style)
+            })
+            .collect();
+
+        let mut title = vec![
+            Span::styled("⏱ ", Style::default().fg(Color::Magenta)),
+            Span::styled("Pressure ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(
+                "% of time stalled · some / full",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ];
+        if self.pressure_monitor.cgroup().is_some() {
+            title.push(Span::styled(
+                " · cg = container",
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(8),
+                Constraint::Length(6),
+                Constraint::Length(6),
+                Constraint::Length(6),
+                Constraint::Length(6),
+                Constraint::Length(6),
+                Constraint::Length(6),
+                Constraint::Min(0),
+            ],
+        )
+        .header(
+            Row::new(vec![
+                "", "Some10", "60s", "300s", "Full10", "60s", "300s", "Some 10s",
+            ])
+            .style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+                    .add_modifier(Modifier::UNDERLINED),
+            ),
+        )
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .border_type(ratatui::widgets::BorderType::Rounded),
+        );
+
+        frame.render_widget(table, area);
+    }
+
+    fn draw_interfaces(&self, frame: &mut Frame, area: Rect) {
+        let selected = self.network_monitor.selected_interface();
+
+        let rows: Vec<Row> = self
+            .network_monitor
+            .get_interfaces()
+            .iter()
+            .map(|iface| {
+                let is_selected = selected == Some(iface.name.as_str());
+                let state_color = if iface.is_up() {
+                    Color::Green
+                } else {
+                    Color::Red
+                };
+                let errors = iface.rx_errors + iface.tx_errors;
+                let drops = iface.rx_dropped + iface.tx_dropped;
+
+                Row::new(vec![
+                    Span::raw(if is_selected { "▶" } else { " " }),
+                    Span::styled(
+                        iface.name.clone(),
+                        Style::default()
+                            .fg(Color::White)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(iface.oper_state.clone(), Style::default().fg(state_color)),
+                    Span::styled(
+                        format!("{}/s", format_bytes(iface.rx_rate, false)),
+                        Style::default().fg(Color::Blue),
+                    ),
+                    Span::styled(
+                        format!("{}/s", format_bytes(iface.tx_rate, false)),
+                        Style::default().fg(Color::Green),
+                    ),
+                    Span::styled(
+                        format!("{}/{}", errors, drops),
+                        Style::default().fg(if errors + drops > 0 {
+                            Color::Yellow
+                        } else {
+                            Color::DarkGray
+                        }),
+                    ),
+                    Span::raw(iface.mtu.map(|m| m.to_string()).unwrap_or_default()),
+                    Span::raw(
+                        iface
+                            .speed_mbps
+                            .map(|s| format!("{}M", s))
+                            .unwrap_or_else(|| "-".to_string()),
+                    ),
+                    Span::styled(
+                        iface.addresses.first().cloned().unwrap_or_default(),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                ])
+                .style(if is_selected {
+                    Style::default().bg(Color::Rgb(50, 50, 80))
+                } else {
+                    Style::default()
+                })
+            })
+            .collect();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(2),
+                Constraint::Length(12),
+                Constraint::Length(8),
+                Constraint::Length(13),
+                Constraint::Length(13),
+                Constraint::Length(8),
+                Constraint::Length(6),
+                Constraint::Length(7),
+                Constraint::Min(15),
+            ],
+        )
+        .header(
+            Row::new(vec![
+                "",
+                "Interface",
+                "State",
+                "Rx",
+                "Tx",
+                "Err/Drop",
+                "MTU",
+                "Speed",
+                "Address",
+            ])
+            .style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+                    .add_modifier(Modifier::UNDERLINED),
+            ),
+        )
+        .block(
+            Block::default()
+                .title(vec![
+                    Span::styled("◆ ", Style::default().fg(Color::Yellow)),
+                    Span::styled("Interfaces", Style::default().add_modifier(Modifier::BOLD)),
+                ])
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .border_type(ratatui::widgets::BorderType::Rounded),
+        );
+
+        frame.render_widget(table, area);
+    }
+
+    fn draw_disk(&self, frame: &mut Frame, area: Rect) {
+        let filesystems = self.disk_monitor.get_filesystems();
+        let warning = self.config.thresholds.disk_warning;
+
+        let rows: Vec<Row> = filesystems
+            .iter()
+            .map(|fs| {
+                let percent = fs.percent();
+                let disk_color = if percent > 90.0 {
+                    Color::Red
+                } else if percent > warning {
+                    Color::Yellow
+                } else {
+                    Color::Green
+                };
+
+                // Compact 8-cell usage bar
+                let filled = ((percent / 100.0 * 8.0) as usize).min(8);
+                let bar = format!("{}{}", "█".repeat(filled), "░".repeat(8 - filled));
+
+                let mount = if fs.bind_mounts.is_empty() {
+                    fs.mount_point.clone()
+                } else {
+                    format!("{} (+{})", fs.mount_point, fs.bind_mounts.len())
+                };
+
+                Row::new(vec![
+                    Span::styled(mount, Style::default().fg(Color::White)),
+                    Span::styled(fs.fs_type.clone(), Style::default().fg(Color::DarkGray)),
+                    Span::styled(
+                        format_bytes(fs.total, true),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    Span::raw(format_bytes(fs.used, true)),
+                    Span::styled(
+                        format_bytes(fs.available, true),
+                        Style::default().fg(Color::Green),
+                    ),
+                    Span::styled(bar, Style::default().fg(disk_color)),
+                    Span::styled(
+                        format!("{:>5.1}%", percent),
+                        Style::default().fg(disk_color).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!("{:>3.0}%", fs.inode_percent()),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ])
+            })
+            .collect();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Min(10),
+                Constraint::Length(6),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(8),
+                Constraint::Length(6),
+                Constraint::Length(5),
+            ],
+        )
+        .header(
+            Row::new(vec![
+                "Mount", "Type", "Size", "Used", "Avail", "", "Use%", "Inode",
+            ])
+            .style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+                    .add_modifier(Modifier::UNDERLINED),
+            ),
+        )
+        .block(
+            Block::default()
+                .title(vec![
+                    Span::styled("💿 ", Style::default().fg(Color::Magenta)),
+                    Span::styled("Storage ", Style::default().add_modifier(Modifier::BOLD)),
+                    Span::styled("[", Style::default().fg(Color::DarkGray)),
+                    Span::styled("s", Style::default().fg(Color::Yellow)),
+                    Span::styled("]", Style::default().fg(Color::DarkGray)),
+                    Span::raw(format!("Sort: {} ", self.disk_monitor.sort_order().name())),
+                ])
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .border_type(ratatui::widgets::BorderType::Rounded),
+        );
+
+        frame.render_widget(table, area);
+    }
+
+    fn draw_disk_io(&self, frame: &mut Frame, area: Rect) {
+        let devices = self.diskio_monitor.get_disk_io();
+
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+            .split(area);
+
+        // Left: per-device table with partitions indented under their disk
+        let mut rows = Vec::new();
+        for device in &devices {
+            let util_color = if device.util_percent > 90.0 {
+                Color::Red
+            } else if device.util_percent > 60.0 {
+                Color::Yellow
+            } else {
+                Color::Green
+            };
+
+            rows.push(Row::new(vec![
+                Span::styled(
+                    device.name.clone(),
+                    Style::default()
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("{}/s", format_bytes(device.read_rate as u64, false)),
+                    Style::default().fg(Color::Blue),
+                ),
+                Span::styled(
+                    format!("{}/s", format_bytes(device.write_rate as u64, false)),
+                    Style::default().fg(Color::Green),
+                ),
+                Span::raw(format!("{:.0}", device.read_iops + device.write_iops)),
+                Span::raw(format!("{:.1}", device.await_ms)),
+                Span::raw(format!("{:.2}", device.queue_depth)),
+                Span::styled(
+                    format!("{:.1}%", device.util_percent),
+                    Style::default().fg(util_color).add_modifier(Modifier::BOLD),
+                ),
+            ]));
+
+            for (i, part) in device.partitions.iter().enumerate() {
+                let guide = if i + 1 == device.partitions.len() {
+                    "└─"
+                } else {
+                    "├─"
+                };
+                rows.push(
+                    Row::new(vec![
+                        format!(" {}{}", guide, part.name),
+                        format!("{}/s", format_bytes(part.read_rate as u64, false)),
+                        format!("{}/s", format_bytes(part.write_rate as u64, false)),
+                        format!("{:.0}", part.read_iops + part.write_iops),
+                        format!("{:.1}", part.await_ms),
+                        format!("{:.2}", part.queue_depth),
+                        format!("{:.1}%", part.util_percent),
+                    ])
+                    .style(Style::default().fg(Color::DarkGray)),
+                );
+            }
+        }
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(14),
+                Constraint::Length(13),
+                Constraint::Length(13),
+                Constraint::Length(6),
+                Constraint::Length(7),
+                Constraint::Length(6),
+                Constraint::Length(7),
+            ],
+        )
+        .header(
+            Row::new(vec![
+                "Device", "Read", "Write", "IOPS", "Await", "Queue", "Util",
+            ])
+            .style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+                    .add_modifier(Modifier::UNDERLINED),
+            ),
+        )
+        .block(
+            Block::default()
+                .title(vec![
+                    Span::styled("💿 ", Style::default().fg(Color::Magenta)),
+                    Span::styled("Disk I/O", Style::default().add_modifier(Modifier::BOLD)),
+                    Span::styled(
+                        " (await ms)",
+                        Style::default()
+                            .fg(Color::DarkGray)
+                            .add_modifier(Modifier::ITALIC),
+                    ),
+                ])
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .border_type(ratatui::widgets::BorderType::Rounded),
+        );
+
+        frame.render_widget(table, chunks[0]);
+
+        // Right: utilization history per disk, throughput history of the busiest disk
+        let graph_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[1]);
+
+        let util_data: Vec<(String, Vec<(f64, f64)>)> = devices
+            .iter()
+            .filter_map(|d| {
+                self.diskio_monitor.get_util_history(&d.name).map(|h| {
+                    let points = h.iter().enumerate().map(|(x, &y)| (x as f64, y)).collect();
+                    (format!("{} {:.0}%", d.name, d.util_percent), points)
+                })
+            })
+            .collect();
+
+        let util_datasets: Vec<Dataset> = util_data
+            .iter()
+            .enumerate()
+            .map(|(i, (name, data))| {
+                Dataset::default()
+                    .name(name.clone())
+                    .marker(symbols::Marker::Braille)
+                    .graph_type(ratatui::widgets::GraphType::Line)
+                    .style(Style::default().fg(COLORS[i % COLORS.len()]))
+                    .data(data)
+            })
+            .collect();
+
+        let util_chart = Chart::new(util_datasets)
+            .block(
+                Block::default()
+                    .title(Span::styled(
+                        "Busy %",
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .border_type(ratatui::widgets::BorderType::Rounded),
+            )
+            .x_axis(Axis::default().bounds([0.0, 60.0]))
+            .y_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([0.0, 100.0])
+                    .labels(vec![Span::raw("0"), Span::raw("50"), Span::raw("100")]),
+            )
+            .legend_position(Some(ratatui::widgets::LegendPosition::TopLeft))
+            .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)));
+
+        frame.render_widget(util_chart, graph_chunks[0]);
+
+        let busiest = devices.iter().max_by(|a, b| {
+            a.util_percent
+                .partial_cmp(&b.util_percent)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let (busiest_name, read_history, write_history) = busiest
+            .and_then(|d| {
+                self.diskio_monitor
+                    .get_rate_history(&d.name)
+                    .map(|(r, w)| (d.name.clone(), r, w))
+            })
+            .unwrap_or_default();
+
+        let max_rate = read_history
+            .iter()
+            .chain(write_history.iter())
+            .fold(1.0_f64, |a, &b| a.max(b));
+        let read_data: Vec<(f64, f64)> = read_history
+            .iter()
+            .enumerate()
+            .map(|(x, &y)| (x as f64, y))
+            .collect();
+        let write_data: Vec<(f64, f64)> = write_history
+            .iter()
+            .enumerate()
+            .map(|(x, &y)| (x as f64, y))
+            .collect();
+
+        let rate_chart = Chart::new(vec![
+            Dataset::default()
+                .name("Read")
+                .marker(symbols::Marker::Braille)
+                .graph_type(ratatui::widgets::GraphType::Line)
+                .style(Style::default().fg(Color::Blue))
+                .data(&read_data),
+            Dataset::default()
+                .name("Write")
+                .marker(symbols::Marker::Braille)
+                .graph_type(ratatui::widgets::GraphType::Line)
+                .style(Style::default().fg(Color::Green))
+                .data(&write_data),
+        ])
+        .block(
+            Block::default()
+                .title(vec![
+                    Span::styled("Throughput ", Style::default().add_modifier(Modifier::BOLD)),
+                    Span::styled(busiest_name, Style::default().fg(Color::Yellow)),
+                ])
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .border_type(ratatui::widgets::BorderType::Rounded),
+        )
+        .x_axis(Axis::default().bounds([0.0, 60.0]))
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds([0.0, max_rate])
+                .labels(vec![
+                    Span::raw("0"),
+                    Span::raw(format!("{}/s", format_bytes(max_rate as u64, false))),
+                ]),
+        )
+        .legend_position(Some(ratatui::widgets::LegendPosition::TopLeft));
+
+        frame.render_widget(rate_chart, graph_chunks[1]);
+    }
+
+    fn draw_process_detail(&self, frame: &mut Frame, area: Rect, monitor: &ProcessDetailMonitor) {
+        let block = |title: String| {
+            Block::default()
+                .title(Span::styled(
+                    title,
+                    Style::default().add_modifier(Modifier::BOLD),
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .border_type(ratatui::widgets::BorderType::Rounded)
+        };
+
+        let Some(detail) = monitor.detail() else {
+            let paragraph = Paragraph::new(vec![
+                Line::from(""),
+                Line::from(Span::styled(
+                    format!("Process {} has exited", monitor.pid()),
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                )),
+                Line::from(Span::styled(
+                    "Press Esc to return",
+                    Style::default().fg(Color::DarkGray),
+                )),
+            ])
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(block(format!(" 🔍 Process {} ", monitor.pid())));
+            frame.render_widget(paragraph, area);
+            return;
+        };
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(area);
+        let top = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+            .split(rows[0]);
+        let bottom = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+            .split(rows[1]);
+
+        // Summary
+        let label = |text: &str| {
+            Span::styled(
+                format!("{:<10}", text),
+                Style::default().fg(Color::DarkGray),
+            )
+        };
+        let value = |text: String| Span::styled(text, Style::default().fg(Color::White));
+        let started = detail
+            .start_time
+            .and_then(|secs| chrono::DateTime::from_timestamp(secs as i64, 0))
+            .map(|t| {
+                t.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_else(|| "?".to_string());
+        let or_unknown =
+            |v: &Option<String>| v.clone().unwrap_or_else(|| "? (no access)".to_string());
+
+        let mut info = vec![
             Line::from(vec![
-                Span::raw("  ["),
+                label("Command"),
                 Span::styled(
-                    bar,
-                    Style::default().fg(disk_color).add_modifier(Modifier::BOLD),
+                    if detail.cmdline.is_empty() {
+                        format!("[{}]", detail.name)
+                    } else {
+                        detail.cmdline.join(" ")
+                    },
+                    Style::default().fg(Color::Yellow),
                 ),
-                Span::raw("]"),
             ]),
-            Line::from(""),
+            Line::from(vec![label("Exe"), value(or_unknown(&detail.exe))]),
+            Line::from(vec![label("Cwd"), value(or_unknown(&detail.cwd))]),
             Line::from(vec![
-                Span::styled(
-                    "  ● ",
-                    Style::default().fg(disk_color).add_modifier(Modifier::BOLD),
+                label("State"),
+                value(format!(
+                    "{} ({})",
+                    detail.state,
+                    process_detail::state_name(detail.state)
+                )),
+                Span::raw("   "),
+                label("PPID"),
+                value(detail.ppid.to_string()),
+            ]),
+            Line::from(vec![label("Started"), value(started)]),
+            Line::from(vec![
+                label("Nice"),
+                value(detail.nice.to_string()),
+                Span::raw("   "),
+                label("Priority"),
+                value(detail.priority.to_string()),
+            ]),
+            Line::from(vec![
+                label("CPU"),
+                value(format!("{:.1}%", detail.cpu_percent)),
+                Span::raw("   "),
+                label("RSS"),
+                value(format_bytes(detail.rss, false)),
+            ]),
+            Line::from(vec![
+                label("Ctx sw"),
+                value(format!(
+                    "{} voluntary / {} involuntary",
+                    detail.voluntary_ctxt_switches, detail.nonvoluntary_ctxt_switches
+                )),
+            ]),
+            Line::from(vec![
+                label("Open fds"),
+                value(
+                    detail
+                        .fd_count
+                        .map(|n| n.to_string())
+                        .unwrap_or_else(|| "? (no access)".to_string()),
+                ),
+                Span::raw("   "),
+                label("Threads"),
+                value(detail.threads.len().to_string()),
+            ]),
+            Line::from(vec![
+                label("I/O"),
+                value(format!(
+                    "R {}/s  W {}/s  (total R {} / W {})",
+                    format_bytes(detail.read_rate as u64, false),
+                    format_bytes(detail.write_rate as u64, false),
+                    format_bytes(detail.read_total, false),
+                    format_bytes(detail.write_total, false)
+                )),
+            ]),
+        ];
+        for cgroup in &detail.cgroups {
+            info.push(Line::from(vec![label("Cgroup"), value(cgroup.clone())]));
+        }
+        info.push(Line::from(vec![
+            label("NS"),
+            value(detail.namespaces.join(" ")),
+        ]));
+
+        let summary = Paragraph::new(info)
+            .wrap(Wrap { trim: false })
+            .block(block(format!(" 🔍 {} ({}) ", detail.name, detail.pid)));
+        frame.render_widget(summary, top[0]);
+
+        // CPU and RSS history
+        let graph_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(top[1]);
+
+        let cpu_history = monitor.cpu_history();
+        let cpu_max = cpu_history.iter().fold(100.0_f64, |a, &b| a.max(b));
+        let cpu_data: Vec<(f64, f64)> = cpu_history
+            .iter()
+            .enumerate()
+            .map(|(x, &y)| (x as f64, y))
+            .collect();
+        let cpu_chart = Chart::new(vec![Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(ratatui::widgets::GraphType::Line)
+            .style(Style::default().fg(Color::Green))
+            .data(&cpu_data)])
+        .block(block(format!(" CPU {:.1}% ", detail.cpu_percent)))
+        .x_axis(Axis::default().bounds([0.0, 60.0]))
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds([0.0, cpu_max])
+                .labels(vec![Span::raw("0"), Span::raw(format!("{:.0}%", cpu_max))]),
+        );
+        frame.render_widget(cpu_chart, graph_chunks[0]);
+
+        let rss_history = monitor.rss_history();
+        let rss_max = rss_history.iter().copied().max().unwrap_or(0).max(1) as f64;
+        let rss_data: Vec<(f64, f64)> = rss_history
+            .iter()
+            .enumerate()
+            .map(|(x, &y)| (x as f64, y as f64))
+            .collect();
+        let rss_chart = Chart::new(vec![Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(ratatui::widgets::GraphType::Line)
+            .style(Style::default().fg(Color::Magenta))
+            .data(&rss_data)])
+        .block(block(format!(" RSS {} ", format_bytes(detail.rss, false))))
+        .x_axis(Axis::default().bounds([0.0, 60.0]))
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds([0.0, rss_max])
+                .labels(vec![
+                    Span::raw("0"),
+                    Span::raw(format_bytes(rss_max as u64, false)),
+                ]),
+        );
+        frame.render_widget(rss_chart, graph_chunks[1]);
+
+        // Threads, busiest first
+        let mut threads = detail.threads.clone();
+        threads.sort_by(|a, b| {
+            b.cpu_percent
+                .partial_cmp(&a.cpu_percent)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let thread_rows: Vec<Row> = threads
+            .iter()
+            .skip(self.detail_scroll.min(threads.len().saturating_sub(1)))
+            .map(|t| {
+                Row::new(vec![
+                    t.tid.to_string(),
+                    t.name.clone(),
+                    t.state.to_string(),
+                    format!("{:.1}%", t.cpu_percent),
+                ])
+            })
+            .collect();
+        let thread_table = Table::new(
+            thread_rows,
+            [
+                Constraint::Length(8),
+                Constraint::Min(12),
+                Constraint::Length(3),
+                Constraint::Length(7),
+            ],
+        )
+        .header(
+            Row::new(vec!["TID", "Thread", "S", "CPU"]).style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+                    .add_modifier(Modifier::UNDERLINED),
+            ),
+        )
+        .block(block(format!(" Threads ({}) ", threads.len())));
+        frame.render_widget(thread_table, bottom[0]);
+
+        // Limits, or the environment when toggled
+        let toggle_hint = vec![
+            Span::styled("[", Style::default().fg(Color::DarkGray)),
+            Span::styled("e", Style::default().fg(Color::Yellow)),
+            Span::styled("]", Style::default().fg(Color::DarkGray)),
+        ];
+        if self.detail_show_env {
+            let lines: Vec<Line> = detail
+                .environ
+                .iter()
+                .skip(
+                    self.detail_scroll
+                        .min(detail.environ.len().saturating_sub(1)),
+                )
+                .map(|var| match var.split_once('=') {
+                    Some((k, v)) => Line::from(vec![
+                        Span::styled(k.to_string(), Style::default().fg(Color::Cyan)),
+                        Span::styled("=", Style::default().fg(Color::DarkGray)),
+                        Span::raw(v.to_string()),
+                    ]),
+                    None => Line::from(var.clone()),
+                })
+                .collect();
+            let mut title = vec![Span::styled(
+                format!(" Environment ({}) ", detail.environ.len()),
+                Style::default().add_modifier(Modifier::BOLD),
+            )];
+            title.extend(toggle_hint);
+            title.push(Span::raw("Limits "));
+            let env = Paragraph::new(lines).block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .border_type(ratatui::widgets::BorderType::Rounded),
+            );
+            frame.render_widget(env, bottom[1]);
+        } else {
+            let limit_rows: Vec<Row> = detail
+                .limits
+                .iter()
+                .map(|l| {
+                    Row::new(vec![
+                        l.name.clone(),
+                        l.soft.clone(),
+                        l.hard.clone(),
+                        l.units.clone(),
+                    ])
+                })
+                .collect();
+            let mut title = vec![Span::styled(
+                " Limits ",
+                Style::default().add_modifier(Modifier::BOLD),
+            )];
+            title.extend(toggle_hint);
+            title.push(Span::raw("Environment "));
+            let limits = Table::new(
+                limit_rows,
+                [
+                    Constraint::Min(20),
+                    Constraint::Length(12),
+                    Constraint::Length(12),
+                    Constraint::Length(8),
+                ],
+            )
+            .header(
+                Row::new(vec!["Limit", "Soft", "Hard", "Units"]).style(
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD)
+                        .add_modifier(Modifier::UNDERLINED),
+                ),
+            )
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .border_type(ratatui::widgets::BorderType::Rounded),
+            );
+            frame.render_widget(limits, bottom[1]);
+        }
+    }
+
+    fn draw_user_summary(&self, frame: &mut Frame, area: Rect) {
+        let summary = self.process_monitor.user_summary();
+        let total_memory = self.memory_monitor.get_memory_data().3.max(1);
+
+        let rows: Vec<Row> = summary
+            .iter()
+            .enumerate()
+            .map(|(i, usage)| {
+                let style = if i % 2 == 0 {
+                    Style::default()
+                } else {
+                    Style::default().bg(Color::Rgb(20, 20, 30))
+                };
+                let cpu_color = if usage.cpu_usage > 100.0 {
+                    Color::Red
+                } else if usage.cpu_usage > 25.0 {
+                    Color::Yellow
+                } else {
+                    Color::Green
+                };
+                Row::new(vec![
+                    Span::raw(usage.user.clone()),
+                    Span::raw(usage.process_count.to_string()),
+                    Span::styled(
+                        format!("{:.1}%", usage.cpu_usage),
+                        Style::default().fg(cpu_color),
+                    ),
+                    Span::raw(format_bytes(usage.memory, false)),
+                    Span::raw(format!(
+                        "{:.1}%",
+                        usage.memory as f64 / total_memory as f64 * 100.0
+                    )),
+                ])
+                .style(style)
+            })
+            .collect();
+
+        let title = vec![
+            Span::styled("👤 ", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                "Usage by User ",
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("[{}] ", summary.len()),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled("[", Style::default().fg(Color::DarkGray)),
+            Span::styled("a", Style::default().fg(Color::Yellow)),
+            Span::styled("]", Style::default().fg(Color::DarkGray)),
+            Span::raw("Processes"),
+        ];
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Min(12),
+                Constraint::Length(7),
+                Constraint::Length(8),
+                Constraint::Length(11),
+                Constraint::Length(6),
+            ],
+        )
+        .header(
+            Row::new(vec!["User", "Procs", "CPU", "Memory", "Mem%"]).style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+                    .add_modifier(Modifier::UNDERLINED),
+            ),
+        )
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .border_type(ratatui::widgets::BorderType::Rounded),
+        );
+
+        frame.render_widget(table, area);
+    }
+
+    fn draw_exited(&self, frame: &mut Frame, area: Rect) {
+        let exits = self.process_monitor.exits();
+        let visible = (area.height as usize).saturating_sub(3);
+
+        let rows: Vec<Row> = self
+            .process_monitor
+            .recent_exits()
+            .take(visible)
+            .enumerate()
+            .map(|(i, p)| {
+                let mut style = if i % 2 == 0 {
+                    Style::default()
+                } else {
+                    Style::default().bg(Color::Rgb(20, 20, 30))
+                };
+                if p.short_lived {
+                    style = style.fg(Color::LightMagenta);
+                }
+                let status_style = match p.status {
+                    Some(ExitStatus::Code(0)) | None => Style::default(),
+                    Some(_) => Style::default().fg(Color::Red),
+                };
+                let exited_at: chrono::DateTime<chrono::Local> = p.exited_at.into();
+                Row::new(vec![
+                    Span::raw(exited_at.format("%H:%M:%S").to_string()),
+                    Span::raw(p.pid.to_string()),
+                    Span::raw(p.name.clone()),
+                    Span::raw(p.lifetime.map(format_cpu_time).unwrap_or_default()),
+                    Span::raw(p.cpu_time.map(format_cpu_time).unwrap_or_default()),
+                    Span::styled(
+                        p.status.map(|s| s.to_string()).unwrap_or_default(),
+                        status_style,
+                    ),
+                    Span::raw(p.command.clone()),
+                ])
+                .style(style)
+            })
+            .collect();
+
+        let source = if exits.uses_proc_events() {
+            "proc events"
+        } else {
+            "polling"
+        };
+        let title = vec![
+            Span::styled("⏏ ", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                "Recently Exited ",
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(
+                    "[{}] last {}s · {} · ",
+                    self.process_monitor.recent_exits().count(),
+                    exits.retention().as_secs(),
+                    source
                 ),
-                Span::styled(
-                    format!("{:>5.1}%", percent),
-                    Style::default().fg(disk_color).add_modifier(Modifier::BOLD),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled("short-lived ", Style::default().fg(Color::LightMagenta)),
+            Span::styled("[", Style::default().fg(Color::DarkGray)),
+            Span::styled("X", Style::default().fg(Color::Yellow)),
+            Span::styled("]", Style::default().fg(Color::DarkGray)),
+            Span::raw("Hide"),
+        ];
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(8),
+                Constraint::Length(7),
+                Constraint::Length(16),
+                Constraint::Length(9),
+                Constraint::Length(9),
+                Constraint::Length(6),
+                Constraint::Min(20),
+            ],
+        )
+        .header(
+            Row::new(vec![
+                "Exited", "PID", "Name", "Lifetime", "CPU Time", "Exit", "Command",
+            ])
+            .style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+                    .add_modifier(Modifier::UNDERLINED),
+            ),
+        )
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .border_type(ratatui::widgets::BorderType::Rounded),
+        );
+
+        frame.render_widget(table, area);
+    }
+
+    fn draw_group_summary(&self, frame: &mut Frame, area: Rect) {
+        let summary = self.process_monitor.group_summary();
+        let rate = |bytes_per_sec: f64| format!("{}/s", format_bytes(bytes_per_sec as u64, false));
+        // Same thresholds as the pressure panel
+        let pressure = |avg10: Option<f64>| match avg10 {
+            Some(avg) => {
+                let color = if avg >= 10.0 {
+                    Color::Red
+                } else if avg >= 1.0 {
+                    Color::Yellow
+                } else {
+                    Color::DarkGray
+                };
+                Span::styled(format!("{:>6.2}", avg), Style::default().fg(color))
+            }
+            None => Span::raw(format!("{:>6}", "-")),
+        };
+
+        let rows: Vec<Row> = summary
+            .iter()
+            .enumerate()
+            .map(|(i, usage)| {
+                let style = if i % 2 == 0 {
+                    Style::default()
+                } else {
+                    Style::default().bg(Color::Rgb(20, 20, 30))
+                };
+                let cpu_color = if usage.cpu_usage > 100.0 {
+                    Color::Red
+                } else if usage.cpu_usage > 25.0 {
+                    Color::Yellow
+                } else {
+                    Color::Green
+                };
+                let kind_color = match usage.kind {
+                    "container" => Color::LightBlue,
+                    "service" => Color::LightGreen,
+                    _ => Color::DarkGray,
+                };
+                let mut cells = vec![
+                    Span::raw(usage.label.clone()),
+                    Span::styled(usage.kind, Style::default().fg(kind_color)),
+                    Span::raw(usage.process_count.to_string()),
+                    Span::styled(
+                        format!("{:.1}%", usage.cpu_usage),
+                        Style::default().fg(cpu_color),
+                    ),
+                    Span::raw(format_bytes(usage.memory, false)),
+                    Span::raw(rate(usage.read_rate)),
+                    Span::raw(rate(usage.write_rate)),
+                ];
+                cells.extend(usage.pressure.map(pressure));
+                cells.push(Span::styled(
+                    usage.path.clone(),
+                    Style::default().fg(Color::DarkGray),
+                ));
+                Row::new(cells).style(style)
+            })
+            .collect();
+
+        let title = vec![
+            Span::styled("📦 ", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                "Containers & Services ",
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("[{}] ", summary.len()),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled("[", Style::default().fg(Color::DarkGray)),
+            Span::styled("g", Style::default().fg(Color::Yellow)),
+            Span::styled("]", Style::default().fg(Color::DarkGray)),
+            Span::raw("Processes"),
+            Span::styled(
+                " · Psi = % of time stalled, some 10s",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ];
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(28),
+                Constraint::Length(9),
+                Constraint::Length(6),
+                Constraint::Length(8),
+                Constraint::Length(11),
+                Constraint::Length(11),
+                Constraint::Length(11),
+                Constraint::Length(6),
+                Constraint::Length(6),
+                Constraint::Length(6),
+                Constraint::Min(20),
+            ],
+        )
+        .header(
+            Row::new(vec![
+                "Group", "Kind", "Procs", "CPU", "Memory", "Read", "Write", "PsiCPU", "PsiMem",
+                "PsiIO", "Cgroup",
+            ])
+            .style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+                    .add_modifier(Modifier::UNDERLINED),
+            ),
+        )
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .border_type(ratatui::widgets::BorderType::Rounded),
+        );
+
+        frame.render_widget(table, area);
+    }
+
+    fn draw_processes(&self, frame: &mut Frame, area: Rect) {
+        if self.show_user_summary {
+            self.draw_user_summary(frame, area);
+            return;
+        }
+        if self.show_group_summary {
+            self.draw_group_summary(frame, area);
+            return;
+        }
+
+        let processes = self.visible_processes();
+
+        let total_processes = processes.len();
+        let visible_count = (area.height as usize)
+            .saturating_sub(3)
+            .min(PROCESS_PAGE_SIZE);
+
+        // The list can shrink under the scroll offset (filter typed, processes exited)
+        let start_index = self.process_scroll.min(total_processes);
+        let end_index = (start_index + visible_count).min(total_processes);
+        let processes_slice = &processes[start_index..end_index];
+
+        let tree_view = self.process_monitor.is_tree_view();
+        let columns = &self.config.process.columns;
+        // Tree guides go on the name, or on the command line when the name is hidden
+        let tree_column = if columns.visible.contains(&ProcessColumn::Name) {
+            ProcessColumn::Name
+        } else {
+            ProcessColumn::Command
+        };
+        let width = |column: ProcessColumn| {
+            let width = columns.width(column);
+            if tree_view && column == tree_column {
+                width + 20
+            } else {
+                width
+            }
+        };
+        let (_, _, _, mem_total) = self.memory_monitor.get_memory_data();
+        let mut rows = Vec::with_capacity(processes_slice.len());
+
+        for (i, p) in processes_slice.iter().enumerate() {
+            let _cpu_color = if p.cpu_usage > 50.0 {
+                Color::Red
+            } else if p.cpu_usage > 25.0 {
+                Color::Yellow
+            } else {
+                Color::Green
+            };
+
+            let is_selected = Some(p.key()) == self.process_selected;
+            let is_tagged = self.process_tagged.contains(&p.key());
+            let mut style = if is_selected {
+                Style::default().bg(Color::Rgb(50, 50, 80)).fg(Color::White)
+            } else if i % 2 == 0 {
+                Style::default()
+            } else {
+                Style::default().bg(Color::Rgb(20, 20, 30))
+            };
+            if is_tagged {
+                style = style.fg(Color::Yellow).add_modifier(Modifier::BOLD);
+            } else if p.is_thread && !is_selected {
+                style = style.fg(Color::Rgb(150, 150, 170));
+            }
+
+            let mut cells = vec![match (is_selected, is_tagged) {
+                (true, _) => "▶".to_string(),
+                (false, true) => "●".to_string(),
+                (false, false) => " ".to_string(),
+            }];
+            cells.extend(columns.visible.iter().map(|&column| {
+                let text = self.process_cell(p, column, mem_total, width(column));
+                if column == tree_column {
+                    format!("{}{}", p.tree_prefix, text)
+                        .chars()
+                        .take(width(column) as usize)
+                        .collect()
+                } else {
+                    text
+                }
+            }));
+            let row = Row::new(cells).style(style);
+
+            rows.push(row);
+        }
+
+        let mut scroll_info = if total_processes > visible_count {
+            format!(" [{}-{}/{}] ", start_index + 1, end_index, total_processes)
+        } else {
+            format!(" [{}] ", total_processes)
+        };
+        if !self.process_tagged.is_empty() {
+            scroll_info.push_str(&format!("{} tagged ", self.process_tagged.len()));
+        }
+        if let Some(user) = &self.user_filter {
+            scroll_info.push_str(&format!("user:{} ", user));
+        }
+
+        let title = vec![
+            Span::styled("⚙ ", Style::default().fg(Color::Yellow)),
+            Span::styled("Processes ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(scroll_info, Style::default().fg(Color::DarkGray)),
+            Span::styled("[", Style::default().fg(Color::DarkGray)),
+            Span::styled("p", Style::default().fg(Color::Yellow)),
+            Span::styled("]", Style::default().fg(Color::DarkGray)),
+            Span::raw("PID "),
+            Span::styled("[", Style::default().fg(Color::DarkGray)),
+            Span::styled("c", Style::default().fg(Color::Yellow)),
+            Span::styled("]", Style::default().fg(Color::DarkGray)),
+            Span::raw("CPU "),
+            Span::styled("[", Style::default().fg(Color::DarkGray)),
+            Span::styled("m", Style::default().fg(Color::Yellow)),
+            Span::styled("]", Style::default().fg(Color::DarkGray)),
+            Span::raw("Mem "),
+            Span::styled("[", Style::default().fg(Color::DarkGray)),
+            Span::styled("t", Style::default().fg(Color::Yellow)),
+            Span::styled("]", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                "Tree ",
+                if tree_view {
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                },
+            ),
+            Span::styled("[", Style::default().fg(Color::DarkGray)),
+            Span::styled("k", Style::default().fg(Color::Yellow)),
+            Span::styled("]", Style::default().fg(Color::DarkGray)),
+            Span::raw("Signal "),
+            Span::styled("[", Style::default().fg(Color::DarkGray)),
+            Span::styled("U", Style::default().fg(Color::Yellow)),
+            Span::styled("]", Style::default().fg(Color::DarkGray)),
+            Span::raw("ser "),
+            Span::styled("[", Style::default().fg(Color::DarkGray)),
+            Span::styled("a", Style::default().fg(Color::Yellow)),
+            Span::styled("]", Style::default().fg(Color::DarkGray)),
+            Span::raw("By user "),
+            Span::styled("[", Style::default().fg(Color::DarkGray)),
+            Span::styled("g", Style::default().fg(Color::Yellow)),
+            Span::styled("]", Style::default().fg(Color::DarkGray)),
+            Span::raw("Groups "),
+            Span::styled("[", Style::default().fg(Color::DarkGray)),
+            Span::styled("C", Style::default().fg(Color::Yellow)),
+            Span::styled("]", Style::default().fg(Color::DarkGray)),
+            Span::raw("olumns"),
+        ];
+
+        let sort_order = self.process_monitor.sort_order();
+        let sort_arrow = if self.process_monitor.is_reverse() {
+            "▲"
+        } else {
+            "▼"
+        };
+        let mut header = vec![String::new()];
+        header.extend(columns.visible.iter().map(|&column| {
+            if SortOrder::from(column) == sort_order {
+                format!("{}{}", column.title(), sort_arrow)
+            } else {
+                column.title().to_string()
+            }
+        }));
+        let mut widths = vec![Constraint::Length(2)];
+        widths.extend(
+            columns
+                .visible
+                .iter()
+                .map(|&column| Constraint::Length(width(column))),
+        );
+
+        let table = Table::new(rows, widths)
+            .header(
+                Row::new(header).style(
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD)
+                        .add_modifier(Modifier::UNDERLINED),
                 ),
-                Span::raw("  Used: "),
-                Span::styled(
-                    format!("{:<10}", format_bytes(used, true)),
+            )
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .border_type(ratatui::widgets::BorderType::Rounded),
+            )
+            .row_highlight_style(
+                Style::default()
+                    .bg(Color::Rgb(50, 50, 80))
+                    .add_modifier(Modifier::BOLD),
+            );
+
+        frame.render_widget(table, area);
+    }
+
+    /// Text of one process table cell; thread rows leave per-process figures blank
+    fn process_cell(
+        &self,
+        p: &ProcessInfo,
+        column: ProcessColumn,
+        mem_total: u64,
+        width: u16,
+    ) -> String {
+        // Collapsed nodes show totals for the whole hidden subtree
+        let (cpu, memory) = if p.collapsed {
+            (p.subtree_cpu, p.subtree_memory)
+        } else {
+            (p.cpu_usage, p.memory)
+        };
+        let process_only = |text: String| if p.is_thread { String::new() } else { text };
+        let rate = |bytes_per_sec: f64| format!("{}/s", format_bytes(bytes_per_sec as u64, false));
+        let history = || {
+            self.process_monitor
+                .history(p.key())
+                .filter(|_| !p.is_thread)
+        };
+
+        match column {
+            ProcessColumn::Pid => p.pid.to_string(),
+            ProcessColumn::Ppid => p.ppid.map(|ppid| ppid.to_string()).unwrap_or_default(),
+            ProcessColumn::User => match &p.effective_user {
+                Some(effective) => format!("{}→{}", p.user, effective),
+                None => p.user.clone(),
+            },
+            ProcessColumn::State => p.state.clone(),
+            ProcessColumn::Nice => process_only(p.nice.to_string()),
+            ProcessColumn::Threads => process_only(p.threads.to_string()),
+            ProcessColumn::Cpu => format!("{:.1}%", cpu),
+            ProcessColumn::MemPercent => process_only(format!(
+                "{:.1}",
+                memory as f64 * 100.0 / mem_total.max(1) as f64
+            )),
+            // Threads share their process's memory
+            ProcessColumn::Rss => process_only(format_bytes(memory, false)),
+            ProcessColumn::Virt => process_only(format_bytes(p.virtual_memory, false)),
+            ProcessColumn::Shared => process_only(format_bytes(p.shared_memory, false)),
+            ProcessColumn::ReadRate => process_only(rate(p.read_rate)),
+            ProcessColumn::WriteRate => process_only(rate(p.write_rate)),
+            ProcessColumn::MinorFaults => process_only(format!("{:.0}", p.minor_fault_rate)),
+            ProcessColumn::MajorFaults => process_only(format!("{:.0}", p.major_fault_rate)),
+            ProcessColumn::StartTime => Self::format_start_time(p.start_time),
+            ProcessColumn::CpuTime => process_only(format_cpu_time(p.cpu_time)),
+            ProcessColumn::Name if p.collapsed => {
+                format!("{} [+{}]", p.name, p.hidden_descendants)
+            }
+            ProcessColumn::Name => p.name.clone(),
+            ProcessColumn::Command if p.is_thread => p.name.clone(),
+            ProcessColumn::Command => p.command.clone(),
+            ProcessColumn::Cgroup => process_only(p.cgroup.clone()),
+            ProcessColumn::Unit => process_only(p.unit.clone().unwrap_or_default()),
+            ProcessColumn::Container => p
+                .container_name
+                .clone()
+                .or_else(|| p.container.clone())
+                .unwrap_or_default(),
+            // CPU is drawn against 100% unless a multi-threaded process goes above it
+            ProcessColumn::CpuGraph => history()
+                .map(|h| self.sparkline(&h.cpu, 100.0, width))
+                .unwrap_or_default(),
+            ProcessColumn::MemGraph => history()
+                .map(|h| self.sparkline(&h.memory, 0.0, width))
+                .unwrap_or_default(),
+            ProcessColumn::IoGraph => history()
+                .map(|h| self.sparkline(&h.io, 0.0, width))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// One-line graph of the most recent samples, newest at the right edge, scaled to
+    /// the larger of `min_scale` and the series peak
+    fn sparkline(&self, values: &VecDeque<f64>, min_scale: f64, width: u16) -> String {
+        // Two samples per character; an even count keeps pairs aligned between refreshes
+        let take = (values.len().min(width as usize * 2)) & !1;
+        let recent: Vec<f64> = values.iter().skip(values.len() - take).copied().collect();
+        let scale = recent.iter().copied().fold(min_scale, f64::max);
+        if scale <= 0.0 {
+            return String::new();
+        }
+        let scaled: Vec<f64> = recent.iter().map(|v| v * 100.0 / scale).collect();
+        let line = GraphRenderer::new(width as usize, 1, self.graph_symbol, false)
+            .render(&scaled)
+            .remove(0);
+        format!("{:>width$}", line, width = width as usize)
+    }
+
+    /// HH:MM for processes started today, otherwise the month and day
+    fn format_start_time(epoch_secs: u64) -> String {
+        let Some(started) = chrono::DateTime::from_timestamp(epoch_secs as i64, 0) else {
+            return String::new();
+        };
+        let started = started.with_timezone(&chrono::Local);
+        if started.date_naive() == chrono::Local::now().date_naive() {
+            started.format("%H:%M").to_string()
+        } else {
+            started.format("%b %d").to_string()
+        }
+    }
+
+    fn draw_column_editor(&self, frame: &mut Frame, area: Rect, editor: &ColumnEditor) {
+        let popup_area = Self::centered_rect(50, 70, area);
+        frame.render_widget(ratatui::widgets::Clear, popup_area);
+
+        let columns = &self.config.process.columns;
+        let sort_order = self.process_monitor.sort_order();
+        let mut text: Vec<Line> = Self::column_order(columns)
+            .into_iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let on = columns.visible.contains(&column);
+                let style = if i == editor.cursor {
                     Style::default()
+                        .bg(Color::Rgb(50, 50, 80))
                         .fg(Color::White)
-                        .add_modifier(Modifier::BOLD),
-                ),
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+                Line::from(vec![
+                    Span::styled(
+                        if on { "[x] " } else { "[ ] " },
+                        style.fg(if on { Color::Green } else { Color::DarkGray }),
+                    ),
+                    Span::styled(format!("{:<10}", column.title()), style),
+                    Span::styled(format!("{:<24}", column.description()), style),
+                    Span::styled(
+                        format!("{:>4}", columns.width(column)),
+                        style.fg(Color::Cyan),
+                    ),
+                    Span::styled(
+                        if SortOrder::from(column) == sort_order {
+                            "  sort"
+                        } else {
+                            ""
+                        },
+                        style.fg(Color::Yellow),
+                    ),
+                ])
+            })
+            .collect();
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(
+            "↑↓ select, Space show/hide, </> move, ←→ width, s sort, Enter/Esc save & close",
+            Style::default().fg(Color::DarkGray),
+        )));
+
+        let paragraph = Paragraph::new(text).block(
+            Block::default()
+                .title(" ▦ Process Columns ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .border_type(ratatui::widgets::BorderType::Thick)
+                .style(Style::default().bg(Color::Rgb(30, 30, 20))),
+        );
+        frame.render_widget(paragraph, popup_area);
+    }
+
+    fn target_line<'a>(&self, targets: &'a [(ProcessKey, String)]) -> Line<'a> {
+        let mut line = match targets {
+            [(key, name)] => Line::from(vec![
+                Span::raw("PID: "),
+                Span::styled(key.pid.to_string(), Style::default().fg(Color::Yellow)),
+                Span::raw("  Name: "),
+                Span::styled(name, Style::default().fg(Color::Cyan)),
             ]),
-            Line::from(vec![
-                Span::styled("  ○ ", Style::default().fg(Color::Green)),
-                Span::raw("Free:  "),
+            targets => Line::from(vec![
                 Span::styled(
-                    format!("{:<10}", format_bytes(available, true)),
-                    Style::default().fg(Color::Green),
+                    targets.len().to_string(),
+                    Style::default().fg(Color::Yellow),
                 ),
-                Span::raw("  Total: "),
-                Span::styled(format_bytes(total, true), Style::default().fg(Color::Cyan)),
+                Span::raw(" tagged processes"),
             ]),
-            Line::from(""),
+        };
+        let hidden = self.hidden_tagged_count();
+        if hidden > 0 {
+            line.push_span(Span::styled(
+                format!("  ({} hidden by the filter, skipped)", hidden),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        line
+    }
+
+    fn draw_priority_menu(&self, frame: &mut Frame, area: Rect, menu: &PriorityMenu) {
+        let popup_area = Self::centered_rect(40, 35, area);
+        frame.render_widget(ratatui::widgets::Clear, popup_area);
+
+        let fields = [
+            ("Nice", menu.nice.to_string()),
+            ("I/O class", menu.io.class.name().to_string()),
+            ("I/O level", menu.io.level.to_string()),
         ];
+        let mut text = vec![self.target_line(&menu.targets), Line::from("")];
+        for (i, (label, value)) in fields.iter().enumerate() {
+            let style = if i == menu.field {
+                Style::default()
+                    .bg(Color::Rgb(50, 50, 80))
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            // Changed fields are marked; the others keep each target's own value
+            let marker = if menu.edited[i] { "*" } else { " " };
+            text.push(Line::from(vec![
+                Span::styled(format!(" {:<10}", label), style.fg(Color::Cyan)),
+                Span::styled(format!(" ◀ {:^12} ▶ ", value), style),
+                Span::styled(marker, style.fg(Color::Yellow)),
+            ]));
+        }
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(
+            "↑↓ field, ←→ change, Enter apply changed fields, other key cancels",
+            Style::default().fg(Color::DarkGray),
+        )));
 
-        let paragraph = Paragraph::new(lines).block(
+        let paragraph = Paragraph::new(text).block(
             Block::default()
-                .title(vec![
-                    Span::styled("💿 ", Style::default().fg(Color::Magenta)),
-                    Span::styled("Storage", Style::default().add_modifier(Modifier::BOLD)),
-                ])
+                .title(" ⚖ Priority ")
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan))
-                .border_type(ratatui::widgets::BorderType::Rounded),
+                .border_style(Style::default().fg(Color::Yellow))
+                .border_type(ratatui::widgets::BorderType::Thick)
+                .style(Style::default().bg(Color::Rgb(30, 30, 20))),
         );
-
-        frame.render_widget(paragraph, area);
+        frame.render_widget(paragraph, popup_area);
     }
 
-    fn draw_processes(&self, frame: &mut Frame, area: Rect) {
-        let mut processes = self.process_monitor.get_sorted_processes();
+    fn draw_affinity_menu(&self, frame: &mut Frame, area: Rect, menu: &AffinityMenu) {
+        let popup_area = Self::centered_rect(60, 60, area);
+        frame.render_widget(ratatui::widgets::Clear, popup_area);
 
-        // Apply filter if active
-        if let Some(ref regex) = self.process_filter_regex {
-            processes.retain(|p| regex.is_match(&p.name));
-        }
+        let cores = self.cpu_monitor.cores();
+        let columns = ((popup_area.width.saturating_sub(2)) / 16).max(1) as usize;
 
-        let total_processes = processes.len();
-        let visible_count = (area.height as usize).saturating_sub(3).min(20);
+        let mut text = vec![self.target_line(&menu.targets), Line::from("")];
+        for (row, chunk) in menu.cpus.chunks(columns).enumerate() {
+            let spans: Vec<Span> = chunk
+                .iter()
+                .enumerate()
+                .flat_map(|(offset, &(cpu, on))| {
+                    let usage = cores
+                        .iter()
+                        .find(|c| c.id == cpu)
+                        .map(|c| c.usage)
+                        .unwrap_or(0.0);
+                    let style = if row * columns + offset == menu.cursor {
+                        Style::default()
+                            .bg(Color::Rgb(50, 50, 80))
+                            .fg(Color::White)
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default()
+                    };
+                    vec![
+                        Span::styled(
+                            if on { "[x]" } else { "[ ]" },
+                            style.fg(if on { Color::Green } else { Color::DarkGray }),
+                        ),
+                        Span::styled(format!(" cpu{:<3}{:>4.0}%", cpu, usage), style),
+                        Span::raw(" "),
+                    ]
+                })
+                .collect();
+            text.push(Line::from(spans));
+        }
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(
+            "←→ move, Space toggle, a all/none, Enter apply, any other key cancels",
+            Style::default().fg(Color::DarkGray),
+        )));
 
-        let end_index = (self.process_scroll + visible_count).min(total_processes);
-        let processes_slice = &processes[self.process_scroll..end_index];
+        let paragraph = Paragraph::new(text).block(
+            Block::default()
+                .title(" 📌 CPU Affinity ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .border_type(ratatui::widgets::BorderType::Thick)
+                .style(Style::default().bg(Color::Rgb(30, 30, 20))),
+        );
+        frame.render_widget(paragraph, popup_area);
+    }
 
-        let mut rows = Vec::with_capacity(processes_slice.len());
+    fn draw_signal_menu(&self, frame: &mut Frame, area: Rect, menu: &SignalMenu) {
+        let popup_area = Self::centered_rect(50, 50, area);
+        frame.render_widget(ratatui::widgets::Clear, popup_area);
 
-        for (i, p) in processes_slice.iter().enumerate() {
-            let _cpu_color = if p.cpu_usage > 50.0 {
-                Color::Red
-            } else if p.cpu_usage > 25.0 {
-                Color::Yellow
-            } else {
-                Color::Green
-            };
+        let mut text = vec![self.target_line(&menu.targets), Line::from("")];
 
-            let is_selected = Some(self.process_scroll + i) == self.process_selected;
-            let style = if is_selected {
-                Style::default().bg(Color::Rgb(50, 50, 80)).fg(Color::White)
-            } else if i % 2 == 0 {
+        for (i, signal) in ProcessSignal::all_signals().iter().enumerate() {
+            let style = if i == menu.selected {
                 Style::default()
+                    .bg(Color::Rgb(50, 50, 80))
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD)
             } else {
-                Style::default().bg(Color::Rgb(20, 20, 30))
+                Style::default()
             };
-
-            let row = Row::new(vec![
-                if is_selected {
-                    "▶".to_string()
-                } else {
-                    " ".to_string()
-                },
-                p.pid.to_string(),
-                p.name.chars().take(20).collect::<String>(),
-                format!("{:.1}%", p.cpu_usage),
-                format_bytes(p.memory, false),
-            ])
-            .style(style);
-
-            rows.push(row);
+            text.push(Line::from(vec![
+                Span::styled(format!(" {} ", i + 1), Style::default().fg(Color::Yellow)),
+                Span::styled(format!("{:<10}", signal.name()), style.fg(Color::Red)),
+                Span::styled(format!(" {}", signal.description()), style),
+            ]));
         }
 
-        let scroll_info = if total_processes > visible_count {
-            format!(
-                " [{}-{}/{}] ",
-                self.process_scroll + 1,
-                end_index,
-                total_processes
-            )
-        } else {
-            format!(" [{}] ", total_processes)
-        };
-
-        let title = vec![
-            Span::styled("⚙ ", Style::default().fg(Color::Yellow)),
-            Span::styled("Processes ", Style::default().add_modifier(Modifier::BOLD)),
-            Span::styled(scroll_info, Style::default().fg(Color::DarkGray)),
-            Span::styled("[", Style::default().fg(Color::DarkGray)),
-            Span::styled("p", Style::default().fg(Color::Yellow)),
-            Span::styled("]", Style::default().fg(Color::DarkGray)),
-            Span::raw("PID "),
-            Span::styled("[", Style::default().fg(Color::DarkGray)),
-            Span::styled("c", Style::default().fg(Color::Yellow)),
-            Span::styled("]", Style::default().fg(Color::DarkGray)),
-            Span::raw("CPU "),
-            Span::styled("[", Style::default().fg(Color::DarkGray)),
-            Span::styled("m", Style::default().fg(Color::Yellow)),
-            Span::styled("]", Style::default().fg(Color::DarkGray)),
-            Span::raw("Mem "),
-            Span::styled("[", Style::default().fg(Color::DarkGray)),
-            Span::styled("k", Style::default().fg(Color::Yellow)),
-            Span::styled("]", Style::default().fg(Color::DarkGray)),
-            Span::raw("Kill"),
-        ];
+        text.push(Line::from(""));
+        text.push(Line::from(vec![Span::styled(
+            "↑↓ select, Enter/1-9 send, any other key cancels",
+            Style::default().fg(Color::DarkGray),
+        )]));
 
-        let table = Table::new(
-            rows,
-            [
-                Constraint::Length(2),
-                Constraint::Length(7),
-                Constraint::Length(20),
-                Constraint::Length(7),
-                Constraint::Length(10),
-            ],
-        )
-        .header(
-            Row::new(vec!["", "PID", "Process", "CPU", "Memory"]).style(
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD)
-                    .add_modifier(Modifier::UNDERLINED),
-            ),
-        )
-        .block(
+        let paragraph = Paragraph::new(text).block(
             Block::default()
-                .title(title)
+                .title(" ⚠ Send Signal ")
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan))
-                .border_type(ratatui::widgets::BorderType::Rounded),
-        )
-        .row_highlight_style(
-            Style::default()
-                .bg(Color::Rgb(50, 50, 80))
-                .add_modifier(Modifier::BOLD),
+                .border_style(Style::default().fg(Color::Red))
+                .border_type(ratatui::widgets::BorderType::Thick)
+                .style(Style::default().bg(Color::Rgb(30, 20, 20))),
         );
 
-        frame.render_widget(table, area);
-
-        // Show kill confirmation dialog
-        if self.show_kill_confirm {
-            self.draw_kill_confirm(frame, area);
-        }
-    }
-
-    fn draw_kill_confirm(&self, frame: &mut Frame, area: Rect) {
-        let popup_area = Self::centered_rect(40, 20, area);
-
-        if let Some(index) = self.process_selected {
-            let processes = self.process_monitor.get_sorted_processes();
-            if let Some(process) = processes.get(index) {
-                let text = vec![
-                    Line::from(""),
-                    Line::from(vec![Span::styled(
-                        "Kill process?",
-                        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-                    )]),
-                    Line::from(""),
-                    Line::from(vec![
-                        Span::raw("PID: "),
-                        Span::styled(
-                            format!("{}", process.pid),
-                            Style::default().fg(Color::Yellow),
-                        ),
-                    ]),
-                    Line::from(vec![
-                        Span::raw("Name: "),
-                        Span::styled(&process.name, Style::default().fg(Color::Cyan)),
-                    ]),
-                    Line::from(""),
-                    Line::from(vec![
-                        Span::styled("Press ", Style::default().fg(Color::DarkGray)),
-                        Span::styled(
-                            "Y",
-                            Style::default()
-                                .fg(Color::Green)
-                                .add_modifier(Modifier::BOLD),
-                        ),
-                        Span::styled(
-                            " to confirm, any other key to cancel",
-                            Style::default().fg(Color::DarkGray),
-                        ),
-                    ]),
-                ];
-
-                let paragraph = Paragraph::new(text)
-                    .block(
-                        Block::default()
-                            .title(" ⚠ Confirm ")
-                            .borders(Borders::ALL)
-                            .border_style(Style::default().fg(Color::Red))
-                            .border_type(ratatui::widgets::BorderType::Thick)
-                            .style(Style::default().bg(Color::Rgb(30, 20, 20))),
-                    )
-                    .alignment(ratatui::layout::Alignment::Center);
-
-                frame.render_widget(paragraph, popup_area);
-            }
-        }
+        frame.render_widget(paragraph, popup_area);
     }
 
     #[allow(dead_code)]
@@ -1922,7 +5121,21 @@ impl App {
             ),
         ]);
 
-        if !self.process_filter.is_empty() {
+        if let Some(message) = &self.status_message {
+            if message.shown_at.elapsed() < STATUS_MESSAGE_DURATION {
+                let color = if message.is_error {
+                    Color::Red
+                } else {
+                    Color::Green
+                };
+                footer_spans.extend(vec![
+                    Span::raw(" │ "),
+                    Span::styled(&message.text, Style::default().fg(color)),
+                ]);
+            }
+        }
+
+        if self.filter_input.is_some() || !self.process_filter.is_empty() {
             footer_spans.extend(vec![
                 Span::raw(" │ "),
                 Span::styled("Filter: ", Style::default().fg(Color::DarkGray)),
@@ -1933,6 +5146,20 @@ impl App {
                         .add_modifier(Modifier::BOLD),
                 ),
             ]);
+            if self.filter_input.is_some() {
+                footer_spans.push(Span::styled(
+                    "█",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::SLOW_BLINK),
+                ));
+            }
+            if let Some(error) = &self.filter_error {
+                footer_spans.extend(vec![
+                    Span::raw("  "),
+                    Span::styled(format!("✗ {}", error), Style::default().fg(Color::Red)),
+                ]);
+            }
         }
 
         let footer_text = vec![Line::from(footer_spans)];
@@ -2023,7 +5250,7 @@ impl App {
                         .fg(Color::Green)
                         .add_modifier(Modifier::BOLD),
                 ),
-                Span::raw("→ Navigate/scroll process list"),
+                Span::raw("→ Move selection in process list"),
             ]),
             Line::from(vec![
                 Span::styled(
@@ -2050,7 +5277,124 @@ impl App {
                         .fg(Color::Green)
                         .add_modifier(Modifier::BOLD),
                 ),
-                Span::raw("→ Kill selected process (with confirm)"),
+                Span::raw("→ Send signal to selected/tagged processes"),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    "    Enter           ",
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("→ Process details (e: environment, Esc: back)"),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    "    x / u           ",
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("→ Tag/untag selected process / untag all"),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    "    n / N           ",
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("→ Renice selected/tagged +1 / -1"),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    "    r / A           ",
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("→ Set nice & I/O priority / CPU affinity"),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    "    y               ",
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("→ Copy selected/tagged PIDs to clipboard"),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    "    U / a           ",
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("→ Cycle user filter / usage by user"),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    "    g               ",
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("→ Usage by container / systemd service"),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    "    t               ",
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("→ Toggle process tree view"),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    "    H               ",
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("→ Show/hide threads of selected process"),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    "    K / S           ",
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("→ Show/hide kernel threads / rtop itself"),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    "    X               ",
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("→ Show/hide recently exited processes (Processes page)"),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    "    C / < >         ",
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("→ Edit columns / sort by previous/next column"),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    "    ←/→             ",
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("→ Collapse/expand selected subtree (tree view)"),
             ]),
             Line::from(vec![
                 Span::styled(
@@ -2059,7 +5403,16 @@ impl App {
                         .fg(Color::Green)
                         .add_modifier(Modifier::BOLD),
                 ),
-                Span::raw("→ Filter processes (regex)"),
+                Span::raw("→ Filter, e.g. user:pg cpu>10 cmd:/--conf/ !name:kworker"),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    "                    ",
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("  (↑/↓ history, Ctrl+U clear, Esc cancel, Backspace clears)"),
             ]),
             Line::from(""),
             Line::from(vec![Span::styled(
@@ -2096,6 +5449,24 @@ impl App {
                 ),
                 Span::raw("→ Sort by Memory usage"),
             ]),
+            Line::from(vec![
+                Span::styled(
+                    "    s               ",
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("→ Cycle storage sort (mount/size/use%/free)"),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    "    i               ",
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("→ Cycle/pin network interface (Network page)"),
+            ]),
             Line::from(""),
             Line::from(vec![Span::styled(
                 "  Features:",
@@ -2114,7 +5485,7 @@ impl App {
             Line::from(vec![Span::raw("    • System info: uptime, load average")]),
             Line::from(vec![Span::raw("    • Export to JSON/CSV formats")]),
             Line::from(vec![Span::raw(
-                "    • Regex filtering and multi-page navigation",
+                "    • Query filtering and multi-page navigation",
             )]),
             Line::from(""),
             Line::from(vec![
@@ -18,6 +18,84 @@ pub fn format_bytes(bytes: u64, decimal: bool) -> String {
     format!("{:.2} {}", value, units[exp])
 }
 
+/// CPU time the way top's TIME+ column shows it: M:SS.hh, or H:MM:SS past an hour
+pub fn format_cpu_time(seconds: f64) -> String {
+    let hundredths = (seconds.max(0.0) * 100.0) as u64;
+    let total_secs = hundredths / 100;
+    if total_secs >= 3600 {
+        format!(
+            "{}:{:02}:{:02}",
+            total_secs / 3600,
+            total_secs / 60 % 60,
+            total_secs % 60
+        )
+    } else {
+        format!(
+            "{}:{:02}.{:02}",
+            total_secs / 60,
+            total_secs % 60,
+            hundredths % 100
+        )
+    }
+}
+
+/// Shell-style glob match supporting `*` (any run) and `?` (any single char)
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            // Let the last '*' swallow one more character and retry
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Standard base64 with padding (used for the OSC 52 clipboard escape)
+pub fn base64_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+
+        out.push(ALPHABET[(n >> 18) as usize & 63] as char);
+        out.push(ALPHABET[(n >> 12) as usize & 63] as char);
+        out.push(if chunk.len() > 1 {
+            ALPHABET[(n >> 6) as usize & 63] as char
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            ALPHABET[n as usize & 63] as char
+        } else {
+            '='
+        });
+    }
+    out
+}
+
 pub const COLORS: [ratatui::style::Color; 6] = [
     ratatui::style::Color::Magenta,
     ratatui::style::Color::Cyan,
@@ -69,6 +147,35 @@ mod tests {
         assert_eq!(format_bytes(2359296, false), "2.25 MiB");
     }
 
+    #[test]
+    fn test_format_cpu_time() {
+        assert_eq!(format_cpu_time(0.0), "0:00.00");
+        assert_eq!(format_cpu_time(75.5), "1:15.50");
+        assert_eq!(format_cpu_time(3599.99), "59:59.99");
+        assert_eq!(format_cpu_time(3600.0 * 26.0 + 61.0), "26:01:01");
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("veth*", "veth1a2b3c"));
+        assert!(glob_match("eth?", "eth0"));
+        assert!(glob_match("*", "anything"));
+        assert!(glob_match("br-*-x", "br-1234-x"));
+        assert!(glob_match("lo", "lo"));
+        assert!(!glob_match("lo", "lo0"));
+        assert!(!glob_match("eth?", "eth10"));
+        assert!(!glob_match("docker*", "br-docker"));
+    }
+
+    #[test]
+    fn test_base64_encode() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"1234 5678"), "MTIzNCA1Njc4");
+    }
+
     #[test]
     fn test_colors_array() {
         assert_eq!(COLORS.len(), 6);